@@ -6,7 +6,15 @@
 
 
 use backend::elements::area_chart::*;
-use backend::traits::Graphable;
+use backend::elements::axis::Axis;
+use backend::elements::extras::Extras;
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::signal::Signal;
+use backend::elements::transform::Transform;
+use backend::elements::general::{Interpolation, Orientation, SCHEMA};
+use backend::general::Numeric;
+use backend::traits::{Cartesian, Graphable};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 /// AreaChart is the primary struct which describes a Area Chart, as by the vega specification
 /// # Example
@@ -19,7 +27,6 @@ use backend::traits::Graphable;
 ///         a.add_data(i, i*i);
 ///     }
 ///```
-#[derive(Serialize)]
 pub struct AreaChart {
     identifier: String,
     description: String,
@@ -27,17 +34,18 @@ pub struct AreaChart {
     height: u32,
     padding: u32,
 
-    signals: Vec<AreaChartSignal>,
+    signals: Vec<Signal>,
 
     data: Vec<AreaChartData>,
-    scales: Vec<AreaChartScale>,
-    axes: Vec<AreaChartAxis>,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
     marks: Vec<AreaChartMark>,
+    extras: Extras,
 }
 
 impl AreaChart {
     pub fn new() -> AreaChart {
-        AreaChart {
+        let mut a = AreaChart {
             identifier: String::from("area_chart"),
             description: String::from("Area Chart"),
             width: 400,
@@ -45,16 +53,102 @@ impl AreaChart {
 
             padding: 5,
 
-            signals: vec![AreaChartSignal::default()],
-            scales: vec![AreaChartScale::default_x(), AreaChartScale::default_y()],
+            signals: vec![Signal::interpolation(Interpolation::Monotone)],
+            scales: vec![
+                Scale::new("xscale", ScaleType::Linear, Domain::field("table", "u"), "width"),
+                Scale::new("yscale", ScaleType::Linear, Domain::field("table", "v"), "height"),
+            ],
             data: vec![AreaChartData::default()],
-            axes: vec![AreaChartAxis::x_axis(), AreaChartAxis::y_axis()],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "xscale"),
+                Axis::new(Orientation::Left, "yscale"),
+            ],
             marks: vec![AreaChartMark::default()],
+            extras: Extras::new(),
+        };
+        a.scales[0].include_zero(false);
+        a.scales[1].include_zero(true);
+        a
+    }
+
+    /// Adds a point at u along the x axis, with a height of v. Both can be integers or
+    /// floating point numbers.
+    pub fn add_data<U: Numeric, V: Numeric>(&mut self, u: U, v: V) {
+        self.data[0].add_data(u.as_f64(), v.as_f64());
+    }
+
+    /// Leaves a gap in the area at u, e.g. where a measurement is missing
+    pub fn add_gap<U: Numeric>(&mut self, u: U) {
+        self.data[0].add_gap(u.as_f64());
+    }
+
+    /// Adds every (u, v) point of the iterator, as `add_data` would
+    pub fn add_data_iter<I, U, V>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (U, V)>,
+        U: Numeric,
+        V: Numeric,
+    {
+        let iter = iter.into_iter();
+        self.data[0].reserve(iter.size_hint().0);
+        for (u, v) in iter {
+            self.add_data(u, v);
         }
     }
 
-    pub fn add_data(&mut self, u: i32, v: i32) {
-        self.data[0].add_data(u, v);
+    /// Reduces the area to at most `n_points` of the points added so far, picking the ones
+    /// that preserve its shape best, like `LineChart::downsample_to`
+    pub fn downsample_to(&mut self, n_points: usize) {
+        self.data[0].downsample(n_points);
+    }
+
+    /// Sets how the area is drawn in between the data points, monotone by default. The
+    /// interpolation can still be changed through the menu below the chart.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::area_chart::AreaChart;
+    ///    use gust::backend::elements::general::Interpolation;
+    ///
+    ///    let mut a = AreaChart::new();
+    ///    a.set_interpolation(Interpolation::StepAfter);
+    /// ```
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.signals[0] = Signal::interpolation(interpolation);
+    }
+
+    /// Sets the opacity of the area, from 0 for invisible to 1 for opaque, which is the
+    /// default. While the area is hovered, half of it is used.
+    pub fn set_fill_opacity(&mut self, opacity: f64) {
+        self.marks[0].set_fill_opacity(opacity);
+    }
+
+    /// Outlines the area in the given color, see `set_stroke_width`
+    pub fn set_stroke_color(&mut self, color: &str) {
+        self.marks[0].set_stroke_color(color);
+    }
+
+    /// Sets the width in pixels of the outline of the area
+    pub fn set_stroke_width(&mut self, width: f64) {
+        self.marks[0].set_stroke_width(width);
+    }
+}
+
+impl Serialize for AreaChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("area_chart", 9)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields_with(&mut s, &self.signals)?;
+        s.end()
     }
 }
 
@@ -66,4 +160,49 @@ impl Graphable for AreaChart {
     fn get_identifier(&self) -> &str {
         &self.identifier
     }
+    /// set_color sets the fill color of the area
+    fn set_color(&mut self, color: &str) {
+        self.marks[0].set_color(color);
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for AreaChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
 }
@@ -3,9 +3,27 @@
  *  Author: Samuel Resendez
  */
 use backend::elements::bar_chart::*;
-use backend::traits::Graphable;
+use backend::elements::axis::Axis;
+use backend::elements::extras::Extras;
+use backend::elements::overlay::{self, ErrorBar, Plot, ValueLabels};
+use backend::elements::predicate::Predicate;
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::{Orientation, SCHEMA};
+use backend::general::Numeric;
+use backend::traits::{Cartesian, Graphable};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
+/// The bars of a bar chart, which overlays are drawn against
+const PLOT: Plot = Plot {
+    data: "table",
+    x: "category",
+    y: "amount",
+    series: None,
+    x_scale: "xscale",
+    y_scale: "yscale",
+    color_scale: None,
+};
 
 pub struct BarChart {
     identifier: String,
@@ -15,14 +33,16 @@ pub struct BarChart {
     padding: i32,
 
     data: Vec<BarChartData>,
-    scales: Vec<BarChartScale>,
-    axes: Vec<BarChartAxis>,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
     marks: Vec<BarChartMark>,
+    error_bars: bool,
+    extras: Extras,
 }
 
 impl BarChart {
     pub fn new() -> BarChart {
-        BarChart {
+        let mut b = BarChart {
             identifier: String::from("barchart"),
             description: String::from("A barchart"),
             width: 500,
@@ -31,18 +51,91 @@ impl BarChart {
 
             data: vec![BarChartData::new()],
             scales: vec![
-                BarChartScale::create_xscale(),
-                BarChartScale::create_yscale(),
+                Scale::new(
+                    "xscale",
+                    ScaleType::Band,
+                    Domain::field("table", "category"),
+                    "width",
+                ),
+                Scale::new(
+                    "yscale",
+                    ScaleType::Linear,
+                    Domain::field("table", "amount"),
+                    "height",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "xscale"),
+                Axis::new(Orientation::Left, "yscale"),
             ],
-            axes: vec![BarChartAxis::create_xaxis(), BarChartAxis::create_yaxis()],
             marks: vec![BarChartMark::create_mark()],
+            error_bars: false,
+            extras: Extras::new(),
+        };
+        for scale in &mut b.scales {
+            scale.set_padding(0.05);
         }
+        b
     }
     /// Bar Chart accept data in the following format:
-    /// { String, Integer }, which represent the category (aka Bar), as well as the value of that bar
+    /// { String, Number }, which represent the category (aka Bar), as well as the value of that bar
     /// (i.e. it's height).
-    pub fn add_data(&mut self, category: String, amount: i32) {
-        self.data[0].add_data(category, amount);
+    pub fn add_data<T: Numeric>(&mut self, category: String, amount: T) {
+        self.data[0].add_data(category, amount.as_f64());
+    }
+
+    /// Adds a bar for every (category, amount) pair, e.g. straight from a vector or a map
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use std::collections::BTreeMap;
+    ///
+    ///    let mut sales = BTreeMap::new();
+    ///    sales.insert(String::from("North"), 12.5);
+    ///    sales.insert(String::from("South"), 7.0);
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.add_data_iter(sales);
+    /// ```
+    pub fn add_data_iter<I, T>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (String, T)>,
+        T: Numeric,
+    {
+        let iter = iter.into_iter();
+        self.data[0].reserve(iter.size_hint().0);
+        for (category, amount) in iter {
+            self.add_data(category, amount);
+        }
+    }
+
+    /// Adds a bar like `add_data`, with an error bar showing the uncertainty of its amount
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::elements::overlay::ErrorBar;
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.add_data_with_error(String::from("A"), 20, ErrorBar::Symmetric(2.5));
+    ///    b.add_data_with_error(String::from("B"), 35.5, ErrorBar::Range(30.0, 37.0));
+    /// ```
+    pub fn add_data_with_error<T>(&mut self, category: String, amount: T, error: ErrorBar)
+    where
+        T: Numeric,
+    {
+        let amount = amount.as_f64();
+        let (lo, hi) = error.bounds(amount);
+        self.data[0].add_data_with_error(category, amount, lo, hi);
+        if !self.error_bars {
+            self.error_bars = true;
+            overlay::add_error_bars(&mut self.extras, &PLOT);
+        }
+    }
+
+    /// Prints the amount of every bar next to it, e.g. `show_values(LabelPosition::OutsideEnd)`
+    /// for labels on top of the bars, see `ValueLabels` for formatting them
+    pub fn show_values<L: Into<ValueLabels>>(&mut self, labels: L) {
+        overlay::add_value_labels(&mut self.extras, &PLOT, &labels.into());
     }
 
 
@@ -54,6 +147,43 @@ impl BarChart {
         self.padding = pad;
     }
 
+    /// Sets the opacity of the bars, from 0 for invisible to 1 for opaque
+    pub fn set_fill_opacity(&mut self, opacity: f64) {
+        self.marks[0].set_fill_opacity(opacity);
+    }
+
+    /// Outlines the bars in the given color, see `set_stroke_width`
+    pub fn set_stroke_color(&mut self, color: &str) {
+        self.marks[0].set_stroke_color(color);
+    }
+
+    /// Sets the width in pixels of the outline of the bars
+    pub fn set_stroke_width(&mut self, width: f64) {
+        self.marks[0].set_stroke_width(width);
+    }
+
+    /// Rounds the corners of the bars by the given radius in pixels
+    pub fn set_corner_radius(&mut self, radius: f64) {
+        self.marks[0].set_corner_radius(radius);
+    }
+
+    /// Fills the bars whose data passes the predicate with `color`, e.g. negative amounts in
+    /// red. The fields of the bars are `category` and `amount`. Where several predicates pass,
+    /// the one added first wins.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::elements::predicate::Predicate;
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.add_data(String::from("Q1"), -12.5);
+    ///    b.add_data(String::from("Q2"), 30.0);
+    ///    b.color_when(Predicate::less_than("amount", 0.0), "firebrick");
+    /// ```
+    pub fn color_when(&mut self, predicate: Predicate, color: &str) {
+        self.marks[0].add_color_rule(&predicate.expression(), color);
+    }
+
     /// clear_data empties out all of the data entries
     /// that currently exist in the given graph
     pub fn clear_data(&mut self) {
@@ -67,17 +197,15 @@ impl Serialize for BarChart {
         S: Serializer,
     {
         let mut s = serializer.serialize_struct("graph", 10)?;
-        s.serialize_field(
-            "$schema",
-            "https://vega.github.io/schema/vega/v3.0.json",
-        )?;
+        s.serialize_field("$schema", SCHEMA)?;
         s.serialize_field("width", &self.width)?;
         s.serialize_field("height", &self.height)?;
         s.serialize_field("padding", &self.padding)?;
-        s.serialize_field("data", &self.data)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
         s.serialize_field("scales", &self.scales)?;
         s.serialize_field("axes", &self.axes)?;
-        s.serialize_field("marks", &self.marks)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
         s.end()
     }
 }
@@ -114,4 +242,32 @@ impl Graphable for BarChart {
         (self.height, self.width)
 
     }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for BarChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
 }
@@ -0,0 +1,220 @@
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{when, Mark, MarkType, Production, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::{Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// The width of a candle, most of the space left to it on the x axis
+const CANDLE_WIDTH: &'static str = "max(1, 0.6 * width / length(data('table')))";
+
+/// A candlestick chart shows how a price moved over each period: a rule spans from the lowest
+/// to the highest price, and a box from the opening to the closing price. Boxes are colored by
+/// whether the price went up or down.
+/// # Example:
+/// ```rust
+///    use gust::backend::candlestick_chart::CandlestickChart;
+///
+///    let mut c = CandlestickChart::new();
+///    // the timestamps are given in milliseconds
+///    c.add_candle(1514764800000, 24.3, 25.9, 23.8, 25.1);
+///    c.add_candle(1514851200000, 25.1, 25.4, 22.9, 23.2);
+/// ```
+pub struct CandlestickChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    up_color: String,
+    down_color: String,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl CandlestickChart {
+    pub fn new() -> CandlestickChart {
+        let mut chart = CandlestickChart {
+            identifier: String::from("candlestick_chart"),
+            description: String::from("Candlestick Chart"),
+            width: 500,
+            height: 300,
+            padding: 5,
+            data: vec![DataSet::from_values("table", vec![])],
+            up_color: String::from("#06982d"),
+            down_color: String::from("#ae1325"),
+            scales: vec![
+                Scale::new("x", ScaleType::Time, Domain::field("table", "time"), "width"),
+                Scale::new(
+                    "y",
+                    ScaleType::Linear,
+                    Domain::fields("table", &["low", "high"]),
+                    "height",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[1].include_zero(false);
+        chart.update_marks();
+        chart
+    }
+
+    /// Rebuilds the wicks and the boxes of the candles
+    fn update_marks(&mut self) {
+        let color = |up: &str, down: &str| -> Production {
+            when("datum.close >= datum.open")
+                .then(ValueRef::value(up))
+                .otherwise(ValueRef::value(down))
+        };
+        let wicks = Mark::new(MarkType::Rule)
+            .from_data("table")
+            .enter("x", ValueRef::scaled("x", "time"))
+            .enter("y", ValueRef::scaled("y", "low"))
+            .enter("y2", ValueRef::scaled("y", "high"))
+            .enter("stroke", color(&self.up_color, &self.down_color));
+        let boxes = Mark::new(MarkType::Rect)
+            .from_data("table")
+            .enter("xc", ValueRef::scaled("x", "time"))
+            .enter("width", ValueRef::signal(CANDLE_WIDTH))
+            .enter("y", ValueRef::scaled("y", "open"))
+            .enter("y2", ValueRef::scaled("y", "close"))
+            .enter("fill", color(&self.up_color, &self.down_color))
+            .enter(
+                "tooltip",
+                ValueRef::signal(
+                    "{'open': datum.open, 'high': datum.high, 'low': datum.low, \
+                     'close': datum.close}",
+                ),
+            );
+        self.marks = vec![wicks, boxes];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds the candle of the period starting at `time`, a timestamp in milliseconds, with the
+    /// opening, highest, lowest and closing price of the period
+    pub fn add_candle(&mut self, time: i64, open: f64, high: f64, low: f64, close: f64) {
+        let mut m = Map::new();
+        m.insert(String::from("time"), Value::from(time));
+        m.insert(String::from("open"), Value::from(open));
+        m.insert(String::from("high"), Value::from(high));
+        m.insert(String::from("low"), Value::from(low));
+        m.insert(String::from("close"), Value::from(close));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Sets the colors of the candles whose price closed at or above its opening, and of the
+    /// ones whose price closed below it. They are green and red by default.
+    pub fn set_colors(&mut self, up: &str, down: &str) {
+        self.up_color = String::from(up);
+        self.down_color = String::from(down);
+        self.update_marks();
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for CandlestickChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("candlestick_chart", 9)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for CandlestickChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every candle in the same color, whichever way its price moved
+    fn set_color(&mut self, color: &str) {
+        self.set_colors(color, color);
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for CandlestickChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
@@ -0,0 +1,172 @@
+
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::hierarchy;
+use backend::elements::mark::{Mark, MarkType, SymbolShape, ValueRef};
+use backend::elements::scale::{Domain, Range, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::SCHEMA;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use backend::traits::Graphable;
+
+/// A circle packing draws a hierarchy as nested circles. Every leaf is a circle whose area is
+/// proportional to its size, and every other node is a circle just large enough to contain
+/// its children. Nodes are colored by their depth in the hierarchy.
+/// # Example:
+/// ```rust
+///    use gust::backend::circle_packing::CirclePacking;
+///
+///    let mut c = CirclePacking::new();
+///    c.add_node("src", "src");
+///    c.add_edge("src", "lib.rs");
+///    c.add_edge("src", "backend");
+///    c.add_edge("backend", "mod.rs");
+///    c.set_size("lib.rs", 4200.0);
+///    c.set_size("mod.rs", 300.0);
+/// ```
+pub struct CirclePacking {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    scales: Vec<Scale>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl CirclePacking {
+    pub fn new() -> CirclePacking {
+        let mut tree = hierarchy::stratify("tree", "nodes");
+        tree.add_transform(Transform::Pack {
+            field: String::from("size"),
+            size: [ValueRef::signal("width"), ValueRef::signal("height")],
+            padding: Some(2.0),
+        });
+
+        CirclePacking {
+            identifier: String::from("circle_packing"),
+            description: String::from("Circle Packing"),
+            width: 500,
+            height: 500,
+            padding: 5,
+            data: vec![DataSet::from_values("nodes", vec![]), tree],
+            scales: vec![
+                Scale::with_range(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("tree", "depth"),
+                    Range::scheme("blues"),
+                ),
+            ],
+            marks: vec![CirclePacking::circles(ValueRef::scaled("color", "depth"))],
+            extras: Extras::new(),
+        }
+    }
+
+    fn circles(fill: ValueRef) -> Mark {
+        Mark::new(MarkType::Symbol)
+            .shape(SymbolShape::Circle)
+            .from_data("tree")
+            .at(ValueRef::field("x"), ValueRef::field("y"))
+            .enter("size", ValueRef::signal("4 * datum.r * datum.r"))
+            .enter("fill", fill)
+            .enter("tooltip", ValueRef::field("name"))
+            .stroke("white", 1.0)
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a node with the given id, labelled `name`
+    pub fn add_node(&mut self, id: &str, name: &str) {
+        hierarchy::add_node(&mut self.data[0], id, name);
+    }
+
+    /// Nests `child` in its parent. Nodes that weren't added before are labelled with their id.
+    /// Every node but the root of the hierarchy needs a parent.
+    pub fn add_edge(&mut self, parent: &str, child: &str) {
+        hierarchy::add_edge(&mut self.data[0], parent, child);
+    }
+
+    /// Sets the size of a leaf. The size of every other node is the sum of its leaves.
+    pub fn set_size(&mut self, id: &str, size: f64) {
+        hierarchy::set_size(&mut self.data[0], id, size);
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for CirclePacking {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("circle_packing", 7)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for CirclePacking {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every circle in the same color, they are told apart by their borders
+    fn set_color(&mut self, color: &str) {
+        self.marks[0] = CirclePacking::circles(ValueRef::value(color));
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the nodes, which run before they are laid out
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+}
@@ -0,0 +1,140 @@
+
+/*!
+Loading themes and chart definitions from config files.
+
+JSON files can always be loaded. TOML files need the `toml` feature, and YAML files the `yaml`
+feature of gust. The format of a file is told by its extension.
+ */
+
+use backend::elements::theme::Theme;
+use backend::traits::Graphable;
+use serde::de::DeserializeOwned;
+use serde_json;
+use std;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+#[cfg(feature = "toml")]
+use toml;
+#[cfg(feature = "yaml")]
+use serde_yaml;
+
+/// ConfigError is returned when a config file can't be read or parsed
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    /// The file could be read, but its contents are malformed
+    Parse(String),
+    /// The extension of the file isn't one of a format gust can load
+    UnsupportedFormat(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigError::Io(ref e) => write!(f, "could not read config: {}", e),
+            ConfigError::Parse(ref e) => write!(f, "could not parse config: {}", e),
+            ConfigError::UnsupportedFormat(ref ext) => {
+                write!(f, "config files ending in '{}' are not supported", ext)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn description(&self) -> &str {
+        match *self {
+            ConfigError::Io(_) => "could not read config",
+            ConfigError::Parse(_) => "could not parse config",
+            ConfigError::UnsupportedFormat(_) => "unsupported config format",
+        }
+    }
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(e: std::io::Error) -> ConfigError {
+        ConfigError::Io(e)
+    }
+}
+
+/// Parses the file at `path` into `T`, in the format given by its extension
+fn load<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, ConfigError> {
+    let path = path.as_ref();
+    let extension = path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+
+    match extension.as_str() {
+        "json" => serde_json::from_str(&contents).map_err(|e| ConfigError::Parse(e.to_string())),
+        #[cfg(feature = "toml")]
+        "toml" => toml::from_str(&contents).map_err(|e| ConfigError::Parse(e.to_string())),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => {
+            serde_yaml::from_str(&contents).map_err(|e| ConfigError::Parse(e.to_string()))
+        }
+        _ => Err(ConfigError::UnsupportedFormat(extension)),
+    }
+}
+
+impl Theme {
+    /// Loads a theme from a TOML, YAML or JSON file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Theme, ConfigError> {
+        load(path)
+    }
+}
+
+/// ChartSpec is the definition of a chart kept in a config file, which is applied to a chart
+/// built in Rust. Everything that isn't set in the file is left as it is.
+/// # Example:
+/// ```toml
+/// identifier = "revenue"
+/// description = "Revenue per Region"
+/// dimensions = [300, 600]
+///
+/// [theme]
+/// font = "Helvetica"
+/// palette = ["#1b9e77", "#d95f02"]
+/// ```
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct ChartSpec {
+    pub identifier: Option<String>,
+    pub description: Option<String>,
+    /// The dimensions of the chart as (height, width)
+    pub dimensions: Option<(i32, i32)>,
+    /// The color of the marks of the chart, see `Graphable::set_color`
+    pub color: Option<String>,
+    pub theme: Option<Theme>,
+}
+
+impl ChartSpec {
+    /// Loads a chart definition from a TOML, YAML or JSON file
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<ChartSpec, ConfigError> {
+        load(path)
+    }
+
+    /// Applies the definition to a chart
+    pub fn apply<G: Graphable>(self, graph: &mut G) {
+        if let Some(ref identifier) = self.identifier {
+            graph.set_identifier(identifier);
+        }
+        if let Some(ref description) = self.description {
+            graph.set_description(description);
+        }
+        if let Some(dimensions) = self.dimensions {
+            graph.set_dimensions(dimensions);
+        }
+        if let Some(ref color) = self.color {
+            graph.set_color(color);
+        }
+        if let Some(theme) = self.theme {
+            graph.set_theme(theme);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+/*!
+Loading chart data from csv files, which needs the `csv` feature of gust.
+
+The first row of a file names its columns. A column whose values all parse as numbers is a
+numeric column, empty values aside; all other columns hold text.
+
+```rust,no_run
+use gust::backend::bar_chart::BarChart;
+use gust::backend::line_chart::LineChart;
+
+let b = BarChart::from_csv("sales.csv", "region", "revenue").unwrap();
+
+let mut c = LineChart::new();
+c.load_csv("temperatures.csv", "day", "celsius").unwrap();
+```
+ */
+
+use backend::area_chart::AreaChart;
+use backend::bar_chart::BarChart;
+use backend::elements::data::DataSet;
+use backend::line_chart::LineChart;
+use backend::scatter_plot::ScatterPlot;
+use csv;
+use serde_json::{Map, Number, Value};
+use std;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+/// CsvError is returned when a csv file can't be read, or doesn't hold the data a chart needs
+#[derive(Debug)]
+pub enum CsvError {
+    Io(std::io::Error),
+    /// The file could be read, but isn't valid csv
+    Parse(String),
+    /// The file has no column of the given name
+    MissingColumn(String),
+    /// The value in a column of a row can't be used, e.g. because it isn't a number
+    Row { line: u64, message: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CsvError::Io(ref e) => write!(f, "could not read csv: {}", e),
+            CsvError::Parse(ref e) => write!(f, "could not parse csv: {}", e),
+            CsvError::MissingColumn(ref column) => write!(f, "csv has no column '{}'", column),
+            CsvError::Row { line, ref message } => write!(f, "csv line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn description(&self) -> &str {
+        match *self {
+            CsvError::Io(_) => "could not read csv",
+            CsvError::Parse(_) => "could not parse csv",
+            CsvError::MissingColumn(_) => "missing csv column",
+            CsvError::Row { .. } => "invalid csv row",
+        }
+    }
+}
+
+impl From<std::io::Error> for CsvError {
+    fn from(e: std::io::Error) -> CsvError {
+        CsvError::Io(e)
+    }
+}
+
+impl From<csv::Error> for CsvError {
+    fn from(e: csv::Error) -> CsvError {
+        CsvError::Parse(e.to_string())
+    }
+}
+
+/// CsvTable holds the rows of a csv file as text, along with the line each row was read from
+pub struct CsvTable {
+    headers: Vec<String>,
+    rows: Vec<(u64, Vec<String>)>,
+}
+
+impl CsvTable {
+    /// Reads the csv file at `path`, whose first row names the columns
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<CsvTable, CsvError> {
+        let mut reader = csv::Reader::from_path(path)?;
+        let headers = reader.headers()?.iter().map(String::from).collect();
+        let mut rows = vec![];
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map(|p| p.line()).unwrap_or(0);
+            rows.push((line, record.iter().map(String::from).collect()));
+        }
+        Ok(CsvTable { headers, rows })
+    }
+
+    /// The names of the columns
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// The number of rows, not counting the header
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn index(&self, column: &str) -> Result<usize, CsvError> {
+        self.headers
+            .iter()
+            .position(|h| h == column)
+            .ok_or_else(|| CsvError::MissingColumn(String::from(column)))
+    }
+
+    /// Whether all values of the column are numbers, ignoring empty values
+    pub fn is_numeric(&self, column: &str) -> bool {
+        match self.index(column) {
+            Ok(i) => self.rows.iter().all(|&(_, ref row)| {
+                row.get(i).map_or(true, |v| v.trim().is_empty() || v.trim().parse::<f64>().is_ok())
+            }),
+            Err(_) => false,
+        }
+    }
+
+    /// The values of a column as text
+    pub fn text_column(&self, column: &str) -> Result<Vec<String>, CsvError> {
+        let i = self.index(column)?;
+        Ok(self.rows
+            .iter()
+            .map(|&(_, ref row)| row.get(i).cloned().unwrap_or_default())
+            .collect())
+    }
+
+    /// The values of a column as numbers. The first value that isn't a number is reported
+    /// along with its line.
+    pub fn number_column(&self, column: &str) -> Result<Vec<f64>, CsvError> {
+        let i = self.index(column)?;
+        self.rows
+            .iter()
+            .map(|&(line, ref row)| {
+                let value = row.get(i).map(|v| v.trim()).unwrap_or("");
+                value.parse().map_err(|_| CsvError::Row {
+                    line,
+                    message: format!("'{}' in column '{}' is not a number", value, column),
+                })
+            })
+            .collect()
+    }
+
+    /// The rows as data objects, with the values of numeric columns as numbers and empty
+    /// values of numeric columns as null
+    pub fn to_values(&self) -> Vec<Value> {
+        let numeric: Vec<bool> = self.headers.iter().map(|h| self.is_numeric(h)).collect();
+        self.rows
+            .iter()
+            .map(|&(_, ref row)| {
+                let mut m = Map::new();
+                for (i, header) in self.headers.iter().enumerate() {
+                    let text = row.get(i).map(|v| v.as_str()).unwrap_or("");
+                    let value = if numeric[i] {
+                        text.trim()
+                            .parse()
+                            .ok()
+                            .and_then(Number::from_f64)
+                            .map_or(Value::Null, Value::Number)
+                    } else {
+                        Value::from(text)
+                    };
+                    m.insert(header.clone(), value);
+                }
+                Value::Object(m)
+            })
+            .collect()
+    }
+
+    /// A data set with the given name holding all rows, see `to_values`
+    pub fn to_data_set(&self, name: &str) -> DataSet {
+        DataSet::from_values(name, self.to_values())
+    }
+}
+
+/// The columns `x` and `y` of the file at `path` as numbers
+fn points<P: AsRef<Path>>(path: P, x: &str, y: &str) -> Result<Vec<(f64, f64)>, CsvError> {
+    let table = CsvTable::from_path(path)?;
+    let xs = table.number_column(x)?;
+    let ys = table.number_column(y)?;
+    Ok(xs.into_iter().zip(ys).collect())
+}
+
+impl BarChart {
+    /// Creates a bar chart with a bar for every row of the csv file at `path`, named by the
+    /// column `category` and as high as the number in the column `value`
+    pub fn from_csv<P: AsRef<Path>>(
+        path: P,
+        category: &str,
+        value: &str,
+    ) -> Result<BarChart, CsvError> {
+        let mut chart = BarChart::new();
+        chart.load_csv(path, category, value)?;
+        Ok(chart)
+    }
+
+    /// Adds a bar for every row of the csv file at `path`, see `from_csv`. Nothing is added if
+    /// a row is invalid.
+    pub fn load_csv<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        category: &str,
+        value: &str,
+    ) -> Result<(), CsvError> {
+        let table = CsvTable::from_path(path)?;
+        let categories = table.text_column(category)?;
+        let values = table.number_column(value)?;
+        self.add_data_iter(categories.into_iter().zip(values));
+        Ok(())
+    }
+}
+
+impl LineChart {
+    /// Adds a point to series 0 for every row of the csv file at `path`, at the numbers in the
+    /// columns `x` and `y`. Nothing is added if a row is invalid.
+    pub fn load_csv<P: AsRef<Path>>(&mut self, path: P, x: &str, y: &str) -> Result<(), CsvError> {
+        let points = points(path, x, y)?;
+        self.add_data_iter(points.into_iter().map(|(x, y)| (x, y, 0)));
+        Ok(())
+    }
+}
+
+impl AreaChart {
+    /// Adds a point for every row of the csv file at `path`, at the numbers in the columns `u`
+    /// and `v`. Nothing is added if a row is invalid.
+    pub fn load_csv<P: AsRef<Path>>(&mut self, path: P, u: &str, v: &str) -> Result<(), CsvError> {
+        let points = points(path, u, v)?;
+        self.add_data_iter(points);
+        Ok(())
+    }
+}
+
+impl ScatterPlot {
+    /// Adds a point for every row of the csv file at `path`, at the numbers in the columns `x`
+    /// and `y`. Nothing is added if a row is invalid.
+    pub fn load_csv<P: AsRef<Path>>(&mut self, path: P, x: &str, y: &str) -> Result<(), CsvError> {
+        let points = points(path, x, y)?;
+        self.add_data_iter(points);
+        Ok(())
+    }
+}
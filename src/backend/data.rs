@@ -0,0 +1,190 @@
+/*!
+Prepares data on the Rust side, for charts that should only carry the values they draw.
+
+The aggregation helpers summarize raw records instead of aggregating them in the viewer (see
+`Aggregate` in the transform module for the latter), and `lttb` thins out long series of
+points, see `LineChart::downsample_to`.
+
+Every aggregation helper groups the records by a key and returns one entry per group, in the order the
+groups first appear, which fits `add_data_iter` of the charts.
+
+```rust
+use gust::backend::bar_chart::BarChart;
+use gust::backend::data::sum_by;
+
+let sales = vec![("North", 12.5), ("South", 7.0), ("North", 3.0)];
+let mut b = BarChart::new();
+b.add_data_iter(sum_by(&sales, |s| s.0.to_string(), |s| s.1));
+```
+ */
+
+use backend::general::Numeric;
+use std::cmp;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The sum of the values and the number of records of every group, in order of appearance
+fn fold_by<I, K, FK, FV, V>(records: I, key: FK, value: FV) -> Vec<(K, f64, usize)>
+where
+    I: IntoIterator,
+    K: Eq + Hash + Clone,
+    FK: Fn(&I::Item) -> K,
+    FV: Fn(&I::Item) -> V,
+    V: Numeric,
+{
+    let mut index = HashMap::new();
+    let mut groups: Vec<(K, f64, usize)> = vec![];
+    for record in records {
+        let k = key(&record);
+        let v = value(&record).as_f64();
+        let i = *index.entry(k.clone()).or_insert_with(|| {
+            groups.push((k, 0.0, 0));
+            groups.len() - 1
+        });
+        groups[i].1 += v;
+        groups[i].2 += 1;
+    }
+    groups
+}
+
+/// The sum of `value` over the records of every group of `key`
+pub fn sum_by<I, K, FK, FV, V>(records: I, key: FK, value: FV) -> Vec<(K, f64)>
+where
+    I: IntoIterator,
+    K: Eq + Hash + Clone,
+    FK: Fn(&I::Item) -> K,
+    FV: Fn(&I::Item) -> V,
+    V: Numeric,
+{
+    fold_by(records, key, value)
+        .into_iter()
+        .map(|(k, sum, _)| (k, sum))
+        .collect()
+}
+
+/// The mean of `value` over the records of every group of `key`
+pub fn mean_by<I, K, FK, FV, V>(records: I, key: FK, value: FV) -> Vec<(K, f64)>
+where
+    I: IntoIterator,
+    K: Eq + Hash + Clone,
+    FK: Fn(&I::Item) -> K,
+    FV: Fn(&I::Item) -> V,
+    V: Numeric,
+{
+    fold_by(records, key, value)
+        .into_iter()
+        .map(|(k, sum, count)| (k, sum / count as f64))
+        .collect()
+}
+
+/// The number of records in every group of `key`
+pub fn count_by<I, K, FK>(records: I, key: FK) -> Vec<(K, usize)>
+where
+    I: IntoIterator,
+    K: Eq + Hash + Clone,
+    FK: Fn(&I::Item) -> K,
+{
+    fold_by(records, key, |_| 0)
+        .into_iter()
+        .map(|(k, _, count)| (k, count))
+        .collect()
+}
+
+/// Orders numbers from the smallest to the largest with NaN after all of them, so that sorting
+/// values that may be NaN doesn't panic
+/// # Example:
+/// ```rust
+///    use gust::backend::data::nan_last;
+///
+///    let mut values = vec![2.0, ::std::f64::NAN, 1.0];
+///    values.sort_by(|&a, &b| nan_last(a, b));
+///    assert_eq!(&values[..2], &[1.0, 2.0]);
+/// ```
+pub fn nan_last(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    }
+}
+
+/// Picks `threshold` of the points, sorted by x, that best preserve the shape of the line
+/// through them, using the largest-triangle-three-buckets algorithm. The first and the last
+/// point are always kept, and of every bucket of points in between the one that spans the
+/// largest triangle with its neighbours. Returns the indices of the picked points in ascending
+/// order, or of all points if there are no more than `threshold` of them or `threshold` is
+/// less than 3.
+pub fn lttb(points: &[(f64, f64)], threshold: usize) -> Vec<usize> {
+    let n = points.len();
+    if threshold >= n || threshold < 3 {
+        return (0..n).collect();
+    }
+    let every = (n - 2) as f64 / (threshold - 2) as f64;
+    let mut picked = Vec::with_capacity(threshold);
+    let mut a = 0;
+    picked.push(a);
+    for i in 0..threshold - 2 {
+        // the average of the next bucket is the third corner of the triangles
+        let start = ((i + 1) as f64 * every) as usize + 1;
+        let end = cmp::min(((i + 2) as f64 * every) as usize + 1, n);
+        let next = &points[start..end];
+        let (sum_x, sum_y) = next.iter().fold((0.0, 0.0), |s, p| (s.0 + p.0, s.1 + p.1));
+        let (avg_x, avg_y) = (sum_x / next.len() as f64, sum_y / next.len() as f64);
+
+        let (ax, ay) = points[a];
+        let bucket = (i as f64 * every) as usize + 1..((i + 1) as f64 * every) as usize + 1;
+        let mut largest = -1.0;
+        for j in bucket {
+            let (bx, by) = points[j];
+            let area = ((ax - avg_x) * (by - ay) - (ax - bx) * (avg_y - ay)).abs();
+            if area > largest {
+                largest = area;
+                a = j;
+            }
+        }
+        picked.push(a);
+    }
+    picked.push(n - 1);
+    picked
+}
+
+/// Thins out every series of data objects to at most `threshold` points, see `lttb`. Takes the
+/// series, x and y of every data object, where a y of `None` marks a gap, and returns whether
+/// to keep each of them, in the same order. Gaps, and points whose x isn't a number, are kept.
+/// # Example:
+/// ```rust
+///    use gust::backend::data::downsample;
+///
+///    let points = vec![(0, 1.0, Some(1.0)), (0, 2.0, None), (1, 1.0, Some(4.0))];
+///    assert_eq!(downsample(points, 3), vec![true, true, true]);
+/// ```
+pub fn downsample<I, K>(points: I, threshold: usize) -> Vec<bool>
+where
+    I: IntoIterator<Item = (K, f64, Option<f64>)>,
+    K: Eq + Hash,
+{
+    let mut keep = vec![];
+    let mut xy = vec![];
+    let mut index = HashMap::new();
+    let mut series: Vec<Vec<usize>> = vec![];
+    for (i, (k, x, y)) in points.into_iter().enumerate() {
+        let gap = y.is_none() || x.is_nan();
+        keep.push(gap);
+        xy.push((x, y.unwrap_or(0.0)));
+        if !gap {
+            let s = *index.entry(k).or_insert_with(|| {
+                series.push(vec![]);
+                series.len() - 1
+            });
+            series[s].push(i);
+        }
+    }
+    for mut defined in series {
+        defined.sort_by(|&a, &b| nan_last(xy[a].0, xy[b].0));
+        let points: Vec<(f64, f64)> = defined.iter().map(|&i| xy[i]).collect();
+        for j in lttb(&points, threshold) {
+            keep[defined[j]] = true;
+        }
+    }
+    keep
+}
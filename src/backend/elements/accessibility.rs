@@ -0,0 +1,79 @@
+
+/*
+ * Helpers for charts that need to be readable by everyone: colorblind-safe palettes and
+ * contrast checks following the WCAG definition of contrast
+ */
+
+/// The palette of Okabe and Ito, which stays distinguishable for the common kinds of color
+/// blindness
+pub const OKABE_ITO: [&'static str; 8] = [
+    "#e69f00", "#56b4e9", "#009e73", "#f0e442", "#0072b2", "#d55e00", "#cc79a7", "#000000",
+];
+
+/// Paul Tol's bright palette, which is colorblind-safe and also works in greyscale print
+pub const TOL_BRIGHT: [&'static str; 7] = [
+    "#4477aa", "#ee6677", "#228833", "#ccbb44", "#66ccee", "#aa3377", "#bbbbbb",
+];
+
+/// The color palette used by IBM design, which is colorblind-safe for up to five series
+pub const IBM: [&'static str; 5] = ["#648fff", "#785ef0", "#dc267f", "#fe6100", "#ffb000"];
+
+/// The minimum contrast WCAG asks for between normal text and its background
+pub const MIN_TEXT_CONTRAST: f64 = 4.5;
+
+/// The minimum contrast WCAG asks for between graphical objects, like marks, and their
+/// background
+pub const MIN_GRAPHICS_CONTRAST: f64 = 3.0;
+
+/// Parses a color written as "#rgb" or "#rrggbb" into its red, green and blue parts
+fn parse_hex(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.trim();
+    let hex = if hex.starts_with('#') { &hex[1..] } else { hex };
+    let channel = |i: usize, len: usize| {
+        let part = &hex[i * len..(i + 1) * len];
+        let value = u8::from_str_radix(part, 16).ok()?;
+        Some(if len == 1 { value * 17 } else { value })
+    };
+    if !hex.is_ascii() {
+        return None;
+    }
+    match hex.len() {
+        3 => Some((channel(0, 1)?, channel(1, 1)?, channel(2, 1)?)),
+        6 => Some((channel(0, 2)?, channel(1, 2)?, channel(2, 2)?)),
+        _ => None,
+    }
+}
+
+/// The relative luminance of a color, from 0 for black to 1 for white
+fn luminance(color: (u8, u8, u8)) -> f64 {
+    let linear = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color.0) + 0.7152 * linear(color.1) + 0.0722 * linear(color.2)
+}
+
+/// The contrast ratio between two hex colors, from 1 (no contrast) to 21 (black on white), or
+/// None if either color isn't written as "#rgb" or "#rrggbb"
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::accessibility::{contrast_ratio, MIN_TEXT_CONTRAST};
+///
+///    let ratio = contrast_ratio("#777777", "#ffffff").unwrap();
+///    assert!(ratio < MIN_TEXT_CONTRAST);
+/// ```
+pub fn contrast_ratio(a: &str, b: &str) -> Option<f64> {
+    let (a, b) = (luminance(parse_hex(a)?), luminance(parse_hex(b)?));
+    let (light, dark) = if a > b { (a, b) } else { (b, a) };
+    Some((light + 0.05) / (dark + 0.05))
+}
+
+/// Whether `color` stands out from `background` with at least the contrast `min`. Colors that
+/// can't be parsed are assumed to fail.
+pub fn has_contrast(color: &str, background: &str, min: f64) -> bool {
+    contrast_ratio(color, background).map_or(false, |ratio| ratio >= min)
+}
@@ -3,49 +3,19 @@
  * area_charts
  */
 
+use backend::data;
 use backend::elements::general::*;
+use backend::elements::transform::Transform;
 
 
 use serde::ser::{Serializer, SerializeStruct, Serialize};
 
-#[derive(Serialize)]
-pub struct AreaChartSignal {
-    name: String,
-    value: String,
-    bind: SignalBinding,
-}
-
-impl AreaChartSignal {
-    pub fn default() -> AreaChartSignal {
-        AreaChartSignal {
-            name: String::from("interpolate"),
-            value: String::from("monotone"),
-            bind: SignalBinding::default(),
-        }
-    }
-}
-#[derive(Serialize)]
-pub struct SignalBinding {
-    input: String,
-    options: Vec<String>,
-}
-impl SignalBinding {
-    pub fn default() -> SignalBinding {
-        SignalBinding {
-            input: String::from("select"),
-            options: vec![
-                String::from("basis"),
-                String::from("cardinal"),
-                String::from("linear"),
-                String::from("monotone"),
-            ],
-        }
-    }
-}
 #[derive(Serialize)]
 pub struct AreaChartData {
     name: String,
     values: Vec<AreaChartDataEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    transform: Vec<Transform>,
 }
 
 impl AreaChartData {
@@ -53,39 +23,36 @@ impl AreaChartData {
         AreaChartData {
             name: String::from("table"),
             values: vec![],
+            transform: vec![],
         }
     }
-    pub fn add_data(&mut self, u: i32, v: i32) {
-        self.values.push(AreaChartDataEntry { u, v });
+    pub fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        &mut self.transform
+    }
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional)
+    }
+    pub fn add_data(&mut self, u: f64, v: f64) {
+        self.values.push(AreaChartDataEntry { u, v: Some(v) });
+    }
+    pub fn add_gap(&mut self, u: f64) {
+        self.values.push(AreaChartDataEntry { u, v: None });
+    }
+    /// Thins out the points to at most `threshold`, see `data::downsample`. Gaps, and points
+    /// whose u isn't a number, are kept.
+    pub fn downsample(&mut self, threshold: usize) {
+        let points = self.values.iter().map(|e| ((), e.u, e.v));
+        let mut keep = data::downsample(points, threshold).into_iter();
+        self.values.retain(|_| keep.next().unwrap());
     }
 }
 #[derive(Serialize)]
 pub struct AreaChartDataEntry {
-    u: i32,
-    v: i32,
+    u: f64,
+    v: Option<f64>,
 }
 
 
-#[derive(Serialize)]
-pub struct AreaChartAxis {
-    orient: Orientation,
-    scale: String,
-}
-
-impl AreaChartAxis {
-    pub fn x_axis() -> AreaChartAxis {
-        AreaChartAxis {
-            orient: Orientation::Bottom,
-            scale: String::from("xscale"),
-        }
-    }
-    pub fn y_axis() -> AreaChartAxis {
-        AreaChartAxis {
-            orient: Orientation::Left,
-            scale: String::from("yscale"),
-        }
-    }
-}
 pub struct AreaChartMark {
     mark_type: String,
     from: KeyVal,
@@ -100,6 +67,20 @@ impl AreaChartMark {
             encode: AreaChartEncoding::default(),
         }
     }
+    pub fn set_color(&mut self, color: &str) {
+        self.encode.enter.fill = KeyVal::new("value", color);
+    }
+    /// Sets the opacity of the area, which halves while it is hovered
+    pub fn set_fill_opacity(&mut self, opacity: f64) {
+        self.encode.update.fillOpacity = QualKeyVal::new("value", opacity as f32);
+        self.encode.hover.fillOpacity = QualKeyVal::new("value", (opacity * 0.5) as f32);
+    }
+    pub fn set_stroke_color(&mut self, color: &str) {
+        self.encode.enter.stroke = Some(KeyVal::new("value", color));
+    }
+    pub fn set_stroke_width(&mut self, width: f64) {
+        self.encode.enter.stroke_width = Some(QualKeyVal::new("value", width as f32));
+    }
 }
 
 impl Serialize for AreaChartMark {
@@ -136,6 +117,11 @@ pub struct AreaChartEnter {
     y: JSONDict,
     y2: JSONDict,
     fill: KeyVal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stroke: Option<KeyVal>,
+    #[serde(rename = "strokeWidth", skip_serializing_if = "Option::is_none")]
+    stroke_width: Option<QualKeyVal>,
+    defined: KeyVal,
 }
 
 impl AreaChartEnter {
@@ -145,10 +131,14 @@ impl AreaChartEnter {
             y: JSONDict::create("scale", "yscale", "field", "v"),
             y2: JSONDict::band_create("scale", "yscale", "value", 0),
             fill: KeyVal::new("value", "steelblue"),
+            stroke: None,
+            stroke_width: None,
+            defined: KeyVal::new("signal", "isValid(datum.v)"),
         }
     }
 }
 #[derive(Serialize)]
+#[allow(non_snake_case)]
 pub struct AreaChartUpdate {
     interpolate: KeyVal,
     fillOpacity: QualKeyVal,
@@ -165,7 +155,7 @@ impl AreaChartUpdate {
 }
 
 #[derive(Serialize)]
-#[ignore(warn_snake_case)]
+#[allow(non_snake_case)]
 pub struct AreaChartHover {
     fillOpacity: QualKeyVal,
 }
@@ -174,54 +164,3 @@ impl AreaChartHover {
         AreaChartHover { fillOpacity: QualKeyVal::new("value", 0.5) }
     }
 }
-
-pub struct AreaChartScale {
-    name: String,
-    scale_type: String,
-    range: String,
-    zero: bool,
-    domain: JSONDict,
-}
-
-impl AreaChartScale {
-    pub fn default_x() -> AreaChartScale {
-        AreaChartScale {
-            name: String::from("xscale"),
-            scale_type: String::from("linear"),
-            zero: false,
-            range: String::from("width"),
-            domain: JSONDict::create("data", "table", "field", "u"),
-        }
-
-    }
-    pub fn default_y() -> AreaChartScale {
-        AreaChartScale {
-            name: String::from("yscale"),
-            scale_type: String::from("linear"),
-            zero: true,
-            range: String::from("height"),
-            domain: JSONDict::create("data", "table", "field", "v"),
-        }
-
-    }
-}
-
-
-impl Serialize for AreaChartScale {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_struct("area_chart", 10)?;
-        s.serialize_field("name", &self.name)?;
-        s.serialize_field("type", &self.scale_type)?;
-        s.serialize_field("range", &self.range)?;
-        s.serialize_field("zero", &self.zero)?;
-        s.serialize_field("domain", &self.domain)?;
-
-
-
-        s.end()
-
-    }
-}
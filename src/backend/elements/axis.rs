@@ -0,0 +1,411 @@
+
+/*
+ * Axis element shared by all charts that are drawn on x/y axes
+ */
+
+use backend::elements::general::Orientation;
+use backend::elements::mark::Align;
+use serde::ser::{Serialize, Serializer};
+
+/// FormatType determines how the tick values of an axis are interpreted when formatting them
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatType {
+    Number,
+    /// Timestamps, formatted in the viewer's local time zone
+    Time,
+    /// Timestamps, formatted in UTC
+    Utc,
+}
+
+/// TickFormat describes how the tick labels of an axis are written. A d3 format string can be
+/// given as well, and turns into `TickFormat::Custom`.
+pub enum TickFormat {
+    /// Fractions as percentages with the given number of decimals, e.g. 0.25 as "25%"
+    Percent(u32),
+    /// Numbers with an SI prefix, e.g. 1500 as "1.5k"
+    Si,
+    /// Amounts of dollars with thousands separators and the given number of decimals
+    Currency(u32),
+    /// Numbers with thousands separators and the given number of decimals
+    Fixed(u32),
+    /// Timestamps written by a d3 time format pattern, e.g. "%b %d" for "Mar 01"
+    DateTime(String),
+    /// A d3 format string
+    Custom(String),
+}
+
+impl TickFormat {
+    /// The d3 format string of the format
+    pub fn pattern(&self) -> String {
+        match *self {
+            TickFormat::Percent(decimals) => format!(".{}%", decimals),
+            TickFormat::Si => String::from("~s"),
+            TickFormat::Currency(decimals) => format!("$,.{}f", decimals),
+            TickFormat::Fixed(decimals) => format!(",.{}f", decimals),
+            TickFormat::DateTime(ref pattern) | TickFormat::Custom(ref pattern) => pattern.clone(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for TickFormat {
+    fn from(pattern: &'a str) -> TickFormat {
+        TickFormat::Custom(String::from(pattern))
+    }
+}
+
+/// LabelOverlap picks how an axis deals with tick labels that would overlap each other
+#[derive(Clone, Copy)]
+pub enum LabelOverlap {
+    /// All labels are drawn, even if they overlap
+    Off,
+    /// Every other label is left out until the remaining ones don't overlap
+    Parity,
+    /// Labels are left out one by one, whenever they would overlap the last label drawn
+    Greedy,
+}
+
+impl Serialize for LabelOverlap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            LabelOverlap::Off => serializer.serialize_bool(false),
+            LabelOverlap::Parity => serializer.serialize_str("parity"),
+            LabelOverlap::Greedy => serializer.serialize_str("greedy"),
+        }
+    }
+}
+
+/// AxisLabels bundles the options for the tick labels of an axis: their angle, how long they
+/// may get and how crowded they may be. It can be applied to the axes of any chart, see
+/// `Cartesian::configure_x_axis`.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::axis::{AxisLabels, LabelOverlap};
+///
+///    // long category names, tilted and cut off after 80 pixels
+///    let config = AxisLabels::new()
+///        .label_angle(-45.0)
+///        .label_limit(80.0)
+///        .label_overlap(LabelOverlap::Greedy)
+///        .tick_count(10);
+/// ```
+#[derive(Clone, Default)]
+pub struct AxisLabels {
+    label_angle: Option<f64>,
+    label_limit: Option<f64>,
+    label_overlap: Option<LabelOverlap>,
+    tick_count: Option<u32>,
+}
+
+impl AxisLabels {
+    pub fn new() -> AxisLabels {
+        AxisLabels::default()
+    }
+
+    /// Rotates the labels by the given number of degrees, clockwise
+    pub fn label_angle(mut self, angle: f64) -> AxisLabels {
+        self.label_angle = Some(angle);
+        self
+    }
+
+    /// Cuts labels off with an ellipsis once they are longer than the given number of pixels
+    pub fn label_limit(mut self, limit: f64) -> AxisLabels {
+        self.label_limit = Some(limit);
+        self
+    }
+
+    pub fn label_overlap(mut self, overlap: LabelOverlap) -> AxisLabels {
+        self.label_overlap = Some(overlap);
+        self
+    }
+
+    /// Sets the number of ticks the axis should have. For quantitative scales this is a hint,
+    /// the ticks are placed at round values close to it.
+    pub fn tick_count(mut self, count: u32) -> AxisLabels {
+        self.tick_count = Some(count);
+        self
+    }
+}
+
+/// AxisStyle bundles the options for the lines of an axis: its gridlines, its domain line and
+/// its ticks. Like `AxisLabels` it can be applied to the axes of any chart, see
+/// `Cartesian::style_x_axis`.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::axis::AxisStyle;
+///
+///    // light dashed gridlines, without the domain line
+///    let style = AxisStyle::new()
+///        .grid(true)
+///        .grid_color("#ddd")
+///        .grid_dash(&[4.0, 2.0])
+///        .domain(false)
+///        .tick_size(3.0);
+/// ```
+#[derive(Clone, Default)]
+pub struct AxisStyle {
+    grid: Option<bool>,
+    grid_color: Option<String>,
+    grid_dash: Option<Vec<f64>>,
+    domain: Option<bool>,
+    domain_color: Option<String>,
+    ticks: Option<bool>,
+    tick_size: Option<f64>,
+}
+
+impl AxisStyle {
+    pub fn new() -> AxisStyle {
+        AxisStyle::default()
+    }
+
+    /// Toggles gridlines across the chart at every tick
+    pub fn grid(mut self, grid: bool) -> AxisStyle {
+        self.grid = Some(grid);
+        self
+    }
+
+    pub fn grid_color(mut self, color: &str) -> AxisStyle {
+        self.grid_color = Some(String::from(color));
+        self
+    }
+
+    /// Dashes the gridlines, alternating between the given lengths of stroke and space in
+    /// pixels
+    pub fn grid_dash(mut self, dash: &[f64]) -> AxisStyle {
+        self.grid_dash = Some(dash.to_vec());
+        self
+    }
+
+    /// Toggles the domain line, i.e. the baseline the ticks are attached to
+    pub fn domain(mut self, domain: bool) -> AxisStyle {
+        self.domain = Some(domain);
+        self
+    }
+
+    pub fn domain_color(mut self, color: &str) -> AxisStyle {
+        self.domain_color = Some(String::from(color));
+        self
+    }
+
+    pub fn ticks(mut self, ticks: bool) -> AxisStyle {
+        self.ticks = Some(ticks);
+        self
+    }
+
+    /// Sets the length of the ticks in pixels
+    pub fn tick_size(mut self, size: f64) -> AxisStyle {
+        self.tick_size = Some(size);
+        self
+    }
+}
+
+/// Axis represents a single Vega axis. Only `orient` and `scale` are always emitted, every other
+/// property is left out of the spec until it has been set, so Vega's defaults apply.
+#[derive(Serialize)]
+pub struct Axis {
+    orient: Orientation,
+    scale: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    zindex: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    domain: Option<bool>,
+    #[serde(rename = "domainColor", skip_serializing_if = "Option::is_none")]
+    domain_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grid: Option<bool>,
+    #[serde(rename = "gridColor", skip_serializing_if = "Option::is_none")]
+    grid_color: Option<String>,
+    #[serde(rename = "gridDash", skip_serializing_if = "Option::is_none")]
+    grid_dash: Option<Vec<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ticks: Option<bool>,
+    #[serde(rename = "tickSize", skip_serializing_if = "Option::is_none")]
+    tick_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(rename = "titleFontSize", skip_serializing_if = "Option::is_none")]
+    title_font_size: Option<f64>,
+    #[serde(rename = "titleColor", skip_serializing_if = "Option::is_none")]
+    title_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    #[serde(rename = "formatType", skip_serializing_if = "Option::is_none")]
+    format_type: Option<FormatType>,
+    #[serde(rename = "labelAlign", skip_serializing_if = "Option::is_none")]
+    label_align: Option<String>,
+    #[serde(rename = "labelAngle", skip_serializing_if = "Option::is_none")]
+    label_angle: Option<f64>,
+    #[serde(rename = "labelLimit", skip_serializing_if = "Option::is_none")]
+    label_limit: Option<f64>,
+    #[serde(rename = "labelOverlap", skip_serializing_if = "Option::is_none")]
+    label_overlap: Option<LabelOverlap>,
+    #[serde(rename = "tickCount", skip_serializing_if = "Option::is_none")]
+    tick_count: Option<u32>,
+}
+
+impl Axis {
+    /// Creates an axis for the scale with the given name, drawn on the given side of the chart
+    pub fn new(orient: Orientation, scale: &str) -> Axis {
+        Axis {
+            orient,
+            scale: String::from(scale),
+            offset: None,
+            position: None,
+            zindex: None,
+            domain: None,
+            domain_color: None,
+            grid: None,
+            grid_color: None,
+            grid_dash: None,
+            ticks: None,
+            tick_size: None,
+            title: None,
+            title_font_size: None,
+            title_color: None,
+            format: None,
+            format_type: None,
+            label_align: None,
+            label_angle: None,
+            label_limit: None,
+            label_overlap: None,
+            tick_count: None,
+        }
+    }
+
+    /// Moves the axis to another side of the chart
+    pub fn set_orient(&mut self, orient: Orientation) {
+        self.orient = orient;
+    }
+
+    /// Moves the axis away from the edge of the plot by the given number of pixels
+    pub fn set_offset(&mut self, offset: i32) {
+        self.offset = Some(offset);
+    }
+
+    /// Anchors the axis at the given pixel position instead of the edge of the plot
+    pub fn set_position(&mut self, position: i32) {
+        self.position = Some(position);
+    }
+
+    /// Sets the drawing order of the axis. An axis with a zindex of 1 or more is drawn
+    /// above the marks of the chart
+    pub fn set_zindex(&mut self, zindex: i32) {
+        self.zindex = Some(zindex);
+    }
+
+    /// Toggles the domain line, i.e. the baseline the ticks of the axis are attached to
+    pub fn show_domain(&mut self, show: bool) {
+        self.domain = Some(show);
+    }
+
+    /// Sets the title shown along the axis, e.g. the quantity and unit of its values
+    pub fn set_title(&mut self, title: &str) {
+        self.title = Some(String::from(title));
+    }
+
+    /// Sets the font size of the title in pixels
+    pub fn set_title_font_size(&mut self, size: f64) {
+        self.title_font_size = Some(size);
+    }
+
+    pub fn set_title_color(&mut self, color: &str) {
+        self.title_color = Some(String::from(color));
+    }
+
+    /// Sets the d3 format string used for the tick labels, e.g. ".0%" for percentages
+    pub fn set_format(&mut self, format: &str) {
+        self.format = Some(String::from(format));
+    }
+
+    /// The d3 format string of the tick labels, if one was set
+    pub fn format(&self) -> Option<&str> {
+        self.format.as_ref().map(String::as_str)
+    }
+
+    /// Leaves the tick labels to Vega's default format again
+    pub fn clear_format(&mut self) {
+        self.format = None;
+    }
+
+    /// Sets how the tick labels are written. Date and time patterns turn the labels of an axis
+    /// that isn't formatted in UTC (see `set_utc`) into local times, while other formats leave
+    /// the type of the labels to Vega again.
+    pub fn set_tick_format(&mut self, format: TickFormat) {
+        match format {
+            TickFormat::DateTime(_) => match self.format_type {
+                Some(FormatType::Utc) | Some(FormatType::Time) => (),
+                _ => self.format_type = Some(FormatType::Time),
+            },
+            _ => self.format_type = None,
+        }
+        self.format = Some(format.pattern());
+    }
+
+    pub fn set_format_type(&mut self, format_type: FormatType) {
+        self.format_type = Some(format_type);
+    }
+
+    /// Formats the ticks of a temporal axis in UTC, or in the local time of the viewer. This
+    /// should match the setting of the scale behind the axis (see `Scale::set_utc`).
+    pub fn set_utc(&mut self, utc: bool) {
+        self.format_type = Some(if utc { FormatType::Utc } else { FormatType::Time });
+    }
+
+    /// Sets the horizontal alignment of the tick labels relative to their ticks
+    pub fn set_label_align(&mut self, align: Align) {
+        self.label_align = Some(String::from(align.name()));
+    }
+
+    /// Leaves the alignment of the tick labels to Vega, which aligns them away from the plot
+    pub fn clear_label_align(&mut self) {
+        self.label_align = None;
+    }
+
+    /// Applies the label options that were set, leaving the others as they are
+    pub fn apply_labels(&mut self, labels: &AxisLabels) {
+        if labels.label_angle.is_some() {
+            self.label_angle = labels.label_angle;
+        }
+        if labels.label_limit.is_some() {
+            self.label_limit = labels.label_limit;
+        }
+        if labels.label_overlap.is_some() {
+            self.label_overlap = labels.label_overlap;
+        }
+        if labels.tick_count.is_some() {
+            self.tick_count = labels.tick_count;
+        }
+    }
+
+    /// Applies the line options that were set in the style, leaving the others as they are
+    pub fn apply_style(&mut self, style: &AxisStyle) {
+        if style.grid.is_some() {
+            self.grid = style.grid;
+        }
+        if style.grid_color.is_some() {
+            self.grid_color = style.grid_color.clone();
+        }
+        if style.grid_dash.is_some() {
+            self.grid_dash = style.grid_dash.clone();
+        }
+        if style.domain.is_some() {
+            self.domain = style.domain;
+        }
+        if style.domain_color.is_some() {
+            self.domain_color = style.domain_color.clone();
+        }
+        if style.ticks.is_some() {
+            self.ticks = style.ticks;
+        }
+        if style.tick_size.is_some() {
+            self.tick_size = style.tick_size;
+        }
+    }
+}
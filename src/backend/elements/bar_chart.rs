@@ -1,12 +1,18 @@
 
 
 use backend::elements::general::*;
-use serde::ser::{Serialize, SerializeStruct, Serializer};
+use backend::elements::mark::{when, Production, ValueRef};
+use backend::elements::transform::Transform;
+use serde::ser::{Error, Serialize, SerializeStruct, Serializer};
+use serde_json;
+use serde_json::Value;
 
 #[derive(Serialize)]
 pub struct BarChartData {
     name: String,
     values: Vec<BarChartValue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    transform: Vec<Transform>,
 }
 
 impl BarChartData {
@@ -14,110 +20,51 @@ impl BarChartData {
         BarChartData {
             name: String::from("table"),
             values: vec![],
+            transform: vec![],
         }
     }
+    pub fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        &mut self.transform
+    }
     pub fn clear(&mut self) {
         self.values.clear()
     }
-    pub fn add_data(&mut self, category: String, amount: i32) {
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional)
+    }
+    pub fn add_data(&mut self, category: String, amount: f64) {
         self.values.push(BarChartValue::new(category, amount));
     }
+    pub fn add_data_with_error(&mut self, category: String, amount: f64, lo: f64, hi: f64) {
+        self.values.push(BarChartValue {
+            lo: Some(lo),
+            hi: Some(hi),
+            ..BarChartValue::new(category, amount)
+        });
+    }
 }
 
 
 #[derive(Serialize)]
 pub struct BarChartValue {
     category: String,
-    amount: i32,
+    amount: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lo: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hi: Option<f64>,
 }
 impl BarChartValue {
-    pub fn new(category: String, amount: i32) -> BarChartValue {
-        BarChartValue { category, amount }
-    }
-}
-
-
-pub struct BarChartScale {
-    name: String,
-    scale_type: String,
-    domain: BarChartDomain,
-    range: String,
-    padding: f64,
-}
-
-impl BarChartScale {
-    pub fn create_xscale() -> BarChartScale {
-        BarChartScale {
-            name: String::from("xscale"),
-            scale_type: String::from("band"),
-            domain: BarChartDomain {
-                data: String::from("table"),
-                field: String::from("category"),
-            },
-            range: String::from("width"),
-            padding: 0.05,
-        }
-    }
-
-    pub fn create_yscale() -> BarChartScale {
-        BarChartScale {
-            name: String::from("yscale"),
-            scale_type: String::from("linear"),
-            domain: BarChartDomain {
-                data: String::from("table"),
-                field: String::from("amount"),
-            },
-            range: String::from("height"),
-            padding: 0.05,
+    pub fn new(category: String, amount: f64) -> BarChartValue {
+        BarChartValue {
+            category,
+            amount,
+            lo: None,
+            hi: None,
         }
     }
 }
 
-impl Serialize for BarChartScale {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_struct("scale", 10)?;
-        s.serialize_field("name", &self.name)?;
-        s.serialize_field("type", &self.scale_type)?;
-        s.serialize_field("domain", &self.domain)?;
-        s.serialize_field("range", &self.range)?;
-        s.serialize_field("padding", &self.padding)?;
-        s.end()
-    }
-}
-
-#[derive(Serialize)]
-struct BarChartDomain {
-    data: String,
-    field: String,
-}
-
-#[derive(Serialize)]
-pub struct BarChartAxis {
-    orient: Orientation,
-    scale: String,
-}
-
-impl BarChartAxis {
-    /// create_xaxis creates a default Axis for the bar chart
-    /// It specifies A BarChartAxis with the following criteria:
-    /// Scale: xscale, Orientation::Bottom,
-    pub fn create_xaxis() -> BarChartAxis {
-        BarChartAxis {
-            orient: Orientation::Bottom,
-            scale: String::from("xscale"),
-        }
-    }
-
-    pub fn create_yaxis() -> BarChartAxis {
-        BarChartAxis {
-            orient: Orientation::Left,
-            scale: String::from("yscale"),
-        }
-    }
-}
 
 pub struct BarChartMark {
     mark_type: String,
@@ -136,6 +83,23 @@ impl BarChartMark {
     pub fn set_color(&mut self, color: &str) {
         self.encode.set_color(color);
     }
+    pub fn set_fill_opacity(&mut self, opacity: f64) {
+        self.encode.update.fill_opacity = Some(QualKeyVal::new("value", opacity as f32));
+    }
+    pub fn set_stroke_color(&mut self, color: &str) {
+        self.encode.enter.stroke = Some(KeyVal::new("value", color));
+    }
+    pub fn set_stroke_width(&mut self, width: f64) {
+        self.encode.enter.stroke_width = Some(QualKeyVal::new("value", width as f32));
+    }
+    pub fn set_corner_radius(&mut self, radius: f64) {
+        self.encode.enter.corner_radius = Some(QualKeyVal::new("value", radius as f32));
+    }
+    /// Fills the bars passing the Vega expression `test` with `color` instead, unless an
+    /// earlier rule applies to them already
+    pub fn add_color_rule(&mut self, test: &str, color: &str) {
+        self.encode.update.add_rule(test, color);
+    }
 }
 
 impl Serialize for BarChartMark {
@@ -179,6 +143,12 @@ struct BarChartEnter {
     width: JSONDict,
     y: JSONDict,
     y2: JSONDict,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stroke: Option<KeyVal>,
+    #[serde(rename = "strokeWidth", skip_serializing_if = "Option::is_none")]
+    stroke_width: Option<QualKeyVal>,
+    #[serde(rename = "cornerRadius", skip_serializing_if = "Option::is_none")]
+    corner_radius: Option<QualKeyVal>,
 }
 impl BarChartEnter {
     pub fn default() -> BarChartEnter {
@@ -187,20 +157,61 @@ impl BarChartEnter {
             width: JSONDict::band_create("scale", "xscale", "band", 1),
             y: JSONDict::create("scale", "yscale", "field", "amount"),
             y2: JSONDict::band_create("scale", "yscale", "value", 0),
+            stroke: None,
+            stroke_width: None,
+            corner_radius: None,
         }
     }
 }
 
-#[derive(Serialize)]
+/// The fill of the bars, which rules may override for some of them
 struct BarChartFill {
     fill: JSONDict,
+    rules: Option<Production>,
+    fill_opacity: Option<QualKeyVal>,
 }
 
 impl BarChartFill {
     pub fn new(color: &str) -> BarChartFill {
-        BarChartFill { fill: JSONDict::create("value", color, "fillOpacity", "0.5") }
+        BarChartFill {
+            fill: JSONDict::create("value", color, "fillOpacity", "0.5"),
+            rules: None,
+            fill_opacity: None,
+        }
     }
     pub fn set_color(&mut self, color: &str) {
         self.fill = JSONDict::create("value", color, "fillOpacity", "0.5");
     }
+    pub fn add_rule(&mut self, test: &str, color: &str) {
+        let value = ValueRef::value(color);
+        self.rules = Some(match self.rules.take() {
+            Some(rules) => rules.when(test).then(value),
+            None => when(test).then(value),
+        });
+    }
+}
+
+impl Serialize for BarChartFill {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("fill", 2)?;
+        match self.rules {
+            Some(ref rules) => {
+                // the rules are followed by the fill of all other bars
+                let mut fill = match serde_json::to_value(rules).map_err(S::Error::custom)? {
+                    Value::Array(rules) => rules,
+                    rule => vec![rule],
+                };
+                fill.push(serde_json::to_value(&self.fill).map_err(S::Error::custom)?);
+                s.serialize_field("fill", &fill)?;
+            }
+            None => s.serialize_field("fill", &self.fill)?,
+        }
+        if let Some(ref opacity) = self.fill_opacity {
+            s.serialize_field("fillOpacity", opacity)?;
+        }
+        s.end()
+    }
 }
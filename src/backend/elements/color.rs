@@ -0,0 +1,136 @@
+/*
+ * Colors, and the color schemes of the scales charts color their series with
+ */
+
+use backend::elements::scale::Range;
+use std::fmt;
+
+/// Color is a single CSS color
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::color::Color;
+///
+///    assert_eq!(Color::rgb(70, 130, 180).to_string(), "rgb(70, 130, 180)");
+///    assert_eq!(Color::from("#4682b4"), Color::hex("#4682b4"));
+///    assert_eq!(Color::from("steelblue"), Color::named("steelblue"));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub enum Color {
+    /// A color written as "#rgb" or "#rrggbb"
+    Hex(String),
+    Rgb(u8, u8, u8),
+    /// A color with an opacity from 0 for invisible to 1 for opaque
+    Rgba(u8, u8, u8, f64),
+    /// One of the CSS color names, like "steelblue"
+    Named(String),
+}
+
+impl Color {
+    /// A color written as "#rgb" or "#rrggbb". The leading "#" may be left out.
+    pub fn hex(hex: &str) -> Color {
+        Color::Hex(format!("#{}", hex.trim_start_matches('#')))
+    }
+
+    pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color::Rgb(r, g, b)
+    }
+
+    pub fn rgba(r: u8, g: u8, b: u8, alpha: f64) -> Color {
+        Color::Rgba(r, g, b, alpha)
+    }
+
+    pub fn named(name: &str) -> Color {
+        Color::Named(String::from(name))
+    }
+}
+
+impl<'a> From<&'a str> for Color {
+    /// Reads "#..." as a hex color and anything else as a color name
+    fn from(color: &'a str) -> Color {
+        if color.starts_with('#') {
+            Color::hex(color)
+        } else {
+            Color::named(color)
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    /// Writes the color the way CSS and Vega expect it
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Color::Hex(ref hex) => write!(f, "{}", hex),
+            Color::Rgb(r, g, b) => write!(f, "rgb({}, {}, {})", r, g, b),
+            Color::Rgba(r, g, b, a) => write!(f, "rgba({}, {}, {}, {})", r, g, b, a),
+            Color::Named(ref name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// ColorScheme is the list of colors a chart colors its series or values with, see
+/// `Graphable::set_colors`
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::color::{Color, ColorScheme};
+///    use gust::backend::pie_chart::PieChart;
+///    use gust::backend::traits::Graphable;
+///
+///    let mut p = PieChart::new();
+///    p.set_colors(ColorScheme::Tableau10);
+///    p.set_colors(ColorScheme::Custom(vec![Color::hex("#1b9e77"), Color::named("orange")]));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub enum ColorScheme {
+    /// Vega's default scheme for categories
+    Category10,
+    Category20,
+    Tableau10,
+    Tableau20,
+    /// A scheme of darker colors for categories
+    Dark2,
+    /// A sequential scheme from dark blue to yellow, which is easy to read in gray scale
+    Viridis,
+    Magma,
+    Blues,
+    Greens,
+    Reds,
+    /// A diverging scheme from red over white to blue
+    RedBlue,
+    /// Any other of Vega's named schemes, e.g. "plasma" or "set2"
+    Named(String),
+    /// An explicit list of colors
+    Custom(Vec<Color>),
+}
+
+impl ColorScheme {
+    /// The name Vega knows the scheme by, unless it is a custom one
+    pub fn name(&self) -> Option<&str> {
+        let name = match *self {
+            ColorScheme::Category10 => "category10",
+            ColorScheme::Category20 => "category20",
+            ColorScheme::Tableau10 => "tableau10",
+            ColorScheme::Tableau20 => "tableau20",
+            ColorScheme::Dark2 => "dark2",
+            ColorScheme::Viridis => "viridis",
+            ColorScheme::Magma => "magma",
+            ColorScheme::Blues => "blues",
+            ColorScheme::Greens => "greens",
+            ColorScheme::Reds => "reds",
+            ColorScheme::RedBlue => "redblue",
+            ColorScheme::Named(ref name) => name.as_str(),
+            ColorScheme::Custom(_) => return None,
+        };
+        Some(name)
+    }
+
+    /// The range of a color scale using the scheme
+    pub fn range(&self) -> Range {
+        match *self {
+            ColorScheme::Custom(ref colors) => {
+                let colors: Vec<String> = colors.iter().map(|c| c.to_string()).collect();
+                Range::Values(colors)
+            }
+            _ => Range::scheme(self.name().unwrap_or_default()),
+        }
+    }
+}
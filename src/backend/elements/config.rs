@@ -0,0 +1,422 @@
+
+/*
+ * The Vega config, which sets the defaults of marks, axes, legends and the ranges of scales
+ * for a whole chart
+ */
+
+use backend::elements::general::Orientation;
+use backend::elements::mark::MarkType;
+use backend::elements::title::Anchor;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::{Map, Value};
+
+/// MarkConfig holds the default properties of marks. Marks that set a property themselves
+/// keep their own value.
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fill: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stroke: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stroke_width: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opacity: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    corner_radius: Option<f64>,
+}
+
+impl MarkConfig {
+    pub fn new() -> MarkConfig {
+        MarkConfig::default()
+    }
+
+    pub fn fill(mut self, color: &str) -> MarkConfig {
+        self.fill = Some(String::from(color));
+        self
+    }
+
+    pub fn stroke(mut self, color: &str, width: f64) -> MarkConfig {
+        self.stroke = Some(String::from(color));
+        self.stroke_width = Some(width);
+        self
+    }
+
+    /// Sets the opacity, from 0 for invisible to 1 for opaque
+    pub fn opacity(mut self, opacity: f64) -> MarkConfig {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// Sets the font of text marks, the size being in pixels
+    pub fn font(mut self, font: &str, size: f64) -> MarkConfig {
+        self.font = Some(String::from(font));
+        self.font_size = Some(size);
+        self
+    }
+
+    /// Rounds the corners of rect marks by the given radius in pixels
+    pub fn corner_radius(mut self, radius: f64) -> MarkConfig {
+        self.corner_radius = Some(radius);
+        self
+    }
+}
+
+/// AxisConfig holds the default properties of axes
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AxisConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grid_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    domain_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tick_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label_font: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label_font_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title_font: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title_font_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title_color: Option<String>,
+}
+
+impl AxisConfig {
+    pub fn new() -> AxisConfig {
+        AxisConfig::default()
+    }
+
+    /// Draws grid lines across the chart at every tick, in the given color
+    pub fn grid(mut self, color: &str) -> AxisConfig {
+        self.grid = Some(true);
+        self.grid_color = Some(String::from(color));
+        self
+    }
+
+    /// Sets the color of the domain line and the ticks
+    pub fn color(mut self, color: &str) -> AxisConfig {
+        self.domain_color = Some(String::from(color));
+        self.tick_color = Some(String::from(color));
+        self
+    }
+
+    /// Sets the font, its size in pixels and the color of the labels at the ticks
+    pub fn labels(mut self, font: &str, size: f64, color: &str) -> AxisConfig {
+        self.label_font = Some(String::from(font));
+        self.label_font_size = Some(size);
+        self.label_color = Some(String::from(color));
+        self
+    }
+
+    /// Sets the font, its size in pixels and the color of the title of the axis
+    pub fn titles(mut self, font: &str, size: f64, color: &str) -> AxisConfig {
+        self.title_font = Some(String::from(font));
+        self.title_font_size = Some(size);
+        self.title_color = Some(String::from(color));
+        self
+    }
+}
+
+/// LegendConfig holds the default properties of legends
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LegendConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    orient: Option<Orientation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label_font: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label_font_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title_font: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title_font_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol_size: Option<f64>,
+}
+
+impl LegendConfig {
+    pub fn new() -> LegendConfig {
+        LegendConfig::default()
+    }
+
+    /// Sets the side of the chart legends are placed on
+    pub fn orient(mut self, orient: Orientation) -> LegendConfig {
+        self.orient = Some(orient);
+        self
+    }
+
+    /// Sets the font and its size in pixels of the labels of the entries
+    pub fn labels(mut self, font: &str, size: f64) -> LegendConfig {
+        self.label_font = Some(String::from(font));
+        self.label_font_size = Some(size);
+        self
+    }
+
+    /// Sets the font and its size in pixels of the title of the legend
+    pub fn titles(mut self, font: &str, size: f64) -> LegendConfig {
+        self.title_font = Some(String::from(font));
+        self.title_font_size = Some(size);
+        self
+    }
+
+    /// Sets the area of the symbols of the entries, in square pixels
+    pub fn symbol_size(mut self, size: f64) -> LegendConfig {
+        self.symbol_size = Some(size);
+        self
+    }
+}
+
+/// TitleConfig holds the default properties of the title of the chart
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TitleConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    anchor: Option<Anchor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<f64>,
+}
+
+impl TitleConfig {
+    pub fn new() -> TitleConfig {
+        TitleConfig::default()
+    }
+
+    /// Sets where titles are placed along the top of the chart
+    pub fn anchor(mut self, anchor: Anchor) -> TitleConfig {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    /// Sets the font, its size in pixels and the color of titles
+    pub fn font(mut self, font: &str, size: f64, color: &str) -> TitleConfig {
+        self.font = Some(String::from(font));
+        self.font_size = Some(size);
+        self.color = Some(String::from(color));
+        self
+    }
+
+    /// Sets the distance in pixels between titles and the chart
+    pub fn offset(mut self, offset: f64) -> TitleConfig {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// Locale sets how numbers are formatted, e.g. with a decimal comma in German
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::config::{Config, Locale};
+///
+///    let german = Locale::new(",", ".").currency("", "\u{a0}€");
+///    let config = Config::new().locale(german);
+/// ```
+#[derive(Serialize)]
+pub struct Locale {
+    decimal: String,
+    thousands: String,
+    grouping: Vec<u32>,
+    currency: [String; 2],
+}
+
+impl Locale {
+    /// A locale with the given decimal and thousands separators, grouping digits by three
+    /// and writing amounts of money in dollars
+    pub fn new(decimal: &str, thousands: &str) -> Locale {
+        Locale {
+            decimal: String::from(decimal),
+            thousands: String::from(thousands),
+            grouping: vec![3],
+            currency: [String::from("$"), String::new()],
+        }
+    }
+
+    /// Sets the sizes of the groups of digits, from the right, e.g. [3, 2] for the Indian
+    /// grouping of 12,34,567. The last size repeats.
+    pub fn grouping(mut self, grouping: &[u32]) -> Locale {
+        self.grouping = grouping.to_vec();
+        self
+    }
+
+    /// Sets the text before and after amounts of money
+    pub fn currency(mut self, prefix: &str, suffix: &str) -> Locale {
+        self.currency = [String::from(prefix), String::from(suffix)];
+        self
+    }
+}
+
+/// The locale block of the config, which only holds the number locale so far
+#[derive(Serialize)]
+struct NumberLocale<'a> {
+    number: &'a Locale,
+}
+
+/// Config sets the defaults of a whole chart, so the styling doesn't have to be repeated on
+/// every mark or axis. It is emitted as the top level `config` of the spec, on top of the
+/// theme of the chart if it has one.
+/// # Example:
+/// ```rust
+///    use gust::backend::bar_chart::BarChart;
+///    use gust::backend::elements::config::{AxisConfig, Config, MarkConfig};
+///    use gust::backend::elements::mark::MarkType;
+///    use gust::backend::traits::Graphable;
+///
+///    let mut b = BarChart::new();
+///    b.set_config(
+///        Config::new()
+///            .mark(MarkConfig::new().opacity(0.9))
+///            .mark_type(MarkType::Rect, MarkConfig::new().corner_radius(2.0))
+///            .axis(AxisConfig::new().grid("#eee").labels("Helvetica", 11.0, "#333"))
+///            .range_scheme("category", "tableau10"),
+///    );
+/// ```
+#[derive(Default)]
+pub struct Config {
+    background: Option<String>,
+    mark: Option<MarkConfig>,
+    mark_types: Vec<(MarkType, MarkConfig)>,
+    axis: Option<AxisConfig>,
+    axis_x: Option<AxisConfig>,
+    axis_y: Option<AxisConfig>,
+    legend: Option<LegendConfig>,
+    title: Option<TitleConfig>,
+    locale: Option<Locale>,
+    range: Map<String, Value>,
+}
+
+impl Config {
+    pub fn new() -> Config {
+        Config::default()
+    }
+
+    pub fn background(mut self, color: &str) -> Config {
+        self.background = Some(String::from(color));
+        self
+    }
+
+    /// Sets the defaults of all marks
+    pub fn mark(mut self, defaults: MarkConfig) -> Config {
+        self.mark = Some(defaults);
+        self
+    }
+
+    /// Sets the defaults of the marks of one type, which take precedence over the ones set
+    /// through `mark`
+    pub fn mark_type(mut self, mark_type: MarkType, defaults: MarkConfig) -> Config {
+        self.mark_types.retain(|&(ref t, _)| t.name() != mark_type.name());
+        self.mark_types.push((mark_type, defaults));
+        self
+    }
+
+    /// Sets the defaults of all axes
+    pub fn axis(mut self, defaults: AxisConfig) -> Config {
+        self.axis = Some(defaults);
+        self
+    }
+
+    /// Sets the defaults of horizontal axes, which take precedence over the ones set through
+    /// `axis`
+    pub fn x_axis(mut self, defaults: AxisConfig) -> Config {
+        self.axis_x = Some(defaults);
+        self
+    }
+
+    /// Sets the defaults of vertical axes, which take precedence over the ones set through
+    /// `axis`
+    pub fn y_axis(mut self, defaults: AxisConfig) -> Config {
+        self.axis_y = Some(defaults);
+        self
+    }
+
+    /// Sets the defaults of all legends
+    pub fn legend(mut self, defaults: LegendConfig) -> Config {
+        self.legend = Some(defaults);
+        self
+    }
+
+    /// Sets the defaults of the title of the chart
+    pub fn title(mut self, defaults: TitleConfig) -> Config {
+        self.title = Some(defaults);
+        self
+    }
+
+    /// Formats the numbers of the chart, like its axis labels, following the locale
+    pub fn locale(mut self, locale: Locale) -> Config {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Fills a named range, e.g. "category" or "ramp", with a Vega color scheme like
+    /// "tableau10" or "viridis". Scales over the range use the scheme.
+    pub fn range_scheme(mut self, range: &str, scheme: &str) -> Config {
+        let mut s = Map::new();
+        s.insert(String::from("scheme"), Value::from(scheme));
+        self.range.insert(String::from(range), Value::Object(s));
+        self
+    }
+
+    /// Fills a named range with the given colors
+    pub fn range_colors(mut self, range: &str, colors: &[&str]) -> Config {
+        let colors = colors.iter().map(|&c| Value::from(c)).collect();
+        self.range.insert(String::from(range), Value::Array(colors));
+        self
+    }
+}
+
+impl Serialize for Config {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut m = serializer.serialize_map(None)?;
+        if let Some(ref background) = self.background {
+            m.serialize_entry("background", background)?;
+        }
+        if let Some(ref mark) = self.mark {
+            m.serialize_entry("mark", mark)?;
+        }
+        for &(ref mark_type, ref defaults) in &self.mark_types {
+            m.serialize_entry(mark_type.name(), defaults)?;
+        }
+        if let Some(ref axis) = self.axis {
+            m.serialize_entry("axis", axis)?;
+        }
+        if let Some(ref axis) = self.axis_x {
+            m.serialize_entry("axisX", axis)?;
+        }
+        if let Some(ref axis) = self.axis_y {
+            m.serialize_entry("axisY", axis)?;
+        }
+        if let Some(ref legend) = self.legend {
+            m.serialize_entry("legend", legend)?;
+        }
+        if let Some(ref title) = self.title {
+            m.serialize_entry("title", title)?;
+        }
+        if let Some(ref locale) = self.locale {
+            m.serialize_entry("locale", &NumberLocale { number: locale })?;
+        }
+        if !self.range.is_empty() {
+            m.serialize_entry("range", &self.range)?;
+        }
+        m.end()
+    }
+}
@@ -0,0 +1,244 @@
+
+/*
+ * Generic data sets, used for data that is added to a chart on top of its own data
+ */
+
+use backend::elements::transform::Transform;
+use serde::ser::Serialize;
+use serde_json;
+use serde_json::Value;
+
+/// Format tells Vega how to parse data that is loaded from a url
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Format {
+    /// A json document. If `property` is set, the data objects are read from that property of
+    /// the document instead of the document itself.
+    Json {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        property: Option<String>,
+    },
+    /// Comma separated values. With `parse` set to "auto", Vega turns the text of every
+    /// column into numbers, booleans or dates where it can.
+    Csv {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse: Option<String>,
+    },
+    /// Tab separated values, parsed like `Csv`
+    Tsv {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse: Option<String>,
+    },
+    /// A TopoJSON document, which is either turned into one GeoJSON feature per geometry of the
+    /// object `feature`, or into a single mesh of the borders of the object `mesh`
+    Topojson {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        feature: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mesh: Option<String>,
+    },
+}
+
+impl Format {
+    /// A json document holding an array of data objects
+    pub fn json() -> Format {
+        Format::Json { property: None }
+    }
+
+    /// A csv file whose first row names the fields, with the types of the values inferred
+    pub fn csv() -> Format {
+        Format::Csv { parse: Some(String::from("auto")) }
+    }
+
+    /// A tsv file whose first row names the fields, with the types of the values inferred
+    pub fn tsv() -> Format {
+        Format::Tsv { parse: Some(String::from("auto")) }
+    }
+
+    /// The features of a GeoJSON feature collection
+    pub fn geojson() -> Format {
+        Format::Json { property: Some(String::from("features")) }
+    }
+
+    /// The geometries of the given object of a TopoJSON document, as GeoJSON features
+    pub fn topojson_feature(object: &str) -> Format {
+        Format::Topojson {
+            feature: Some(String::from(object)),
+            mesh: None,
+        }
+    }
+
+    /// The borders between the geometries of the given object of a TopoJSON document, where
+    /// every border is only drawn once
+    pub fn topojson_mesh(object: &str) -> Format {
+        Format::Topojson {
+            feature: None,
+            mesh: Some(String::from(object)),
+        }
+    }
+}
+
+/// DataSource replaces the values of the data of a chart, see `Graphable::set_data_url` and
+/// `Graphable::set_records`
+pub enum DataSource {
+    /// The values are loaded from the url
+    Url { url: String, format: Format },
+    /// The values are the given records, whose fields are copied into the fields the chart
+    /// expects, as listed by the (chart field, record field) pairs of `fields`
+    Records {
+        values: Vec<Value>,
+        fields: Vec<(String, String)>,
+    },
+}
+
+impl DataSource {
+    /// Serializes the data set `data`, with its values replaced by the source. Its name and
+    /// transforms are kept.
+    pub fn apply<T: Serialize>(&self, data: &T) -> Result<Value, serde_json::Error> {
+        let mut value = serde_json::to_value(data)?;
+        if let Some(object) = value.as_object_mut() {
+            object.remove("values");
+            match *self {
+                DataSource::Url { ref url, ref format } => {
+                    object.insert(String::from("url"), Value::from(url.as_str()));
+                    object.insert(String::from("format"), serde_json::to_value(format)?);
+                }
+                DataSource::Records { ref values, ref fields } => {
+                    object.insert(String::from("values"), Value::Array(values.clone()));
+                    // the fields are copied first, so the chart's own transforms can use them
+                    let mut transforms = vec![];
+                    for &(ref field, ref record_field) in fields {
+                        let expr = format!("datum[{}]", Value::from(record_field.as_str()));
+                        transforms.push(serde_json::to_value(&Transform::formula(&expr, field))?);
+                    }
+                    if let Some(Value::Array(existing)) = object.remove("transform") {
+                        transforms.extend(existing);
+                    }
+                    if !transforms.is_empty() {
+                        object.insert(String::from("transform"), Value::Array(transforms));
+                    }
+                }
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// DataSet is a generic, named Vega data set. It either holds its own values, loads them from a
+/// url, or derives them from another data set of the chart by running its transforms over it.
+#[derive(Serialize)]
+pub struct DataSet {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    values: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<Format>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    transform: Vec<Transform>,
+}
+
+impl DataSet {
+    /// Creates a data set holding the given values
+    pub fn from_values(name: &str, values: Vec<Value>) -> DataSet {
+        DataSet { values: Some(values), ..DataSet::new(name) }
+    }
+
+    /// Creates a data set holding the given records, which can be of any type that serializes to
+    /// a json object, e.g. a struct deriving `Serialize`
+    pub fn from_records<T: Serialize>(name: &str, records: &[T]) -> Result<DataSet, serde_json::Error> {
+        let mut values = Vec::with_capacity(records.len());
+        for record in records {
+            values.push(serde_json::to_value(record)?);
+        }
+        Ok(DataSet::from_values(name, values))
+    }
+
+    /// Creates an empty data set, whose values are generated by one of its transforms, e.g. a
+    /// graticule
+    pub fn new(name: &str) -> DataSet {
+        DataSet {
+            name: String::from(name),
+            source: None,
+            values: None,
+            url: None,
+            format: None,
+            transform: vec![],
+        }
+    }
+
+    /// Creates a data set which derives its values from the data set called `source`
+    pub fn from_source(name: &str, source: &str) -> DataSet {
+        DataSet { source: Some(String::from(source)), ..DataSet::new(name) }
+    }
+
+    /// Creates a data set which Vega loads from the given url when the chart is drawn, parsed
+    /// according to `format`
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::data::{DataSet, Format};
+    ///
+    ///    let countries = DataSet::from_url(
+    ///        "countries",
+    ///        "https://cdn.jsdelivr.net/npm/vega-datasets@2/data/world-110m.json",
+    ///        Format::topojson_feature("countries"),
+    ///    );
+    /// ```
+    pub fn from_url(name: &str, url: &str, format: Format) -> DataSet {
+        DataSet {
+            url: Some(String::from(url)),
+            format: Some(format),
+            ..DataSet::new(name)
+        }
+    }
+
+    /// Creates a data set holding the features of a GeoJSON feature collection, or the single
+    /// feature if `geojson` is a feature or a geometry
+    pub fn from_geojson(name: &str, geojson: &Value) -> DataSet {
+        let features = match geojson.get("features") {
+            Some(&Value::Array(ref features)) => features.clone(),
+            _ => vec![geojson.clone()],
+        };
+        DataSet::from_values(name, features)
+    }
+
+    /// The name marks and scales refer to the data set by
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the data set derives its values from another data set
+    pub fn is_derived(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// Appends a data object to a data set holding its own values
+    pub fn add_value(&mut self, value: Value) {
+        self.values_mut().push(value);
+    }
+
+    /// The values of a data set holding its own values
+    pub fn values_mut(&mut self) -> &mut Vec<Value> {
+        self.values.get_or_insert_with(Vec::new)
+    }
+
+    pub fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        &mut self.transform
+    }
+
+    pub fn add_transform(&mut self, transform: Transform) {
+        self.transform.push(transform);
+    }
+
+    /// Copies the given properties of GeoJSON features into fields of the same name, so they
+    /// can be used by scales, tooltips or lookups like any other field
+    pub fn extract_properties(&mut self, properties: &[&str]) {
+        for property in properties {
+            let expr = format!("datum.properties['{}']", property);
+            self.add_transform(Transform::formula(&expr, property));
+        }
+    }
+}
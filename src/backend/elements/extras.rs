@@ -0,0 +1,422 @@
+
+/*
+ * Everything that is added to a chart on top of the elements its chart type defines
+ */
+
+use backend::elements::config::Config;
+use backend::elements::data::{DataSet, DataSource, Format};
+use backend::elements::geo::Projection;
+use backend::elements::legend::Legend;
+use backend::elements::mark::Mark;
+use backend::elements::signal::Signal;
+use backend::elements::theme::Theme;
+use backend::elements::title::Title;
+use backend::elements::tooltip::Tooltip;
+use serde::ser::{Error, Serialize, SerializeSeq, SerializeStruct, Serializer};
+use serde_json;
+use serde_json::{Map, Value};
+
+/// Extras holds the data sets, marks and signals that were added to a chart, e.g. by an overlay,
+/// along with top level properties of the spec like projections, legends or the theme.
+///
+/// Vega only lets data sets refer to data sets defined before them, so data sets holding their
+/// own values are emitted ahead of the chart's data (where the chart's transforms can look
+/// them up), and derived ones after it. Added marks are drawn on top of the chart's own marks.
+pub struct Extras {
+    data: Vec<DataSet>,
+    marks: Vec<Mark>,
+    signals: Vec<Signal>,
+    projections: Vec<Projection>,
+    legends: Vec<Legend>,
+    title: Option<Title>,
+    theme: Option<Theme>,
+    config: Option<Config>,
+    description: Option<String>,
+    rtl: bool,
+    watermark: Option<Mark>,
+    tooltip: Option<Tooltip>,
+    usermeta: Map<String, Value>,
+    data_source: Option<DataSource>,
+}
+
+impl Extras {
+    pub fn new() -> Extras {
+        Extras {
+            data: vec![],
+            marks: vec![],
+            signals: vec![],
+            projections: vec![],
+            legends: vec![],
+            title: None,
+            theme: None,
+            config: None,
+            description: None,
+            rtl: false,
+            watermark: None,
+            tooltip: None,
+            usermeta: Map::new(),
+            data_source: None,
+        }
+    }
+
+    pub fn data(&self) -> &[DataSet] {
+        &self.data
+    }
+
+    pub fn marks(&self) -> &[Mark] {
+        &self.marks
+    }
+
+    pub fn add_data_set(&mut self, data: DataSet) {
+        self.data.push(data);
+    }
+
+    /// The added data set with the given name
+    pub fn data_set_mut(&mut self, name: &str) -> Option<&mut DataSet> {
+        self.data.iter_mut().find(|d| d.name() == name)
+    }
+
+    pub fn add_mark(&mut self, mark: Mark) {
+        self.marks.push(mark);
+    }
+
+    pub fn add_signal(&mut self, signal: Signal) {
+        self.signals.push(signal);
+    }
+
+    /// Whether a signal with the given name has been added
+    pub fn has_signal(&self, name: &str) -> bool {
+        self.signals.iter().any(|s| s.name() == name)
+    }
+
+    pub fn add_projection(&mut self, projection: Projection) {
+        self.projections.push(projection);
+    }
+
+    pub fn add_legend(&mut self, legend: Legend) {
+        self.legends.push(legend);
+    }
+
+    /// Adds a legend in place of the legends explaining the scale `scale`, e.g. the one a chart
+    /// shows for its series by default
+    pub fn replace_legend(&mut self, scale: &str, legend: Legend) {
+        let at = self.legends.iter().position(|l| l.explains(scale));
+        self.legends.retain(|l| !l.explains(scale));
+        let at = at.unwrap_or(self.legends.len());
+        self.legends.insert(at, legend);
+    }
+
+    /// The title of the chart, which is created without any text if the chart has none yet
+    pub fn title_mut(&mut self) -> &mut Title {
+        self.title.get_or_insert_with(|| Title::new(""))
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = Some(theme);
+    }
+
+    pub fn set_config(&mut self, config: Config) {
+        self.config = Some(config);
+    }
+
+    pub fn set_description(&mut self, description: &str) {
+        self.description = Some(String::from(description));
+    }
+
+    /// Sets a mark that is drawn on top of all other marks, replacing any earlier watermark
+    pub fn set_watermark(&mut self, watermark: Mark) {
+        self.watermark = Some(watermark);
+    }
+
+    /// Shows the tooltip on all marks drawn from data, see `Tooltip::apply`
+    pub fn set_tooltip(&mut self, tooltip: Tooltip) {
+        self.tooltip = Some(tooltip);
+    }
+
+    /// Sets an entry of the usermeta block, replacing an earlier value with the same key
+    pub fn set_metadata(&mut self, key: &str, value: Value) {
+        self.usermeta.insert(String::from(key), value);
+    }
+
+    /// Loads the first data set of the chart from `url`, instead of the values it holds
+    pub fn set_data_url(&mut self, url: &str, format: Format) {
+        self.data_source = Some(DataSource::Url {
+            url: String::from(url),
+            format,
+        });
+    }
+
+    /// Fills the first data set of the chart with `values` instead of the values it holds, see
+    /// `DataSource::Records`
+    pub fn set_records(&mut self, values: Vec<Value>, fields: &[(&str, &str)]) {
+        self.data_source = Some(DataSource::Records {
+            values,
+            fields: fields
+                .iter()
+                .map(|&(field, record_field)| (String::from(field), String::from(record_field)))
+                .collect(),
+        });
+    }
+
+    pub fn set_right_to_left(&mut self, rtl: bool) {
+        self.rtl = rtl;
+    }
+
+    /// Whether the text of the chart is laid out right-to-left
+    pub fn is_right_to_left(&self) -> bool {
+        self.rtl
+    }
+
+    /// Writes the top level properties that were added to a chart into the spec of the chart.
+    /// Properties that were never set are left out.
+    pub fn serialize_fields<S: SerializeStruct>(&self, s: &mut S) -> Result<(), S::Error> {
+        self.serialize_fields_with::<S, Signal>(s, &[])
+    }
+
+    /// Same as `serialize_fields`, for charts defining signals of their own, which go ahead of
+    /// the added ones
+    pub fn serialize_fields_with<S: SerializeStruct, T: Serialize>(
+        &self,
+        s: &mut S,
+        signals: &[T],
+    ) -> Result<(), S::Error> {
+        if !signals.is_empty() || !self.signals.is_empty() {
+            let signals = Layered {
+                own: signals,
+                added: &self.signals,
+                last: None,
+                source: None,
+                tooltip: None,
+            };
+            s.serialize_field("signals", &signals)?;
+        }
+        if !self.projections.is_empty() {
+            s.serialize_field("projections", &self.projections)?;
+        }
+        if !self.legends.is_empty() {
+            if self.rtl {
+                let legends: Vec<Legend> = self.legends.iter().map(Legend::right_to_left).collect();
+                s.serialize_field("legends", &legends)?;
+            } else {
+                s.serialize_field("legends", &self.legends)?;
+            }
+        }
+        if let Some(ref title) = self.title {
+            if self.rtl {
+                s.serialize_field("title", &title.right_to_left())?;
+            } else {
+                s.serialize_field("title", title)?;
+            }
+        }
+        if let Some(ref description) = self.description {
+            s.serialize_field("description", description)?;
+        }
+        if self.theme.is_some() || self.config.is_some() {
+            let mut config = Map::new();
+            if let Some(ref theme) = self.theme {
+                merge(&mut config, object(theme).map_err(S::Error::custom)?);
+            }
+            if let Some(ref defaults) = self.config {
+                merge(&mut config, object(defaults).map_err(S::Error::custom)?);
+            }
+            s.serialize_field("config", &config)?;
+        }
+        if !self.usermeta.is_empty() {
+            s.serialize_field("usermeta", &self.usermeta)?;
+        }
+        Ok(())
+    }
+
+    /// Writes everything that was added to a chart into the json specification `spec`, whose
+    /// data sets, marks and signals take the place of the chart's own. Lists like the legends
+    /// are appended to, objects like the config are merged, and other properties replaced.
+    pub fn merge_into(&self, spec: &mut Map<String, Value>) -> Result<(), serde_json::Error> {
+        for &key in &["data", "marks"] {
+            let had_key = spec.contains_key(key);
+            let own = match spec.remove(key) {
+                Some(Value::Array(own)) => own,
+                _ => vec![],
+            };
+            let layered = if key == "data" {
+                serde_json::to_value(self.data_with(&own))?
+            } else {
+                serde_json::to_value(self.marks_with(&own))?
+            };
+            if had_key || layered.as_array().map_or(false, |l| !l.is_empty()) {
+                spec.insert(String::from(key), layered);
+            }
+        }
+        let signals = match spec.get("signals") {
+            Some(&Value::Array(ref signals)) => signals.clone(),
+            _ => vec![],
+        };
+        let mut replaced = Map::new();
+        for (key, value) in object(&Fields { extras: self, signals: &signals })? {
+            match (spec.get_mut(&key), value) {
+                (Some(&mut Value::Array(ref mut own)), Value::Array(added)) if key != "signals" => {
+                    own.extend(added)
+                }
+                (_, value) => {
+                    replaced.insert(key, value);
+                }
+            }
+        }
+        merge(spec, replaced);
+        Ok(())
+    }
+
+    /// The data sets of a chart, `own` being the ones its chart type defines
+    pub fn data_with<'a, T: Serialize>(&'a self, own: &'a [T]) -> Layered<'a, T, DataSet> {
+        Layered {
+            own,
+            added: &self.data,
+            last: None,
+            source: self.data_source.as_ref(),
+            tooltip: None,
+        }
+    }
+
+    /// The marks of a chart, `own` being the ones its chart type defines
+    pub fn marks_with<'a, T: Serialize>(&'a self, own: &'a [T]) -> Layered<'a, T, Mark> {
+        Layered {
+            own,
+            added: &self.marks,
+            last: self.watermark.as_ref(),
+            source: None,
+            tooltip: self.tooltip.as_ref(),
+        }
+    }
+}
+
+/// Fields serializes the top level properties of a chart with the signals `signals` of its
+/// own, see `Extras::serialize_fields_with`
+struct Fields<'a> {
+    extras: &'a Extras,
+    signals: &'a [Value],
+}
+
+impl<'a> Serialize for Fields<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("fields", 9)?;
+        self.extras.serialize_fields_with(&mut s, self.signals)?;
+        s.end()
+    }
+}
+
+/// The json object `value` serializes to
+fn object<T: Serialize>(value: &T) -> Result<Map<String, Value>, serde_json::Error> {
+    match serde_json::to_value(value)? {
+        Value::Object(object) => Ok(object),
+        _ => Ok(Map::new()),
+    }
+}
+
+/// The json value the mark `mark` serializes to, showing the tooltip
+fn with_tooltip<T: Serialize>(mark: &T, tooltip: &Tooltip) -> Result<Value, serde_json::Error> {
+    let mut value = serde_json::to_value(mark)?;
+    tooltip.apply(&mut value)?;
+    Ok(value)
+}
+
+/// Merges the object `from` into `into`, where the values of `from` take precedence
+fn merge(into: &mut Map<String, Value>, from: Map<String, Value>) {
+    for (key, value) in from {
+        let merged = match (into.remove(&key), value) {
+            (Some(Value::Object(mut existing)), Value::Object(value)) => {
+                merge(&mut existing, value);
+                Value::Object(existing)
+            }
+            (_, value) => value,
+        };
+        into.insert(key, merged);
+    }
+}
+
+/// Layered serializes the elements a chart defines itself together with the ones that were
+/// added to it as a single json array
+pub struct Layered<'a, A: 'a, B: 'a> {
+    own: &'a [A],
+    added: &'a [B],
+    /// An element that goes after all others, like the watermark
+    last: Option<&'a B>,
+    /// Where the first of the chart's own data sets takes its values from, instead of the
+    /// values it holds
+    source: Option<&'a DataSource>,
+    /// The tooltip that is added to the marks
+    tooltip: Option<&'a Tooltip>,
+}
+
+impl<'a, A: Serialize> Serialize for Layered<'a, A, DataSet> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_seq(Some(self.own.len() + self.added.len()))?;
+        for d in self.added.iter().filter(|d| !d.is_derived()) {
+            s.serialize_element(d)?;
+        }
+        for (i, o) in self.own.iter().enumerate() {
+            match self.source {
+                Some(source) if i == 0 => {
+                    s.serialize_element(&source.apply(o).map_err(S::Error::custom)?)?
+                }
+                _ => s.serialize_element(o)?,
+            }
+        }
+        for d in self.added.iter().filter(|d| d.is_derived()) {
+            s.serialize_element(d)?;
+        }
+        s.end()
+    }
+}
+
+impl<'a, A: Serialize> Serialize for Layered<'a, A, Signal> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_seq(Some(self.own.len() + self.added.len()))?;
+        for o in self.own {
+            s.serialize_element(o)?;
+        }
+        for a in self.added {
+            s.serialize_element(a)?;
+        }
+        s.end()
+    }
+}
+
+impl<'a, A: Serialize> Serialize for Layered<'a, A, Mark> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = self.own.len() + self.added.len() + self.last.iter().count();
+        let mut s = serializer.serialize_seq(Some(len))?;
+        match self.tooltip {
+            Some(tooltip) => {
+                for o in self.own {
+                    s.serialize_element(&with_tooltip(o, tooltip).map_err(S::Error::custom)?)?;
+                }
+                for m in self.added {
+                    s.serialize_element(&with_tooltip(m, tooltip).map_err(S::Error::custom)?)?;
+                }
+            }
+            None => {
+                for o in self.own {
+                    s.serialize_element(o)?;
+                }
+                for m in self.added {
+                    s.serialize_element(m)?;
+                }
+            }
+        }
+        if let Some(m) = self.last {
+            s.serialize_element(m)?;
+        }
+        s.end()
+    }
+}
@@ -2,9 +2,12 @@
 use std::collections::HashMap;
 use serde::ser::{Serialize, Serializer, SerializeStruct};
 
+/// The Vega schema all of the generated specifications adhere to
+pub const SCHEMA: &'static str = "https://vega.github.io/schema/vega/v5.json";
+
 
 /// Orientation is an enum for the different possible axis values
-#[derive(Serialize)]
+#[derive(Serialize, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum Orientation {
     Top,
@@ -13,6 +16,67 @@ pub enum Orientation {
     Right,
 }
 
+/// Interpolation is the way lines and areas are drawn in between their data points
+#[derive(Serialize, Clone, Copy)]
+pub enum Interpolation {
+    /// A smooth B-spline, which doesn't pass through the points themselves
+    #[serde(rename = "basis")]
+    Basis,
+    #[serde(rename = "cardinal")]
+    Cardinal,
+    #[serde(rename = "catmull-rom")]
+    CatmullRom,
+    /// Straight lines from point to point
+    #[serde(rename = "linear")]
+    Linear,
+    /// A smooth curve which never overshoots the points, keeping monotonic data monotonic
+    #[serde(rename = "monotone")]
+    Monotone,
+    #[serde(rename = "natural")]
+    Natural,
+    /// Steps half way in between two points
+    #[serde(rename = "step")]
+    Step,
+    /// Steps at every point, holding its value until the next point
+    #[serde(rename = "step-after")]
+    StepAfter,
+    /// Steps just before every point, taking on its value in advance
+    #[serde(rename = "step-before")]
+    StepBefore,
+}
+
+impl Interpolation {
+    /// All of the interpolations, in the order the interpolation menu of a chart lists them
+    pub fn all() -> Vec<Interpolation> {
+        vec![
+            Interpolation::Basis,
+            Interpolation::Cardinal,
+            Interpolation::CatmullRom,
+            Interpolation::Linear,
+            Interpolation::Monotone,
+            Interpolation::Natural,
+            Interpolation::Step,
+            Interpolation::StepAfter,
+            Interpolation::StepBefore,
+        ]
+    }
+
+    /// The name Vega knows the interpolation by
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Interpolation::Basis => "basis",
+            Interpolation::Cardinal => "cardinal",
+            Interpolation::CatmullRom => "catmull-rom",
+            Interpolation::Linear => "linear",
+            Interpolation::Monotone => "monotone",
+            Interpolation::Natural => "natural",
+            Interpolation::Step => "step",
+            Interpolation::StepAfter => "step-after",
+            Interpolation::StepBefore => "step-before",
+        }
+    }
+}
+
 /// A small helper struct that represents a json object of the following type:
 /// { key:  val}, where both key and val are strings (&str, and String)
 pub struct KeyVal {
@@ -93,6 +157,16 @@ impl JSONDict {
         d
     }
 
+    /// single creates a json object which only holds one string entry, e.g. `{ "value": "red" }`
+    pub fn single(key: &'static str, val: &str) -> JSONDict {
+        let mut d = JSONDict {
+            str_vals: HashMap::new(),
+            i32_vals: HashMap::new(),
+        };
+        d.str_vals.insert(key, val.to_string());
+        d
+    }
+
     /// band_create adds one tuple of type (String, String), and another of type (String, i32),
     /// that get serialized. often times bands and other values that describe a visualization show up
     /// and use this structure, hence a dedicated constructor to make it easier.
@@ -0,0 +1,133 @@
+
+/*
+ * Map projections and the building blocks of maps, which can be layered under geographic data
+ */
+
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Mark, ValueRef};
+use backend::elements::transform::Transform;
+
+/// ProjectionType lists the cartographic projections of Vega
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProjectionType {
+    Albers,
+    /// A composite of Albers projections showing Alaska and Hawaii next to the lower 48 states
+    AlbersUsa,
+    AzimuthalEqualArea,
+    AzimuthalEquidistant,
+    ConicConformal,
+    ConicEqualArea,
+    ConicEquidistant,
+    EqualEarth,
+    Equirectangular,
+    Gnomonic,
+    Identity,
+    Mercator,
+    NaturalEarth1,
+    Orthographic,
+    Stereographic,
+    TransverseMercator,
+}
+
+/// Projection maps longitude and latitude onto the pixels of a chart. Only the name and the type
+/// are always emitted, Vega derives sensible defaults for everything else.
+#[derive(Serialize)]
+pub struct Projection {
+    name: String,
+    #[serde(rename = "type")]
+    projection_type: ProjectionType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scale: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    center: Option<[f64; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rotate: Option<[f64; 3]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translate: Option<[f64; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fit: Option<ValueRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<ValueRef>,
+}
+
+impl Projection {
+    pub fn new(name: &str, projection_type: ProjectionType) -> Projection {
+        Projection {
+            name: String::from(name),
+            projection_type,
+            scale: None,
+            center: None,
+            rotate: None,
+            translate: None,
+            fit: None,
+            size: None,
+        }
+    }
+
+    /// Sets the zoom level of the projection
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = Some(scale);
+    }
+
+    /// Sets the longitude and latitude shown at the translation point, by default the middle of
+    /// the chart
+    pub fn set_center(&mut self, longitude: f64, latitude: f64) {
+        self.center = Some([longitude, latitude]);
+    }
+
+    /// Rotates the globe by the given angles (yaw, pitch and roll) in degrees before projecting
+    pub fn set_rotate(&mut self, lambda: f64, phi: f64, gamma: f64) {
+        self.rotate = Some([lambda, phi, gamma]);
+    }
+
+    /// Sets the pixel position the center of the projection is drawn at
+    pub fn set_translate(&mut self, x: f64, y: f64) {
+        self.translate = Some([x, y]);
+    }
+
+    /// Scales and translates the projection so the GeoJSON features of the data set `data` fill
+    /// the whole chart, which takes precedence over `set_scale` and `set_translate`
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::geo::{Projection, ProjectionType};
+    ///
+    ///    let mut projection = Projection::new("projection", ProjectionType::Mercator);
+    ///    projection.fit("countries");
+    /// ```
+    pub fn fit(&mut self, data: &str) {
+        self.fit = Some(ValueRef::signal(&format!("data('{}')", data)));
+        self.size = Some(ValueRef::signal("[width, height]"));
+    }
+}
+
+/// A path mark drawing the GeoJSON features of the data set `data`, which have been run through
+/// a geopath transform
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::data::DataSet;
+///    use gust::backend::elements::geo;
+///    use gust::backend::elements::transform::Transform;
+///
+///    let mut countries = DataSet::from_source("countries_path", "countries");
+///    countries.add_transform(Transform::geopath("projection"));
+///    let shapes = geo::path("countries_path");
+/// ```
+pub fn path(data: &str) -> Mark {
+    Mark::path(ValueRef::field("path")).from_data(data)
+}
+
+/// Draws the meridians and parallels of a map through the projection with the given name,
+/// spaced `step` degrees apart. The graticule is drawn on top of the chart's own marks, so it
+/// should be added before any other geographic layers.
+pub fn add_graticule(extras: &mut Extras, projection: &str, step: f64) {
+    let name = format!("graticule{}", extras.data().len());
+
+    let mut graticule = DataSet::new(&name);
+    graticule.add_transform(Transform::Graticule { step: Some([step, step]) });
+    graticule.add_transform(Transform::geopath(projection));
+    extras.add_data_set(graticule);
+
+    extras.add_mark(path(&name).stroke("#ddd", 0.5));
+}
@@ -0,0 +1,164 @@
+
+/*
+ * Graph-structured data, made up of nodes connected by weighted flows, which is laid out into
+ * columns for sankey diagrams
+ */
+
+use backend::data;
+use serde_json::{Map, Value};
+
+/// Flow is a weighted, directed edge from the node `source` to the node `target`
+pub struct Flow {
+    pub source: String,
+    pub target: String,
+    pub weight: f64,
+}
+
+/// The layout of a single node: its column, and its top and bottom as fractions of the height
+struct Node {
+    name: String,
+    depth: usize,
+    value: f64,
+    y0: f64,
+    y1: f64,
+}
+
+/// The index of the node with the given name, which is added if it doesn't exist yet
+fn index(nodes: &mut Vec<Node>, name: &str) -> usize {
+    match nodes.iter().position(|n| n.name == name) {
+        Some(i) => i,
+        None => {
+            nodes.push(Node {
+                name: String::from(name),
+                depth: 0,
+                value: 0.0,
+                y0: 0.0,
+                y1: 0.0,
+            });
+            nodes.len() - 1
+        }
+    }
+}
+
+fn object(fields: Vec<(&str, Value)>) -> Value {
+    let mut m = Map::new();
+    for (key, value) in fields {
+        m.insert(String::from(key), value);
+    }
+    Value::Object(m)
+}
+
+/// Lays the flows out as a sankey diagram, and returns the data objects of its nodes and links.
+///
+/// Every node is placed in the column after the furthest of its sources, and nodes without
+/// any outgoing flows in the last column. A node is as tall as the larger of its incoming and
+/// outgoing flows, the nodes of every column are separated by `padding` and centered. All
+/// positions are fractions: `column` runs from 0 (first column) to 1 (last column), and `y0`
+/// and `y1` from 0 (top) to 1 (bottom).
+///
+/// Nodes have a `name`, `value`, `column`, `y0` and `y1`. Links have a `source`, `target`
+/// and `value`, the columns `column0` and `column1` of their ends, the centers `y0` and `y1`
+/// of their ends and their thickness `size`.
+pub fn sankey(flows: &[Flow], padding: f64) -> (Vec<Value>, Vec<Value>) {
+    let mut nodes = vec![];
+    let edges: Vec<(usize, usize, f64)> = flows
+        .iter()
+        .map(|f| (index(&mut nodes, &f.source), index(&mut nodes, &f.target), f.weight))
+        .collect();
+
+    // relaxing every edge once per node settles the depths, and caps them on cycles
+    for _ in 0..nodes.len() {
+        for &(s, t, _) in &edges {
+            if nodes[t].depth <= nodes[s].depth && nodes[s].depth + 1 < nodes.len() {
+                nodes[t].depth = nodes[s].depth + 1;
+            }
+        }
+    }
+    let last = nodes.iter().map(|n| n.depth).max().unwrap_or(0);
+    for i in 0..nodes.len() {
+        let incoming: f64 = edges.iter().filter(|e| e.1 == i).map(|e| e.2).sum();
+        let outgoing: f64 = edges.iter().filter(|e| e.0 == i).map(|e| e.2).sum();
+        nodes[i].value = incoming.max(outgoing);
+        if !edges.iter().any(|e| e.0 == i) {
+            nodes[i].depth = last;
+        }
+    }
+
+    // one scale for all columns, so that the fullest column just fits
+    let mut k = ::std::f64::INFINITY;
+    for depth in 0..last + 1 {
+        let column: Vec<&Node> = nodes.iter().filter(|n| n.depth == depth).collect();
+        let total: f64 = column.iter().map(|n| n.value).sum();
+        if total > 0.0 {
+            let space = 1.0 - padding * (column.len() as f64 - 1.0);
+            k = k.min(space.max(0.0) / total);
+        }
+    }
+    if !k.is_finite() {
+        k = 0.0;
+    }
+    for depth in 0..last + 1 {
+        let mut y = 0.0;
+        for node in nodes.iter_mut().filter(|n| n.depth == depth) {
+            node.y0 = y;
+            node.y1 = y + node.value * k;
+            y = node.y1 + padding;
+        }
+        let offset = (1.0 - (y - padding)) / 2.0;
+        for node in nodes.iter_mut().filter(|n| n.depth == depth) {
+            node.y0 += offset;
+            node.y1 += offset;
+        }
+    }
+
+    // links leave their source ordered by the position of their target, and vice versa
+    let mut ends = vec![(0.0, 0.0); edges.len()];
+    for i in 0..nodes.len() {
+        let mut outgoing: Vec<usize> = (0..edges.len()).filter(|&e| edges[e].0 == i).collect();
+        outgoing.sort_by(|&a, &b| data::nan_last(nodes[edges[a].1].y0, nodes[edges[b].1].y0));
+        let mut y = nodes[i].y0;
+        for e in outgoing {
+            ends[e].0 = y + edges[e].2 * k / 2.0;
+            y += edges[e].2 * k;
+        }
+
+        let mut incoming: Vec<usize> = (0..edges.len()).filter(|&e| edges[e].1 == i).collect();
+        incoming.sort_by(|&a, &b| data::nan_last(nodes[edges[a].0].y0, nodes[edges[b].0].y0));
+        let mut y = nodes[i].y0;
+        for e in incoming {
+            ends[e].1 = y + edges[e].2 * k / 2.0;
+            y += edges[e].2 * k;
+        }
+    }
+
+    let column = |depth: usize| if last == 0 { 0.0 } else { depth as f64 / last as f64 };
+    let node_values = nodes
+        .iter()
+        .map(|n| {
+            object(vec![
+                ("name", Value::from(n.name.as_str())),
+                ("value", Value::from(n.value)),
+                ("column", Value::from(column(n.depth))),
+                ("y0", Value::from(n.y0)),
+                ("y1", Value::from(n.y1)),
+            ])
+        })
+        .collect();
+    let link_values = edges
+        .iter()
+        .zip(ends)
+        .map(|(&(s, t, weight), (y0, y1))| {
+            object(vec![
+                ("source", Value::from(nodes[s].name.as_str())),
+                ("target", Value::from(nodes[t].name.as_str())),
+                ("value", Value::from(weight)),
+                ("column0", Value::from(column(nodes[s].depth))),
+                ("column1", Value::from(column(nodes[t].depth))),
+                ("y0", Value::from(y0)),
+                ("y1", Value::from(y1)),
+                ("size", Value::from(weight * k)),
+            ])
+        })
+        .collect();
+    (node_values, link_values)
+}
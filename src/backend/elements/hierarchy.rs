@@ -0,0 +1,69 @@
+
+/*
+ * Hierarchical data, made up of nodes and the edges between them, which can be laid out by
+ * the tree, pack or partition transforms
+ */
+
+use backend::elements::data::DataSet;
+use backend::elements::transform::Transform;
+use serde_json::{Map, Value};
+
+/// The data object of the node with the given id, if there is one
+fn find<'a>(nodes: &'a mut DataSet, id: &str) -> Option<&'a mut Map<String, Value>> {
+    nodes
+        .values_mut()
+        .iter_mut()
+        .filter_map(|v| v.as_object_mut())
+        .find(|n| n.get("id").and_then(|i| i.as_str()) == Some(id))
+}
+
+/// The data object of the node with the given id, which is added (named after its id) if it
+/// doesn't exist yet
+fn find_or_add<'a>(nodes: &'a mut DataSet, id: &str) -> Option<&'a mut Map<String, Value>> {
+    if find(nodes, id).is_none() {
+        add_node(nodes, id, id);
+    }
+    find(nodes, id)
+}
+
+/// Adds a node to the data set `nodes`, or renames it if a node with that id already exists
+pub fn add_node(nodes: &mut DataSet, id: &str, name: &str) {
+    if let Some(node) = find(nodes, id) {
+        node.insert(String::from("name"), Value::from(name));
+        return;
+    }
+    let mut node = Map::new();
+    node.insert(String::from("id"), Value::from(id));
+    node.insert(String::from("name"), Value::from(name));
+    nodes.add_value(Value::Object(node));
+}
+
+/// Makes `child` a child of `parent`, adding either node if it doesn't exist yet. A node only
+/// has a single parent, so this replaces any earlier parent of `child`.
+pub fn add_edge(nodes: &mut DataSet, parent: &str, child: &str) {
+    if find_or_add(nodes, parent).is_none() {
+        return;
+    }
+    if let Some(node) = find_or_add(nodes, child) {
+        node.insert(String::from("parent"), Value::from(parent));
+    }
+}
+
+/// Sets the size of a node. Layouts that size nodes sum up the sizes of the leaves below every
+/// node, so only leaves should be given a size.
+pub fn set_size(nodes: &mut DataSet, id: &str, size: f64) {
+    if let Some(node) = find_or_add(nodes, id) {
+        node.insert(String::from("size"), Value::from(size));
+    }
+}
+
+/// A data set called `name`, organizing the nodes of `source` into a hierarchy that layout
+/// transforms can be run on
+pub fn stratify(name: &str, source: &str) -> DataSet {
+    let mut tree = DataSet::from_source(name, source);
+    tree.add_transform(Transform::Stratify {
+        key: String::from("id"),
+        parent_key: String::from("parent"),
+    });
+    tree
+}
@@ -0,0 +1,179 @@
+/*
+ * Legends, which explain the colors, sizes and shapes a chart encodes its data with
+ */
+
+use backend::elements::general::Orientation;
+use backend::elements::mark::SymbolShape;
+
+/// LegendKind is the encoding a legend explains, each of which charts keep in a scale of the
+/// same name
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LegendKind {
+    /// The colors of the "color" scale
+    Color,
+    /// The symbol sizes of the "size" scale
+    Size,
+    /// The symbol shapes of the "shape" scale
+    Shape,
+}
+
+impl LegendKind {
+    /// The name of the scale charts use for the encoding
+    pub fn scale_name(&self) -> &'static str {
+        match *self {
+            LegendKind::Color => "color",
+            LegendKind::Size => "size",
+            LegendKind::Shape => "shape",
+        }
+    }
+}
+
+/// Legend represents a single Vega legend, listing the domain values of a scale next to the
+/// color they are drawn in. Optional properties are only emitted once set.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::general::Orientation;
+///    use gust::backend::elements::legend::Legend;
+///
+///    // explains the line colors of the color scale, below the chart
+///    let legend = Legend::stroke("color").title("Region").orient(Orientation::Bottom);
+/// ```
+#[derive(Serialize, Clone)]
+pub struct Legend {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fill: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stroke: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shape: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    orient: Option<Orientation>,
+    #[serde(rename = "symbolType", skip_serializing_if = "Option::is_none")]
+    symbol_type: Option<String>,
+    #[serde(rename = "symbolSize", skip_serializing_if = "Option::is_none")]
+    symbol_size: Option<f64>,
+    #[serde(rename = "symbolFillColor", skip_serializing_if = "Option::is_none")]
+    symbol_fill_color: Option<String>,
+    #[serde(rename = "symbolStrokeWidth", skip_serializing_if = "Option::is_none")]
+    symbol_stroke_width: Option<f64>,
+}
+
+impl Legend {
+    /// A legend for the scale with the given name, which colors the fill of the marks, e.g. the
+    /// color scale of bars or areas
+    pub fn fill(scale: &str) -> Legend {
+        Legend {
+            fill: Some(String::from(scale)),
+            ..Legend::empty()
+        }
+    }
+
+    /// A legend for the scale with the given name, which colors the stroke of the marks, e.g.
+    /// the color scale of lines
+    pub fn stroke(scale: &str) -> Legend {
+        Legend {
+            stroke: Some(String::from(scale)),
+            ..Legend::empty()
+        }
+    }
+
+    /// A legend for the scale with the given name, which sets the size of symbols, e.g. the
+    /// size scale of a bubble chart
+    pub fn size(scale: &str) -> Legend {
+        Legend {
+            size: Some(String::from(scale)),
+            ..Legend::empty()
+        }
+    }
+
+    /// A legend for the scale with the given name, which sets the shape of symbols
+    pub fn shape(scale: &str) -> Legend {
+        Legend {
+            shape: Some(String::from(scale)),
+            ..Legend::empty()
+        }
+    }
+
+    /// A legend of the given kind for the scale charts use for it, e.g. a fill legend for the
+    /// "color" scale
+    pub fn of(kind: LegendKind) -> Legend {
+        match kind {
+            LegendKind::Color => Legend::fill(kind.scale_name()),
+            LegendKind::Size => Legend::size(kind.scale_name()),
+            LegendKind::Shape => Legend::shape(kind.scale_name()),
+        }
+    }
+
+    fn empty() -> Legend {
+        Legend {
+            fill: None,
+            stroke: None,
+            size: None,
+            shape: None,
+            title: None,
+            orient: None,
+            symbol_type: None,
+            symbol_size: None,
+            symbol_fill_color: None,
+            symbol_stroke_width: None,
+        }
+    }
+
+    pub fn title(mut self, title: &str) -> Legend {
+        self.title = Some(String::from(title));
+        self
+    }
+
+    /// Places the legend on the given side of the chart, Vega places it on the right by default,
+    /// and charts laid out right-to-left place it on the left
+    pub fn orient(mut self, orient: Orientation) -> Legend {
+        self.orient = Some(orient);
+        self
+    }
+
+    /// Sets the shape of the symbols next to the labels, circles by default
+    pub fn symbol_type(mut self, shape: SymbolShape) -> Legend {
+        self.symbol_type = Some(shape.name());
+        self
+    }
+
+    /// Sets the area of the symbols in square pixels, unless a size legend sizes them
+    pub fn symbol_size(mut self, size: f64) -> Legend {
+        self.symbol_size = Some(size);
+        self
+    }
+
+    /// Fills the symbols with the given color, e.g. where a size or shape legend would
+    /// otherwise draw them hollow
+    pub fn symbol_fill_color(mut self, color: &str) -> Legend {
+        self.symbol_fill_color = Some(String::from(color));
+        self
+    }
+
+    /// Sets the width of the outline of the symbols in pixels
+    pub fn symbol_stroke_width(mut self, width: f64) -> Legend {
+        self.symbol_stroke_width = Some(width);
+        self
+    }
+
+    /// Whether the legend explains the scale with the given name
+    pub fn explains(&self, scale: &str) -> bool {
+        let scales = [&self.fill, &self.stroke, &self.size, &self.shape];
+        scales.iter().any(|s| match **s {
+            Some(ref s) => s == scale,
+            None => false,
+        })
+    }
+
+    /// The legend as written into a chart laid out right-to-left: on the left side of the
+    /// chart, unless it was placed elsewhere
+    pub fn right_to_left(&self) -> Legend {
+        let mut legend = self.clone();
+        legend.orient = legend.orient.or(Some(Orientation::Left));
+        legend
+    }
+}
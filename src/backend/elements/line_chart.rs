@@ -1,59 +1,17 @@
 
 
 
+use backend::data;
 use backend::elements::general::*;
+use backend::elements::transform::Transform;
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
-#[derive(Serialize)]
-pub struct LineChartSignal {
-    name: String,
-    value: String,
-    bind: LineChartInterpolation,
-}
-impl LineChartSignal {
-    pub fn new() -> LineChartSignal {
-        LineChartSignal {
-            name: String::from("interpolate"),
-            value: String::from("linear"),
-            bind: LineChartInterpolation::new(),
-        }
-    }
-}
-
-///A small json object which represents the selector for different interpolation options that are
-/// currently supported by Vega.
-/// They are:
-/// basis, cardinal, catmull-rom, linear, monotone, natural, step, step-after, and step-before
-#[derive(Serialize)]
-pub struct LineChartInterpolation {
-    input: String,
-    options: Vec<String>,
-}
-impl LineChartInterpolation {
-    pub fn new() -> LineChartInterpolation {
-        LineChartInterpolation {
-            input: String::from("select"),
-            options: vec![
-                String::from("basis"),
-                String::from("cardinal"),
-                String::from("catmull-rom"),
-                String::from("linear"),
-                String::from("monotone"),
-                String::from("natural"),
-                String::from("step"),
-                String::from("step-after"),
-                String::from("step-before"),
-            ],
-        }
-    }
-}
-
-
-
 #[derive(Serialize)]
 pub struct LineChartData {
     name: String,
     values: Vec<LineChartDataValue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    transform: Vec<Transform>,
 }
 
 impl LineChartData {
@@ -61,86 +19,60 @@ impl LineChartData {
         LineChartData {
             name: String::from("table"),
             values: vec![],
+            transform: vec![],
         }
     }
-    pub fn add_data(&mut self, x: i64, y: i64, z: i64) {
-        self.values.push(LineChartDataValue { x, y, z });
+    pub fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        &mut self.transform
+    }
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional)
+    }
+    pub fn add_data(&mut self, x: f64, y: f64, z: i64) {
+        self.values.push(LineChartDataValue {
+            x,
+            y: Some(y),
+            z,
+            lo: None,
+            hi: None,
+        });
+    }
+    pub fn add_data_with_error(&mut self, x: f64, y: f64, z: i64, lo: f64, hi: f64) {
+        self.values.push(LineChartDataValue {
+            x,
+            y: Some(y),
+            z,
+            lo: Some(lo),
+            hi: Some(hi),
+        });
+    }
+    pub fn add_gap(&mut self, x: f64, z: i64) {
+        self.values.push(LineChartDataValue {
+            x,
+            y: None,
+            z,
+            lo: None,
+            hi: None,
+        });
+    }
+    /// Thins out every series to at most `threshold` points, see `data::downsample`. Gaps, and
+    /// points whose x isn't a number, are kept.
+    pub fn downsample(&mut self, threshold: usize) {
+        let points = self.values.iter().map(|v| (v.z, v.x, v.y));
+        let mut keep = data::downsample(points, threshold).into_iter();
+        self.values.retain(|_| keep.next().unwrap());
     }
 }
 
 #[derive(Serialize)]
 pub struct LineChartDataValue {
-    x: i64,
-    y: i64,
+    x: f64,
+    y: Option<f64>,
     z: i64,
-}
-
-pub struct LineChartScale {
-    name: String,
-    scale_type: String,
-    range: String,
-    domain: JSONDict,
-}
-impl LineChartScale {
-    pub fn new_xscale() -> LineChartScale {
-        LineChartScale {
-            name: String::from("x"),
-            scale_type: String::from("point"),
-            range: String::from("width"),
-            domain: JSONDict::create("data", "table", "field", "x"),
-        }
-    }
-    pub fn new_yscale() -> LineChartScale {
-        LineChartScale {
-            name: String::from("y"),
-            scale_type: String::from("linear"),
-            range: String::from("height"),
-            domain: JSONDict::create("data", "table", "field", "y"),
-        }
-    }
-    pub fn new_ordinal_scale() -> LineChartScale {
-        LineChartScale {
-            name: String::from("color"),
-            scale_type: String::from("ordinal"),
-            range: String::from("category"),
-            domain: JSONDict::create("data", "table", "field", "z"),
-        }
-    }
-}
-impl Serialize for LineChartScale {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_struct("scale", 4)?;
-        s.serialize_field("name", &self.name)?;
-        s.serialize_field("type", &self.scale_type)?;
-        s.serialize_field("range", &self.range)?;
-        s.serialize_field("domain", &self.domain)?;
-        s.end()
-    }
-}
-
-#[derive(Serialize)]
-pub struct LineChartAxis {
-    scale: String,
-    orient: Orientation,
-}
-
-impl LineChartAxis {
-    pub fn new_xaxis() -> LineChartAxis {
-        LineChartAxis {
-            scale: String::from("x"),
-            orient: Orientation::Bottom,
-        }
-    }
-
-    pub fn new_yaxis() -> LineChartAxis {
-        LineChartAxis {
-            scale: String::from("y"),
-            orient: Orientation::Left,
-        }
-    }
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lo: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hi: Option<f64>,
 }
 
 pub struct LineChartMark {
@@ -156,6 +88,11 @@ impl LineChartMark {
             marks: vec![LineChartMarkDescription::new()],
         }
     }
+    pub fn set_color(&mut self, color: &str) {
+        for mark in &mut self.marks {
+            mark.encode.enter.stroke = JSONDict::single("value", color);
+        }
+    }
 }
 impl Serialize for LineChartMark {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -219,6 +156,7 @@ pub struct LineChartEnter {
     y: JSONDict,
     stroke: JSONDict,
     strokeWidth: QualKeyVal,
+    defined: KeyVal,
 }
 impl LineChartEnter {
     pub fn new() -> LineChartEnter {
@@ -227,6 +165,7 @@ impl LineChartEnter {
             y: JSONDict::create("scale", "y", "field", "y"),
             stroke: JSONDict::create("scale", "color", "field", "z"),
             strokeWidth: QualKeyVal::new("value", 2.0),
+            defined: KeyVal::new("signal", "isValid(datum.y)"),
         }
     }
 }
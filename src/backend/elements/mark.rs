@@ -0,0 +1,686 @@
+
+/*
+ * Generic marks, used for everything that is layered on top of the marks a chart type
+ * defines itself
+ */
+
+use backend::elements::scale::Scale;
+use backend::elements::transform::Transform;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
+
+/// MarkType lists the graphical primitives of Vega
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkType {
+    Arc,
+    Area,
+    Group,
+    Image,
+    Line,
+    Path,
+    Rect,
+    Rule,
+    Shape,
+    Symbol,
+    Text,
+    Trail,
+}
+
+impl MarkType {
+    /// The name Vega knows the mark type by
+    pub fn name(&self) -> &'static str {
+        match *self {
+            MarkType::Arc => "arc",
+            MarkType::Area => "area",
+            MarkType::Group => "group",
+            MarkType::Image => "image",
+            MarkType::Line => "line",
+            MarkType::Path => "path",
+            MarkType::Rect => "rect",
+            MarkType::Rule => "rule",
+            MarkType::Shape => "shape",
+            MarkType::Symbol => "symbol",
+            MarkType::Text => "text",
+            MarkType::Trail => "trail",
+        }
+    }
+}
+
+/// Align is the horizontal alignment of text, or of an image, relative to its position
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+impl Align {
+    /// The name Vega knows the alignment by
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Align::Left => "left",
+            Align::Center => "center",
+            Align::Right => "right",
+        }
+    }
+}
+
+/// Baseline is the vertical alignment of text, or of an image, relative to its position
+pub enum Baseline {
+    Top,
+    Middle,
+    Bottom,
+    /// The baseline of the text itself, Vega's default
+    Alphabetic,
+}
+
+impl Baseline {
+    fn name(&self) -> &'static str {
+        match *self {
+            Baseline::Top => "top",
+            Baseline::Middle => "middle",
+            Baseline::Bottom => "bottom",
+            Baseline::Alphabetic => "alphabetic",
+        }
+    }
+}
+
+/// SymbolShape lists the shapes of symbol marks
+pub enum SymbolShape {
+    Circle,
+    Square,
+    Cross,
+    Diamond,
+    TriangleUp,
+    TriangleDown,
+    TriangleLeft,
+    TriangleRight,
+    /// An isosceles triangle, which can be rotated through the angle channel
+    Triangle,
+    Arrow,
+    Wedge,
+    /// A thin horizontal line
+    Stroke,
+    /// A custom shape, given as an SVG path string with coordinates between -1 and 1, which is
+    /// scaled to the size of the symbol, e.g. "M-1,-1H1V1H-1Z"
+    Path(String),
+}
+
+impl SymbolShape {
+    /// The name Vega knows the shape by, or the path of a custom shape
+    pub fn name(&self) -> String {
+        let name = match *self {
+            SymbolShape::Circle => "circle",
+            SymbolShape::Square => "square",
+            SymbolShape::Cross => "cross",
+            SymbolShape::Diamond => "diamond",
+            SymbolShape::TriangleUp => "triangle-up",
+            SymbolShape::TriangleDown => "triangle-down",
+            SymbolShape::TriangleLeft => "triangle-left",
+            SymbolShape::TriangleRight => "triangle-right",
+            SymbolShape::Triangle => "triangle",
+            SymbolShape::Arrow => "arrow",
+            SymbolShape::Wedge => "wedge",
+            SymbolShape::Stroke => "stroke",
+            SymbolShape::Path(ref path) => path,
+        };
+        String::from(name)
+    }
+}
+
+/// ValueRef is the value of a single encoding channel. It is either a constant, a field of the
+/// current datum (optionally passed through a scale), or a signal expression.
+#[derive(Serialize)]
+pub struct ValueRef {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    test: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    band: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<f64>,
+}
+
+impl ValueRef {
+    fn empty() -> ValueRef {
+        ValueRef {
+            test: None,
+            value: None,
+            field: None,
+            scale: None,
+            signal: None,
+            band: None,
+            offset: None,
+        }
+    }
+
+    /// A constant value, such as a color or a number of pixels
+    pub fn value<V: Into<Value>>(value: V) -> ValueRef {
+        ValueRef { value: Some(value.into()), ..ValueRef::empty() }
+    }
+
+    /// The raw value of a field of the datum
+    pub fn field(field: &str) -> ValueRef {
+        ValueRef { field: Some(String::from(field)), ..ValueRef::empty() }
+    }
+
+    /// A field of the datum, mapped through the scale with the given name
+    pub fn scaled(scale: &str, field: &str) -> ValueRef {
+        ValueRef {
+            field: Some(String::from(field)),
+            scale: Some(String::from(scale)),
+            ..ValueRef::empty()
+        }
+    }
+
+    /// A constant which is mapped through the scale with the given name
+    pub fn scaled_value<V: Into<Value>>(scale: &str, value: V) -> ValueRef {
+        ValueRef {
+            value: Some(value.into()),
+            scale: Some(String::from(scale)),
+            ..ValueRef::empty()
+        }
+    }
+
+    /// A Vega expression, which may refer to the datum as `datum`
+    pub fn signal(expression: &str) -> ValueRef {
+        ValueRef { signal: Some(String::from(expression)), ..ValueRef::empty() }
+    }
+
+    /// Uses the given fraction of the band width of a band scale
+    pub fn band(mut self, band: f64) -> ValueRef {
+        self.band = Some(band);
+        self
+    }
+
+    /// Shifts the resulting value by the given number of pixels
+    pub fn offset(mut self, offset: f64) -> ValueRef {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// Production is what an encoding channel is set to: a single ValueRef, or a list of rules
+/// which each apply a value only if their test passes. The first rule whose test passes is used.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::mark::{when, Mark, MarkType, ValueRef};
+///
+///    // bars above the threshold are drawn red, all others gray
+///    let bars = Mark::new(MarkType::Rect).update(
+///        "fill",
+///        when("datum.amount > 50")
+///            .then(ValueRef::value("red"))
+///            .otherwise(ValueRef::value("gray")),
+///    );
+/// ```
+pub struct Production {
+    rules: Vec<ValueRef>,
+}
+
+impl Production {
+    /// Adds another rule, which is only checked if the tests of the earlier rules failed
+    pub fn when(self, test: &str) -> When {
+        When {
+            production: self,
+            test: String::from(test),
+        }
+    }
+
+    /// Adds a rule which is checked before all the rules of the production
+    pub fn first_when(mut self, test: &str, value: ValueRef) -> Production {
+        self.rules.insert(0, ValueRef { test: Some(String::from(test)), ..value });
+        self
+    }
+
+    /// Sets the value used when none of the tests pass
+    pub fn otherwise(mut self, value: ValueRef) -> Production {
+        self.rules.push(value);
+        self
+    }
+}
+
+impl From<ValueRef> for Production {
+    fn from(value: ValueRef) -> Production {
+        Production { rules: vec![value] }
+    }
+}
+
+impl Serialize for Production {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.rules.len() {
+            1 if self.rules[0].test.is_none() => self.rules[0].serialize(serializer),
+            _ => self.rules.serialize(serializer),
+        }
+    }
+}
+
+/// When is a rule of a Production whose value is still missing, see `then`
+pub struct When {
+    production: Production,
+    test: String,
+}
+
+impl When {
+    /// Sets the value used if the test of the rule passes
+    pub fn then(mut self, value: ValueRef) -> Production {
+        self.production.rules.push(ValueRef { test: Some(self.test), ..value });
+        self.production
+    }
+}
+
+/// Starts a Production with a rule that applies if the Vega expression `test` is true, e.g.
+/// `datum.amount > 50`
+pub fn when(test: &str) -> When {
+    Production { rules: vec![] }.when(test)
+}
+
+/// EncodeSet holds the encoding channels of one encoding set (enter, update, hover), in the
+/// order they were set
+pub struct EncodeSet {
+    channels: Vec<(String, Production)>,
+}
+
+impl EncodeSet {
+    pub fn new() -> EncodeSet {
+        EncodeSet { channels: vec![] }
+    }
+    /// Sets the value of a channel, replacing any earlier value of that channel
+    pub fn set<P: Into<Production>>(&mut self, channel: &str, value: P) {
+        self.channels.retain(|c| c.0 != channel);
+        self.channels.push((String::from(channel), value.into()));
+    }
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+}
+
+impl Serialize for EncodeSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut m = serializer.serialize_map(Some(self.channels.len()))?;
+        for &(ref channel, ref value) in &self.channels {
+            m.serialize_entry(channel, value)?;
+        }
+        m.end()
+    }
+}
+
+/// Encode groups the encoding sets of a mark
+#[derive(Serialize)]
+pub struct Encode {
+    #[serde(skip_serializing_if = "EncodeSet::is_empty")]
+    enter: EncodeSet,
+    #[serde(skip_serializing_if = "EncodeSet::is_empty")]
+    update: EncodeSet,
+    #[serde(skip_serializing_if = "EncodeSet::is_empty")]
+    hover: EncodeSet,
+}
+
+/// MarkSource describes which data a mark draws
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum MarkSource {
+    /// One mark instance per data object of the data set
+    Data { data: String },
+    /// Splits a data set into groups, so that a group mark can draw each of them separately
+    Facet { facet: Facet },
+}
+
+#[derive(Serialize)]
+pub struct Facet {
+    name: String,
+    data: String,
+    groupby: String,
+}
+
+/// Mark is a generic Vega mark, which is built up step by step.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::mark::{Mark, MarkType, ValueRef};
+///
+///    // a red dot at every data point of a line chart
+///    let dots = Mark::new(MarkType::Symbol)
+///        .from_data("table")
+///        .enter("x", ValueRef::scaled("x", "x"))
+///        .enter("y", ValueRef::scaled("y", "y"))
+///        .enter("fill", ValueRef::value("red"));
+/// ```
+#[derive(Serialize)]
+pub struct Mark {
+    #[serde(rename = "type")]
+    mark_type: MarkType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<MarkSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interactive: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aria: Option<bool>,
+    encode: Encode,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    transform: Vec<Transform>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    scales: Vec<Scale>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    marks: Vec<Mark>,
+}
+
+impl Mark {
+    pub fn new(mark_type: MarkType) -> Mark {
+        Mark {
+            mark_type,
+            name: None,
+            from: None,
+            interactive: None,
+            aria: None,
+            encode: Encode {
+                enter: EncodeSet::new(),
+                update: EncodeSet::new(),
+                hover: EncodeSet::new(),
+            },
+            transform: vec![],
+            scales: vec![],
+            marks: vec![],
+        }
+    }
+
+    /// Creates an image mark showing the image at `url`, scaled to the given size in pixels.
+    /// The image is centered on the position set through `at`.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::mark::{Mark, ValueRef};
+    ///
+    ///    // the logo of every team, at its position in the chart
+    ///    let logos = Mark::image(ValueRef::field("logo"), 24.0, 24.0)
+    ///        .from_data("teams")
+    ///        .at(ValueRef::scaled("x", "wins"), ValueRef::scaled("y", "points"));
+    /// ```
+    pub fn image(url: ValueRef, width: f64, height: f64) -> Mark {
+        Mark::new(MarkType::Image)
+            .enter("url", url)
+            .enter("width", ValueRef::value(width))
+            .enter("height", ValueRef::value(height))
+            .align(Align::Center)
+            .baseline(Baseline::Middle)
+    }
+
+    /// Whether an image keeps its aspect ratio when it doesn't fit its width and height,
+    /// which Vega does by default
+    pub fn aspect(self, aspect: bool) -> Mark {
+        self.enter("aspect", ValueRef::value(aspect))
+    }
+
+    /// Creates an arc mark, a slice of a circle or ring between the given angles in radians,
+    /// measured clockwise from the top. The center of the circle is set through `at`.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::mark::{Mark, ValueRef};
+    ///
+    ///    // a gauge filling the upper half of a ring up to the value of the datum
+    ///    let gauge = Mark::arc(
+    ///            ValueRef::value(-1.5708),
+    ///            ValueRef::signal("-1.5708 + PI * datum.value"),
+    ///        )
+    ///        .from_data("table")
+    ///        .at(ValueRef::signal("width / 2"), ValueRef::signal("height"))
+    ///        .radius(ValueRef::value(80), ValueRef::value(100));
+    /// ```
+    pub fn arc(start_angle: ValueRef, end_angle: ValueRef) -> Mark {
+        Mark::new(MarkType::Arc)
+            .enter("startAngle", start_angle)
+            .enter("endAngle", end_angle)
+    }
+
+    /// Sets the inner and outer radius of an arc in pixels. An inner radius above zero turns
+    /// the slice into a segment of a ring.
+    pub fn radius(self, inner: ValueRef, outer: ValueRef) -> Mark {
+        self.enter("innerRadius", inner).enter("outerRadius", outer)
+    }
+
+    /// Sets the angle in radians left empty between adjacent arcs
+    pub fn pad_angle(self, angle: f64) -> Mark {
+        self.enter("padAngle", ValueRef::value(angle))
+    }
+
+    /// Rounds the corners of an arc, or a rect, by the given number of pixels
+    pub fn corner_radius(self, radius: f64) -> Mark {
+        self.enter("cornerRadius", ValueRef::value(radius))
+    }
+
+    /// Creates a trail mark, a line through the points at x and y whose width varies with
+    /// `size`. Unlike lines, trails are colored through their fill.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::mark::{Mark, ValueRef};
+    ///
+    ///    // a path getting wider with the number of travellers
+    ///    let flow = Mark::trail(
+    ///            ValueRef::scaled("x", "date"),
+    ///            ValueRef::scaled("y", "distance"),
+    ///            ValueRef::scaled("width", "travellers"),
+    ///        )
+    ///        .from_data("journey")
+    ///        .enter("fill", ValueRef::value("steelblue"));
+    /// ```
+    pub fn trail(x: ValueRef, y: ValueRef, size: ValueRef) -> Mark {
+        Mark::new(MarkType::Trail).at(x, y).enter("size", size)
+    }
+
+    /// Creates a rule mark, a straight line from (x, y) to (x2, y2)
+    pub fn rule(x: ValueRef, y: ValueRef, x2: ValueRef, y2: ValueRef) -> Mark {
+        Mark::new(MarkType::Rule)
+            .at(x, y)
+            .enter("x2", x2)
+            .enter("y2", y2)
+    }
+
+    /// Creates a rule mark spanning the whole width of the chart at the given y coordinate,
+    /// e.g. to mark a target value
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::mark::{Mark, ValueRef};
+    ///
+    ///    let target = Mark::horizontal_rule(ValueRef::scaled_value("yscale", 100))
+    ///        .stroke("firebrick", 2.0)
+    ///        .dash(&[4.0, 2.0]);
+    /// ```
+    pub fn horizontal_rule(y: ValueRef) -> Mark {
+        Mark::new(MarkType::Rule)
+            .at(ValueRef::value(0), y)
+            .enter("x2", ValueRef::signal("width"))
+    }
+
+    /// Creates a rule mark spanning the whole height of the chart at the given x coordinate
+    pub fn vertical_rule(x: ValueRef) -> Mark {
+        Mark::new(MarkType::Rule)
+            .at(x, ValueRef::value(0))
+            .enter("y2", ValueRef::signal("height"))
+    }
+
+    /// Sets the color and the width in pixels of the stroke
+    pub fn stroke(self, color: &str, width: f64) -> Mark {
+        self.enter("stroke", ValueRef::value(color))
+            .enter("strokeWidth", ValueRef::value(width))
+    }
+
+    /// Dashes the stroke, alternating between the given lengths of dashes and gaps in pixels
+    pub fn dash(self, pattern: &[f64]) -> Mark {
+        self.enter("strokeDash", ValueRef::value(pattern.to_vec()))
+    }
+
+    /// Creates a path mark drawing the given SVG path, usually the field written by a geopath
+    /// or linkpath transform
+    pub fn path(path: ValueRef) -> Mark {
+        Mark::new(MarkType::Path).enter("path", path)
+    }
+
+    /// Creates a symbol mark of the given shape, whose area is `size` square pixels
+    pub fn symbol(shape: SymbolShape, size: f64) -> Mark {
+        Mark::new(MarkType::Symbol)
+            .shape(shape)
+            .enter("size", ValueRef::value(size))
+    }
+
+    /// Sets the shape of a symbol mark. To vary the shape with the data, encode the shape
+    /// channel through a scale over `Range::shapes` instead.
+    pub fn shape(self, shape: SymbolShape) -> Mark {
+        self.enter("shape", ValueRef::value(shape.name()))
+    }
+
+    /// Creates a text mark, e.g. a label or a caption
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::mark::{Align, Mark, ValueRef};
+    ///
+    ///    // the value of every bar, printed just above it
+    ///    let labels = Mark::text(ValueRef::field("amount"))
+    ///        .from_data("table")
+    ///        .at(
+    ///            ValueRef::scaled("xscale", "category").band(0.5),
+    ///            ValueRef::scaled("yscale", "amount"),
+    ///        )
+    ///        .align(Align::Center)
+    ///        .shift(0.0, -4.0)
+    ///        .font("sans-serif", 11.0);
+    /// ```
+    pub fn text(text: ValueRef) -> Mark {
+        Mark::new(MarkType::Text).enter("text", text)
+    }
+
+    pub fn align(self, align: Align) -> Mark {
+        self.enter("align", ValueRef::value(align.name()))
+    }
+
+    pub fn baseline(self, baseline: Baseline) -> Mark {
+        self.enter("baseline", ValueRef::value(baseline.name()))
+    }
+
+    /// Rotates text by the given angle in degrees
+    pub fn angle(self, angle: f64) -> Mark {
+        self.enter("angle", ValueRef::value(angle))
+    }
+
+    /// Moves text from its position by the given number of pixels
+    pub fn shift(self, dx: f64, dy: f64) -> Mark {
+        self.enter("dx", ValueRef::value(dx)).enter("dy", ValueRef::value(dy))
+    }
+
+    /// Sets the font family and the font size in pixels of text
+    pub fn font(self, font: &str, size: f64) -> Mark {
+        self.enter("font", ValueRef::value(font))
+            .enter("fontSize", ValueRef::value(size))
+    }
+
+    /// Sets text right-to-left, as needed for Hebrew or Arabic, or left-to-right
+    pub fn right_to_left(self, rtl: bool) -> Mark {
+        self.enter("dir", ValueRef::value(if rtl { "rtl" } else { "ltr" }))
+    }
+
+    /// Sets the font weight of text, e.g. "bold" or "300"
+    pub fn font_weight(self, weight: &str) -> Mark {
+        self.enter("fontWeight", ValueRef::value(weight))
+    }
+
+    /// Positions the mark at the given x and y coordinates
+    pub fn at(self, x: ValueRef, y: ValueRef) -> Mark {
+        self.enter("x", x).enter("y", y)
+    }
+
+    /// Names the mark, so it can be referred to from signals or other marks
+    pub fn name(mut self, name: &str) -> Mark {
+        self.name = Some(String::from(name));
+        self
+    }
+
+    /// Draws one instance of the mark for each data object of the given data set, or for each
+    /// item of the mark with the given name
+    pub fn from_data(mut self, data: &str) -> Mark {
+        self.from = Some(MarkSource::Data { data: String::from(data) });
+        self
+    }
+
+    /// Whether the mark reacts to the pointer. Marks that only show something should turn this
+    /// off, so they don't cover the hover targets of the marks below them.
+    pub fn interactive(mut self, interactive: bool) -> Mark {
+        self.interactive = Some(interactive);
+        self
+    }
+
+    /// Describes every item of the mark to screen readers, e.g. with a signal like
+    /// "datum.category + ': ' + datum.amount"
+    pub fn aria_description(self, description: ValueRef) -> Mark {
+        self.enter("description", description)
+    }
+
+    /// Hides the mark from screen readers when turned off, which suits decorative marks like
+    /// grid lines or the hover targets of tooltips
+    pub fn aria(mut self, aria: bool) -> Mark {
+        self.aria = Some(aria);
+        self
+    }
+
+    /// Adds a transform that runs over the items of the mark after they are encoded, e.g. a
+    /// voronoi transform over the positions of the items of another mark
+    pub fn transform(mut self, transform: Transform) -> Mark {
+        self.transform.push(transform);
+        self
+    }
+
+    /// Splits `data` by `groupby` into data sets called `name`, which the marks nested in this
+    /// (group) mark can draw from
+    pub fn facet(mut self, name: &str, data: &str, groupby: &str) -> Mark {
+        self.from = Some(MarkSource::Facet {
+            facet: Facet {
+                name: String::from(name),
+                data: String::from(data),
+                groupby: String::from(groupby),
+            },
+        });
+        self
+    }
+
+    /// Sets a channel which is evaluated when the mark is first drawn
+    pub fn enter<P: Into<Production>>(mut self, channel: &str, value: P) -> Mark {
+        self.encode.enter.set(channel, value);
+        self
+    }
+
+    /// Sets a channel which is evaluated whenever the chart updates
+    pub fn update<P: Into<Production>>(mut self, channel: &str, value: P) -> Mark {
+        self.encode.update.set(channel, value);
+        self
+    }
+
+    /// Sets a channel which is evaluated while the pointer hovers over the mark
+    pub fn hover<P: Into<Production>>(mut self, channel: &str, value: P) -> Mark {
+        self.encode.hover.set(channel, value);
+        self
+    }
+
+    /// Adds a scale to a group mark, which only the marks nested in it can use, e.g. a band
+    /// scale over the data of each facet
+    pub fn scale(mut self, scale: Scale) -> Mark {
+        self.scales.push(scale);
+        self
+    }
+
+    /// Nests another mark in this one, which should be a group mark
+    pub fn mark(mut self, mark: Mark) -> Mark {
+        self.marks.push(mark);
+        self
+    }
+}
@@ -4,3 +4,23 @@ pub mod stacked_bar_chart;
 pub mod line_chart;
 pub mod area_chart;
 pub mod general;
+pub mod color;
+pub mod axis;
+pub mod legend;
+pub mod title;
+pub mod tooltip;
+pub mod scale;
+pub mod transform;
+pub mod mark;
+pub mod predicate;
+pub mod data;
+pub mod extras;
+pub mod overlay;
+pub mod qq_plot;
+pub mod geo;
+pub mod hierarchy;
+pub mod graph;
+pub mod theme;
+pub mod accessibility;
+pub mod signal;
+pub mod config;
@@ -0,0 +1,593 @@
+
+/*
+ * Overlays, which layer derived data and marks on top of the marks of a chart
+ */
+
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Align, Baseline, Mark, MarkType, SymbolShape, ValueRef};
+use backend::elements::signal::Signal;
+use backend::elements::transform::{Method, Order, Regression, Transform, Window, WindowOp};
+use serde_json::Value;
+
+/// Plot names the data set, fields and scales of the main plot of a chart, which overlays are
+/// drawn against
+pub struct Plot {
+    pub data: &'static str,
+    pub x: &'static str,
+    pub y: &'static str,
+    /// The field telling the series of the chart apart, if it can hold more than one
+    pub series: Option<&'static str>,
+    pub x_scale: &'static str,
+    pub y_scale: &'static str,
+    /// The scale coloring the series
+    pub color_scale: Option<&'static str>,
+}
+
+impl Plot {
+    /// The stroke or fill of an overlay drawn for a data object, matching the color of its series
+    fn series_color(&self, fallback: &str) -> ValueRef {
+        match (self.color_scale, self.series) {
+            (Some(scale), Some(series)) => ValueRef::scaled(scale, series),
+            _ => ValueRef::value(fallback),
+        }
+    }
+
+    /// Draws `line` once for every series of the data set `data`, or just once if the plot
+    /// only has a single series
+    fn per_series(&self, data: &str, line: Mark) -> Mark {
+        match self.series {
+            Some(series) => {
+                let facet = format!("{}_series", data);
+                Mark::new(MarkType::Group)
+                    .facet(&facet, data, series)
+                    .mark(line.from_data(&facet))
+            }
+            None => line.from_data(data),
+        }
+    }
+}
+
+/// Adds a dashed trend line to the plot, fitted to every series with a regression of the given
+/// method. If `label` is set, the fitted equations are printed in the top left corner of
+/// the chart.
+pub fn add_trendline(extras: &mut Extras, plot: &Plot, method: Method, label: bool) {
+    let name = format!("trend{}", extras.data().len());
+
+    let mut regression = Regression::new(plot.x, plot.y, method);
+    if let Some(series) = plot.series {
+        regression = regression.groupby(series);
+    }
+    let mut curve = DataSet::from_source(&name, plot.data);
+    curve.add_transform(Transform::Regression(regression));
+    extras.add_data_set(curve);
+
+    let line = Mark::new(MarkType::Line)
+        .enter("x", ValueRef::scaled(plot.x_scale, plot.x))
+        .enter("y", ValueRef::scaled(plot.y_scale, plot.y))
+        .enter("stroke", plot.series_color("gray"))
+        .enter("strokeWidth", ValueRef::value(1.5))
+        .enter("strokeDash", ValueRef::value(vec![6, 4]));
+    let line = plot.per_series(&name, line);
+    extras.add_mark(line);
+
+    if !label {
+        return;
+    }
+    let params_name = format!("{}_params", name);
+    let mut params = Regression::new(plot.x, plot.y, method).params(true);
+    if let Some(series) = plot.series {
+        params = params.groupby(series);
+    }
+    let mut coefficients = DataSet::from_source(&params_name, plot.data);
+    coefficients.add_transform(Transform::Regression(params));
+    coefficients.add_transform(Transform::Window(
+        Window::new().op(WindowOp::RowNumber, None, "row"),
+    ));
+    extras.add_data_set(coefficients);
+
+    let text = format!(
+        "{} + '  (R² = ' + format(datum.rSquared, '.3f') + ')'",
+        method.equation_expression()
+    );
+    let rtl = extras.is_right_to_left();
+    let (x, align) = if rtl {
+        (ValueRef::signal("width - 5"), Align::Right)
+    } else {
+        (ValueRef::value(5), Align::Left)
+    };
+    extras.add_mark(
+        Mark::text(ValueRef::signal(&text))
+            .from_data(&params_name)
+            .at(x, ValueRef::signal("datum.row * 14"))
+            .align(align)
+            .right_to_left(rtl)
+            .enter("fill", plot.series_color("gray")),
+    );
+}
+
+/// Adds a smoothed line to every series of the plot, which is the mean over a trailing window
+/// of the given number of data objects, ordered by x
+pub fn add_moving_average(extras: &mut Extras, plot: &Plot, window: u32) {
+    let name = format!("moving_average{}", extras.data().len());
+
+    let mut average = Window::new()
+        .sort(plot.x, Order::Ascending)
+        .op(WindowOp::Mean, Some(plot.y), "moving_average")
+        .frame(Some(1 - window.max(1) as i32), Some(0));
+    if let Some(series) = plot.series {
+        average = average.groupby(series);
+    }
+    let mut smoothed = DataSet::from_source(&name, plot.data);
+    smoothed.add_transform(Transform::Window(average));
+    extras.add_data_set(smoothed);
+
+    let line = Mark::new(MarkType::Line)
+        .enter("x", ValueRef::scaled(plot.x_scale, plot.x))
+        .enter("y", ValueRef::scaled(plot.y_scale, "moving_average"))
+        .enter("stroke", plot.series_color("gray"))
+        .enter("strokeWidth", ValueRef::value(3))
+        .enter("strokeOpacity", ValueRef::value(0.6));
+    let line = plot.per_series(&name, line);
+    extras.add_mark(line);
+}
+
+/// Adds a smooth curve through every series of the plot, fitted by a loess transform with the
+/// given bandwidth. Unlike a trend line it doesn't assume the data follows a given model.
+pub fn add_loess(extras: &mut Extras, plot: &Plot, bandwidth: f64) {
+    let name = format!("loess{}", extras.data().len());
+
+    let groupby = plot.series.into_iter().map(String::from).collect();
+    let mut smoothed = DataSet::from_source(&name, plot.data);
+    smoothed.add_transform(Transform::Loess {
+        x: String::from(plot.x),
+        y: String::from(plot.y),
+        groupby,
+        bandwidth: Some(bandwidth),
+    });
+    extras.add_data_set(smoothed);
+
+    let line = Mark::new(MarkType::Line)
+        .enter("x", ValueRef::scaled(plot.x_scale, plot.x))
+        .enter("y", ValueRef::scaled(plot.y_scale, plot.y))
+        .enter("stroke", plot.series_color("gray"))
+        .enter("strokeWidth", ValueRef::value(2))
+        .enter("strokeOpacity", ValueRef::value(0.8));
+    let line = plot.per_series(&name, line);
+    extras.add_mark(line);
+}
+
+/// Adds tooltips showing the x and y value of the closest point of the plot to wherever the
+/// pointer is. The chart is divided into the voronoi cells of the points, which act as
+/// invisible hover targets, so nobody needs to point at a point exactly.
+pub fn add_voronoi(extras: &mut Extras, plot: &Plot) {
+    let name = format!("voronoi_points{}", extras.marks().len());
+
+    extras.add_mark(
+        Mark::symbol(SymbolShape::Circle, 0.0)
+            .name(&name)
+            .from_data(plot.data)
+            .at(
+                ValueRef::scaled(plot.x_scale, plot.x),
+                ValueRef::scaled(plot.y_scale, plot.y),
+            )
+            .interactive(false)
+            .aria(false),
+    );
+
+    let tooltip = format!(
+        "{{'{x}': datum.datum['{x}'], '{y}': datum.datum['{y}']}}",
+        x = plot.x,
+        y = plot.y
+    );
+    extras.add_mark(
+        Mark::path(ValueRef::field("path"))
+            .from_data(&name)
+            .enter("fill", ValueRef::value("transparent"))
+            .enter("tooltip", ValueRef::signal(&tooltip))
+            .aria(false)
+            .transform(Transform::Voronoi {
+                x: String::from("datum.x"),
+                y: String::from("datum.y"),
+                size: [ValueRef::signal("width"), ValueRef::signal("height")],
+            }),
+    );
+}
+
+/// Lets the points of the plot be stepped through with the keyboard. The arrow keys move a
+/// highlight from point to point in the order of x, Escape removes it, and Enter sets the
+/// `click` signal to the highlighted data object, which is where a mouse click on a mark sets
+/// it as well. The index of the highlighted point is kept in the `focus` signal, -1 while
+/// nothing is highlighted.
+///
+/// Vega rejects specs defining a signal twice, so this does nothing if a `focus` or `click`
+/// signal has been added already, including by an earlier call.
+pub fn add_keyboard_navigation(extras: &mut Extras, plot: &Plot) {
+    if extras.has_signal("focus") || extras.has_signal("click") {
+        return;
+    }
+    let name = format!("focused{}", extras.data().len());
+
+    let mut order = Window::new()
+        .sort(plot.x, Order::Ascending)
+        .op(WindowOp::RowNumber, None, "focus_index");
+    if let Some(series) = plot.series {
+        order = order.sort(series, Order::Ascending);
+    }
+    let mut focused = DataSet::from_source(&name, plot.data);
+    focused.add_transform(Transform::Window(order));
+    focused.add_transform(Transform::filter("datum.focus_index === focus + 1"));
+    extras.add_data_set(focused);
+
+    let key = |keys: &[&str]| {
+        let filter: Vec<String> = keys.iter().map(|k| format!("event.key === '{}'", k)).collect();
+        format!("window:keydown[{}]", filter.join(" || "))
+    };
+    let last = format!("length(data('{}')) - 1", plot.data);
+    extras.add_signal(
+        Signal::new("focus", -1)
+            .on(&key(&["ArrowRight", "ArrowDown"]), &format!("min(focus + 1, {})", last))
+            .on(&key(&["ArrowLeft", "ArrowUp"]), "max(focus - 1, 0)")
+            .on(&key(&["Home"]), "0")
+            .on(&key(&["End"]), &last)
+            .on(&key(&["Escape"]), "-1"),
+    );
+    extras.add_signal(
+        Signal::new("click", Value::Null)
+            .on("click", "datum")
+            .on(
+                &key(&["Enter"]),
+                &format!("focus >= 0 ? data('{}')[0] : click", name),
+            ),
+    );
+
+    extras.add_mark(
+        Mark::symbol(SymbolShape::Circle, 200.0)
+            .from_data(&name)
+            .at(
+                ValueRef::scaled(plot.x_scale, plot.x),
+                ValueRef::scaled(plot.y_scale, plot.y),
+            )
+            .enter("fill", ValueRef::value("transparent"))
+            .stroke("black", 2.0)
+            .interactive(false)
+            .aria(false),
+    );
+}
+
+/// ErrorBar is the uncertainty of a data point
+pub enum ErrorBar {
+    /// The value may lie up to the given amount above or below the data point
+    Symmetric(f64),
+    /// The value lies between the given lower and upper bound
+    Range(f64, f64),
+}
+
+impl ErrorBar {
+    /// The lower and the upper bound around the value of a data point
+    pub fn bounds(&self, value: f64) -> (f64, f64) {
+        match *self {
+            ErrorBar::Symmetric(error) => (value - error.abs(), value + error.abs()),
+            ErrorBar::Range(lo, hi) => (lo, hi),
+        }
+    }
+}
+
+/// Draws an error bar over every data object of the plot with the fields `lo` and `hi`: a
+/// vertical rule spanning from `lo` to `hi`, with a short tick at either end. The bars are
+/// centered on the band of the data object if the x scale is a band scale.
+pub fn add_error_bars(extras: &mut Extras, plot: &Plot) {
+    let name = format!("error_bars{}", extras.data().len());
+
+    let mut errors = DataSet::from_source(&name, plot.data);
+    errors.add_transform(Transform::filter("isValid(datum.lo) && isValid(datum.hi)"));
+    extras.add_data_set(errors);
+
+    let x = |offset: f64| ValueRef::scaled(plot.x_scale, plot.x).band(0.5).offset(offset);
+    let y = |bound: &str| ValueRef::scaled(plot.y_scale, bound);
+    extras.add_mark(
+        Mark::rule(x(0.0), y("lo"), x(0.0), y("hi"))
+            .from_data(&name)
+            .enter("stroke", plot.series_color("black"))
+            .interactive(false),
+    );
+    for &bound in &["lo", "hi"] {
+        extras.add_mark(
+            Mark::rule(x(-4.0), y(bound), x(4.0), y(bound))
+                .from_data(&name)
+                .enter("stroke", plot.series_color("black"))
+                .interactive(false),
+        );
+    }
+}
+
+/// LabelPosition is where the value labels of `add_value_labels` are placed
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LabelPosition {
+    /// Just above the top of a bar, or above a point
+    OutsideEnd,
+    /// Just below the top of a bar, within the bar
+    InsideEnd,
+    /// Halfway between the top of a bar and zero
+    Center,
+}
+
+/// ValueLabels prints the y value of every data point of a chart next to it, see
+/// `add_value_labels`
+/// # Example:
+/// ```rust
+///    use gust::backend::bar_chart::BarChart;
+///    use gust::backend::elements::overlay::{LabelPosition, ValueLabels};
+///
+///    let mut b = BarChart::new();
+///    b.add_data(String::from("A"), 1250.5);
+///    b.show_values(ValueLabels::new(LabelPosition::InsideEnd).format(",.0f").color("white"));
+/// ```
+pub struct ValueLabels {
+    position: LabelPosition,
+    format: Option<String>,
+    color: Option<String>,
+    font_size: Option<f64>,
+    avoid_overlap: bool,
+}
+
+impl ValueLabels {
+    pub fn new(position: LabelPosition) -> ValueLabels {
+        ValueLabels {
+            position,
+            format: None,
+            color: None,
+            font_size: None,
+            avoid_overlap: false,
+        }
+    }
+
+    /// Formats the values with a d3 format string, e.g. ",.2f" or ".0%"
+    pub fn format(mut self, format: &str) -> ValueLabels {
+        self.format = Some(String::from(format));
+        self
+    }
+
+    /// Sets the color of the labels, black by default
+    pub fn color(mut self, color: &str) -> ValueLabels {
+        self.color = Some(String::from(color));
+        self
+    }
+
+    pub fn font_size(mut self, size: f64) -> ValueLabels {
+        self.font_size = Some(size);
+        self
+    }
+
+    /// Moves labels that would overlap each other to another side of their point, and hides
+    /// the ones that can't be placed anywhere, which helps on crowded line and scatter charts
+    pub fn avoid_overlap(mut self, avoid: bool) -> ValueLabels {
+        self.avoid_overlap = avoid;
+        self
+    }
+}
+
+impl From<LabelPosition> for ValueLabels {
+    fn from(position: LabelPosition) -> ValueLabels {
+        ValueLabels::new(position)
+    }
+}
+
+/// Prints the y value of every data point of the plot next to it, e.g. on top of every bar
+pub fn add_value_labels(extras: &mut Extras, plot: &Plot, labels: &ValueLabels) {
+    let text = match labels.format {
+        Some(ref format) => {
+            let expression = format!("format(datum.{}, {})", plot.y, Value::from(format.as_str()));
+            ValueRef::signal(&expression)
+        }
+        None => ValueRef::field(plot.y),
+    };
+    let x = ValueRef::scaled(plot.x_scale, plot.x).band(0.5);
+    let (y, baseline, offset) = match labels.position {
+        LabelPosition::OutsideEnd => (ValueRef::scaled(plot.y_scale, plot.y), Baseline::Bottom, -3.0),
+        LabelPosition::InsideEnd => (ValueRef::scaled(plot.y_scale, plot.y), Baseline::Top, 3.0),
+        LabelPosition::Center => {
+            let middle = format!(
+                "(scale('{s}', datum.{y}) + scale('{s}', 0)) / 2",
+                s = plot.y_scale,
+                y = plot.y
+            );
+            (ValueRef::signal(&middle), Baseline::Middle, 0.0)
+        }
+    };
+    // with overlaps avoided, the label transform offsets the labels itself
+    let y = if labels.avoid_overlap || labels.position == LabelPosition::Center {
+        y
+    } else {
+        y.offset(offset)
+    };
+    let mut mark = Mark::text(text)
+        .from_data(plot.data)
+        .at(x, y)
+        .align(Align::Center)
+        .baseline(baseline)
+        .enter("fill", ValueRef::value(labels.color.as_ref().map_or("black", |c| c.as_str())))
+        .right_to_left(extras.is_right_to_left())
+        .interactive(false);
+    if let Some(size) = labels.font_size {
+        mark = mark.enter("fontSize", ValueRef::value(size));
+    }
+    if labels.avoid_overlap {
+        let anchor = match labels.position {
+            LabelPosition::OutsideEnd => vec!["top", "right", "left", "bottom"],
+            LabelPosition::InsideEnd => vec!["bottom"],
+            LabelPosition::Center => vec!["middle"],
+        };
+        mark = mark.transform(Transform::Label {
+            size: [ValueRef::signal("width"), ValueRef::signal("height")],
+            anchor: anchor.into_iter().map(String::from).collect(),
+            offset: vec![offset.abs()],
+        });
+    }
+    extras.add_mark(mark);
+}
+
+/// Shading styles a shaded region or band, see `add_region` and `add_band`
+/// # Example:
+/// ```rust
+///    use gust::backend::line_chart::LineChart;
+///    use gust::backend::elements::overlay::Shading;
+///    use gust::backend::traits::Cartesian;
+///
+///    let mut c = LineChart::new();
+///    for i in 0..20 {
+///        c.add_data(i, i * i, 0);
+///    }
+///    c.add_region(5, 8, Shading::new().label("Maintenance").color("orange"));
+///    c.add_band(100, 200, Shading::new().opacity(0.1));
+/// ```
+pub struct Shading {
+    color: String,
+    opacity: f64,
+    label: Option<String>,
+}
+
+impl Shading {
+    /// Gray shading at an opacity of 0.2, without a label
+    pub fn new() -> Shading {
+        Shading {
+            color: String::from("gray"),
+            opacity: 0.2,
+            label: None,
+        }
+    }
+
+    pub fn color(mut self, color: &str) -> Shading {
+        self.color = String::from(color);
+        self
+    }
+
+    /// Sets the opacity, from 0 for invisible to 1 for opaque
+    pub fn opacity(mut self, opacity: f64) -> Shading {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Prints a label in the top corner of the shaded area
+    pub fn label(mut self, label: &str) -> Shading {
+        self.label = Some(String::from(label));
+        self
+    }
+
+    /// A rect mark filling the area between x and x2, and y and y2
+    fn rect(&self, x: ValueRef, x2: ValueRef, y: ValueRef, y2: ValueRef) -> Mark {
+        Mark::new(MarkType::Rect)
+            .enter("x", x)
+            .enter("x2", x2)
+            .enter("y", y)
+            .enter("y2", y2)
+            .enter("fill", ValueRef::value(self.color.as_str()))
+            .enter("fillOpacity", ValueRef::value(self.opacity))
+            .interactive(false)
+    }
+
+    /// A text mark with the label at the given position, if the shading has one
+    fn text(&self, x: ValueRef, y: ValueRef, align: Align, rtl: bool) -> Option<Mark> {
+        self.label.as_ref().map(|label| {
+            Mark::text(ValueRef::value(label.as_str()))
+                .at(x, y)
+                .align(align)
+                .baseline(Baseline::Top)
+                .right_to_left(rtl)
+                .font("sans-serif", 11.0)
+                .enter("fill", ValueRef::value("gray"))
+                .interactive(false)
+        })
+    }
+}
+
+/// Shades the area between `start` and `end` of the x scale with the given name across the
+/// whole height of the chart, e.g. to mark a recession or a maintenance window. On band scales
+/// the area covers the bands of both `start` and `end`.
+pub fn add_region(extras: &mut Extras, x_scale: &str, start: Value, end: Value, shading: Shading) {
+    let rtl = extras.is_right_to_left();
+    extras.add_mark(shading.rect(
+        ValueRef::scaled_value(x_scale, start.clone()),
+        ValueRef::scaled_value(x_scale, end.clone()).band(1.0),
+        ValueRef::value(0),
+        ValueRef::signal("height"),
+    ));
+
+    // on a reversed x scale, the end is on the left
+    let (x, align) = if rtl {
+        (ValueRef::scaled_value(x_scale, start).offset(-4.0), Align::Right)
+    } else {
+        (ValueRef::scaled_value(x_scale, start).offset(4.0), Align::Left)
+    };
+    if let Some(label) = shading.text(x, ValueRef::value(4), align, rtl) {
+        extras.add_mark(label);
+    }
+}
+
+/// Shades the area between `low` and `high` of the y scale with the given name across the
+/// whole width of the chart, e.g. to mark a target range
+pub fn add_band(extras: &mut Extras, y_scale: &str, low: Value, high: Value, shading: Shading) {
+    let rtl = extras.is_right_to_left();
+    extras.add_mark(shading.rect(
+        ValueRef::value(0),
+        ValueRef::signal("width"),
+        ValueRef::scaled_value(y_scale, high.clone()),
+        ValueRef::scaled_value(y_scale, low),
+    ));
+
+    let (x, align) = if rtl {
+        (ValueRef::signal("width - 4"), Align::Right)
+    } else {
+        (ValueRef::value(4), Align::Left)
+    };
+    let y = ValueRef::scaled_value(y_scale, high).offset(4.0);
+    if let Some(label) = shading.text(x, y, align, rtl) {
+        extras.add_mark(label);
+    }
+}
+
+/// Watermark is the content of a watermark, see `watermark`
+pub enum Watermark {
+    Text(String),
+    /// The image at `url`, scaled to the given width and height in pixels
+    Image { url: String, width: f64, height: f64 },
+}
+
+/// Corner is a corner of a chart
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// A mark showing the watermark in the given corner of the chart, where opacity ranges from 0
+/// for invisible to 1 for opaque. It doesn't react to the pointer and is hidden from screen
+/// readers, so it doesn't get in the way of the chart.
+pub fn watermark(content: Watermark, corner: Corner, opacity: f64) -> Mark {
+    let (x, align) = match corner {
+        Corner::TopLeft | Corner::BottomLeft => (ValueRef::value(5), Align::Left),
+        Corner::TopRight | Corner::BottomRight => (ValueRef::signal("width - 5"), Align::Right),
+    };
+    let (y, baseline) = match corner {
+        Corner::TopLeft | Corner::TopRight => (ValueRef::value(5), Baseline::Top),
+        Corner::BottomLeft | Corner::BottomRight => {
+            (ValueRef::signal("height - 5"), Baseline::Bottom)
+        }
+    };
+    let mark = match content {
+        Watermark::Text(text) => Mark::text(ValueRef::value(text))
+            .font("sans-serif", 12.0)
+            .enter("fill", ValueRef::value("gray")),
+        Watermark::Image { url, width, height } => {
+            Mark::image(ValueRef::value(url), width, height)
+        }
+    };
+    mark.name("watermark")
+        .at(x, y)
+        .align(align)
+        .baseline(baseline)
+        .enter("opacity", ValueRef::value(opacity))
+        .interactive(false)
+        .aria(false)
+}
@@ -0,0 +1,85 @@
+/*
+ * Predicates, which test the fields of a datum in conditional encodings and filters
+ */
+
+use serde_json::Value;
+
+/// Predicate is a test on the fields of a datum, which is written out as a Vega expression
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::predicate::Predicate;
+///
+///    assert_eq!(Predicate::less_than("amount", 0.0).expression(), "datum[\"amount\"] < 0");
+///    let either = Predicate::equals("region", "North").or(Predicate::greater_than("amount", 100.0));
+/// ```
+pub enum Predicate {
+    GreaterThan(String, f64),
+    LessThan(String, f64),
+    /// The field holds exactly the value
+    Equals(String, Value),
+    /// The field lies between the two values, both included
+    Between(String, f64, f64),
+    /// The field holds no value, or one that isn't a number
+    Missing(String),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+    /// Any Vega expression, which may refer to the datum as `datum`
+    Expression(String),
+}
+
+impl Predicate {
+    pub fn greater_than(field: &str, value: f64) -> Predicate {
+        Predicate::GreaterThan(String::from(field), value)
+    }
+
+    pub fn less_than(field: &str, value: f64) -> Predicate {
+        Predicate::LessThan(String::from(field), value)
+    }
+
+    pub fn equals<V: Into<Value>>(field: &str, value: V) -> Predicate {
+        Predicate::Equals(String::from(field), value.into())
+    }
+
+    pub fn between(field: &str, min: f64, max: f64) -> Predicate {
+        Predicate::Between(String::from(field), min, max)
+    }
+
+    pub fn missing(field: &str) -> Predicate {
+        Predicate::Missing(String::from(field))
+    }
+
+    pub fn from_expression(expression: &str) -> Predicate {
+        Predicate::Expression(String::from(expression))
+    }
+
+    pub fn and(self, other: Predicate) -> Predicate {
+        Predicate::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Predicate) -> Predicate {
+        Predicate::Or(Box::new(self), Box::new(other))
+    }
+
+    pub fn negate(self) -> Predicate {
+        Predicate::Not(Box::new(self))
+    }
+
+    /// The Vega expression testing the predicate
+    pub fn expression(&self) -> String {
+        let field = |f: &str| format!("datum[{}]", Value::from(f));
+        match *self {
+            Predicate::GreaterThan(ref f, value) => format!("{} > {}", field(f), value),
+            Predicate::LessThan(ref f, value) => format!("{} < {}", field(f), value),
+            Predicate::Equals(ref f, ref value) => format!("{} === {}", field(f), value),
+            Predicate::Between(ref f, min, max) => {
+                format!("{f} >= {} && {f} <= {}", min, max, f = field(f))
+            }
+            Predicate::Missing(ref f) => format!("!isValid({f}) || isNaN({f})", f = field(f)),
+            Predicate::And(ref a, ref b) => format!("({}) && ({})", a.expression(), b.expression()),
+            Predicate::Or(ref a, ref b) => format!("({}) || ({})", a.expression(), b.expression()),
+            Predicate::Not(ref p) => format!("!({})", p.expression()),
+            Predicate::Expression(ref expression) => expression.clone(),
+        }
+    }
+}
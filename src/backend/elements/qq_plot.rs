@@ -0,0 +1,36 @@
+
+/*
+ * Elements specific to Q-Q plots
+ */
+
+/// Distribution is a theoretical distribution the sample of a Q-Q plot can be compared against
+pub enum Distribution {
+    /// A normal distribution with the given mean and standard deviation
+    Normal { mean: f64, stdev: f64 },
+    /// A log-normal distribution, whose logarithm has the given mean and standard deviation
+    LogNormal { mean: f64, stdev: f64 },
+    /// A uniform distribution between min and max
+    Uniform { min: f64, max: f64 },
+}
+
+impl Distribution {
+    /// The standard normal distribution, with a mean of 0 and a standard deviation of 1
+    pub fn standard_normal() -> Distribution {
+        Distribution::Normal { mean: 0.0, stdev: 1.0 }
+    }
+
+    /// A Vega expression computing the quantile of the distribution at `datum.prob`
+    pub fn quantile_expression(&self) -> String {
+        match *self {
+            Distribution::Normal { mean, stdev } => {
+                format!("quantileNormal(datum.prob, {}, {})", mean, stdev)
+            }
+            Distribution::LogNormal { mean, stdev } => {
+                format!("quantileLogNormal(datum.prob, {}, {})", mean, stdev)
+            }
+            Distribution::Uniform { min, max } => {
+                format!("quantileUniform(datum.prob, {}, {})", min, max)
+            }
+        }
+    }
+}
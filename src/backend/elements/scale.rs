@@ -0,0 +1,295 @@
+
+/*
+ * Scale element shared by all charts
+ */
+
+use backend::elements::mark::SymbolShape;
+
+/// ScaleType lists the kinds of Vega scales Gust knows how to emit
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ScaleType {
+    Linear,
+    Band,
+    Point,
+    Ordinal,
+    /// A logarithmic scale, which can only map positive values
+    Log,
+    /// A power scale with an exponent of 0.5
+    Sqrt,
+    /// A power scale, the exponent is set through `Scale::set_exponent`
+    Pow,
+    /// A symmetric log scale, which unlike a log scale can handle zero and negative values.
+    /// The slope around zero is tuned through `Scale::set_constant`
+    Symlog,
+    /// Divides the continuous domain into uniform segments, one for each value of the range
+    Quantize,
+    /// Divides a sample of domain values into groups of the same size, one for each value of
+    /// the range
+    Quantile,
+    /// Maps values onto the range based on a set of explicit threshold values
+    Threshold,
+    /// A temporal scale over timestamps, in the viewer's local time zone
+    Time,
+    /// A temporal scale over timestamps, in UTC
+    Utc,
+}
+
+/// Domain describes the input values a scale maps from
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Domain {
+    /// The domain spans all the values of `field` in the data set `data`
+    Data { data: String, field: String },
+    /// The domain spans all the values of several fields in the data set `data`
+    Fields { data: String, fields: Vec<String> },
+    /// An explicit list of domain values
+    Values(Vec<f64>),
+    /// The domain spans the values of fields from several data sets, see `union`
+    Union { fields: Vec<Domain> },
+}
+
+impl Domain {
+    pub fn field(data: &str, field: &str) -> Domain {
+        Domain::Data {
+            data: String::from(data),
+            field: String::from(field),
+        }
+    }
+
+    /// A domain spanning several fields, e.g. the lower and upper end `y0` and `y1` of a stack
+    pub fn fields(data: &str, fields: &[&str]) -> Domain {
+        Domain::Fields {
+            data: String::from(data),
+            fields: fields.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    /// A domain spanning fields of different data sets, given as (data, field) pairs, e.g. the
+    /// x values of two data sets that share an axis
+    pub fn union(sources: &[(&str, &str)]) -> Domain {
+        Domain::Union {
+            fields: sources.iter().map(|&(data, field)| Domain::field(data, field)).collect(),
+        }
+    }
+}
+
+/// Range describes the output values a scale maps onto
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Range {
+    /// One of Vega's named ranges, like "width", "height" or "category"
+    Named(String),
+    /// A named Vega color scheme, like "blues" or "viridis"
+    Scheme { scheme: String },
+    /// An explicit list of output values, such as colors
+    Values(Vec<String>),
+    /// An explicit list of numeric output values, such as sizes in pixels
+    Numbers(Vec<f64>),
+    /// A Vega expression evaluating to the range, e.g. "[0, bandwidth('x')]"
+    Signal { signal: String },
+}
+
+impl Range {
+    pub fn scheme(scheme: &str) -> Range {
+        Range::Scheme { scheme: String::from(scheme) }
+    }
+    pub fn values(values: &[&str]) -> Range {
+        Range::Values(values.iter().map(|v| v.to_string()).collect())
+    }
+
+    pub fn numbers(values: &[f64]) -> Range {
+        Range::Numbers(values.to_vec())
+    }
+
+    pub fn signal(expression: &str) -> Range {
+        Range::Signal { signal: String::from(expression) }
+    }
+
+    /// A range of symbol shapes, for an ordinal scale encoding the shape of symbols
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::mark::SymbolShape;
+    ///    use gust::backend::elements::scale::{Domain, Range, Scale, ScaleType};
+    ///
+    ///    let shapes = Scale::with_range(
+    ///        "shape",
+    ///        ScaleType::Ordinal,
+    ///        Domain::field("table", "z"),
+    ///        Range::shapes(&[SymbolShape::Circle, SymbolShape::Path(String::from("M0,-1L1,1H-1Z"))]),
+    ///    );
+    /// ```
+    pub fn shapes(shapes: &[SymbolShape]) -> Range {
+        Range::Values(shapes.iter().map(|s| s.name()).collect())
+    }
+}
+
+/// Scale represents a single Vega scale. Optional properties are only emitted once set, so
+/// Vega's defaults apply to anything that was not configured.
+#[derive(Serialize)]
+pub struct Scale {
+    name: String,
+    #[serde(rename = "type")]
+    scale_type: ScaleType,
+    domain: Domain,
+    range: Range,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    padding: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    zero: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nice: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    clamp: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reverse: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exponent: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constant: Option<f64>,
+    /// The zero setting the scale had before it became a log scale
+    #[serde(skip_serializing)]
+    zero_before_log: Option<bool>,
+}
+
+impl Scale {
+    /// Creates a scale with the given name, mapping the domain onto a named Vega range such as
+    /// "width", "height" or "category"
+    pub fn new(name: &str, scale_type: ScaleType, domain: Domain, range: &str) -> Scale {
+        Scale::with_range(name, scale_type, domain, Range::Named(String::from(range)))
+    }
+
+    /// The name marks refer to the scale by
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn scale_type(&self) -> ScaleType {
+        self.scale_type
+    }
+
+    /// The field the domain of the scale spans, if it spans a single field of a data set
+    pub fn domain_field(&self) -> Option<&str> {
+        match self.domain {
+            Domain::Data { ref field, .. } => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Creates a scale which maps the domain onto an arbitrary range
+    pub fn with_range(name: &str, scale_type: ScaleType, domain: Domain, range: Range) -> Scale {
+        Scale {
+            name: String::from(name),
+            scale_type,
+            domain,
+            range,
+            padding: None,
+            zero: None,
+            nice: None,
+            clamp: None,
+            reverse: None,
+            exponent: None,
+            constant: None,
+            zero_before_log: None,
+        }
+    }
+
+    /// Creates a quantize scale over `field` of the data set `data`. The extent of the field is
+    /// split into as many equally sized segments as there are values in `range`.
+    pub fn quantize(name: &str, data: &str, field: &str, range: Range) -> Scale {
+        Scale::with_range(name, ScaleType::Quantize, Domain::field(data, field), range)
+    }
+
+    /// Creates a quantile scale over `field` of the data set `data`, putting the same number of
+    /// values into each of the groups described by `range`
+    pub fn quantile(name: &str, data: &str, field: &str, range: Range) -> Scale {
+        Scale::with_range(name, ScaleType::Quantile, Domain::field(data, field), range)
+    }
+
+    /// Creates a threshold scale. Values below the first threshold map onto the first value of
+    /// `range`, so `range` should hold one more value than `thresholds`.
+    pub fn threshold(name: &str, thresholds: Vec<f64>, range: Range) -> Scale {
+        Scale::with_range(name, ScaleType::Threshold, Domain::Values(thresholds), range)
+    }
+
+    /// Changes the type of the scale. As a log scale can't extend its domain to zero, turning
+    /// the scale into one turns `include_zero` off, until it is turned into another type again.
+    pub fn set_scale_type(&mut self, scale_type: ScaleType) {
+        match (self.scale_type == ScaleType::Log, scale_type == ScaleType::Log) {
+            (false, true) => {
+                self.zero_before_log = self.zero;
+                self.zero = Some(false);
+            }
+            (true, false) => self.zero = self.zero_before_log.take(),
+            _ => (),
+        }
+        self.scale_type = scale_type;
+    }
+
+    /// Switches a temporal scale between UTC and the local time of the viewer. Charts that are
+    /// generated on a server should usually stick to UTC, so they look the same in every time
+    /// zone. Scales that are not temporal are left as they are, `set_scale_type` turns them
+    /// into temporal ones.
+    pub fn set_utc(&mut self, utc: bool) {
+        match self.scale_type {
+            ScaleType::Time | ScaleType::Utc => {
+                self.set_scale_type(if utc { ScaleType::Utc } else { ScaleType::Time })
+            }
+            _ => (),
+        }
+    }
+
+    pub fn set_range(&mut self, range: Range) {
+        self.range = range;
+    }
+
+    pub fn set_padding(&mut self, padding: f64) {
+        self.padding = Some(padding);
+    }
+
+    /// include_zero controls whether the domain of a quantitative scale is extended to include
+    /// zero. Turning it off lets line and area charts start at the minimum of their data, while
+    /// bar charts should keep their zero baseline.
+    pub fn include_zero(&mut self, zero: bool) {
+        self.zero = Some(zero);
+    }
+
+    /// Toggles whether the domain of a quantitative scale is extended to round values, which
+    /// Vega does by default for linear scales
+    pub fn set_nice(&mut self, nice: bool) {
+        self.nice = Some(nice);
+    }
+
+    /// Replaces the domain of the scale, e.g. to pin it to fixed values instead of deriving it
+    /// from the data
+    pub fn set_domain(&mut self, domain: Domain) {
+        self.domain = domain;
+    }
+
+    /// When clamp is turned on, values outside of the domain are pinned to the edge of the range
+    /// instead of being drawn outside of the plot
+    pub fn set_clamp(&mut self, clamp: bool) {
+        self.clamp = Some(clamp);
+    }
+
+    /// Flips the range of the scale, e.g. to draw an x axis from right to left
+    pub fn set_reverse(&mut self, reverse: bool) {
+        self.reverse = Some(reverse);
+    }
+
+    /// Leaves the direction of the range to Vega's default again
+    pub fn clear_reverse(&mut self) {
+        self.reverse = None;
+    }
+
+    /// Sets the exponent of a `ScaleType::Pow` scale
+    pub fn set_exponent(&mut self, exponent: f64) {
+        self.exponent = Some(exponent);
+    }
+
+    /// Sets the constant of a `ScaleType::Symlog` scale, which determines how quickly the scale
+    /// switches from linear to logarithmic behaviour around zero
+    pub fn set_constant(&mut self, constant: f64) {
+        self.constant = Some(constant);
+    }
+}
@@ -0,0 +1,176 @@
+
+/*
+ * Signals, the dynamic variables of a chart which respond to input events
+ */
+
+use backend::elements::general::Interpolation;
+use serde_json::Value;
+use std::ops::Range;
+
+/// EventHandler updates a signal whenever one of the events it listens to occurs
+#[derive(Serialize)]
+pub struct EventHandler {
+    /// A Vega event selector, such as `click` or `window:keydown[event.key === 'Enter']`
+    events: String,
+    /// The Vega expression computing the new value of the signal
+    update: String,
+}
+
+/// Binding is an input element below the chart which sets the value of a signal, see
+/// `Signal::bind`
+#[derive(Serialize)]
+pub struct Binding {
+    input: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    step: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    options: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+impl Binding {
+    fn input(input: &'static str, options: Vec<Value>) -> Binding {
+        Binding {
+            input,
+            min: None,
+            max: None,
+            step: None,
+            options,
+            name: None,
+        }
+    }
+
+    /// A slider from `min` to `max`, moving in steps of `step`
+    pub fn range(min: f64, max: f64, step: f64) -> Binding {
+        Binding {
+            min: Some(min),
+            max: Some(max),
+            step: Some(step),
+            ..Binding::input("range", vec![])
+        }
+    }
+
+    /// A drop down menu of the options
+    pub fn select<V: Into<Value> + Clone>(options: &[V]) -> Binding {
+        Binding::input("select", options.iter().cloned().map(Into::into).collect())
+    }
+
+    /// A group of radio buttons, one for each option
+    pub fn radio<V: Into<Value> + Clone>(options: &[V]) -> Binding {
+        Binding::input("radio", options.iter().cloned().map(Into::into).collect())
+    }
+
+    /// A checkbox, which sets the signal to true or false
+    pub fn checkbox() -> Binding {
+        Binding::input("checkbox", vec![])
+    }
+
+    /// Labels the input, instead of with the name of the signal
+    pub fn label(mut self, label: &str) -> Binding {
+        self.name = Some(String::from(label));
+        self
+    }
+}
+
+/// Signal is a named value which marks, transforms and other signals can refer to in their
+/// expressions. Its value changes in response to input events, see `on`, or through an input
+/// element bound to it, see `bind`.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::signal::Signal;
+///
+///    // counts the clicks on the chart
+///    let clicks = Signal::new("clicks", 0).on("click", "clicks + 1");
+///
+///    // a slider below the chart, which expressions can refer to as `bandwidth`
+///    let bandwidth = Signal::slider("bandwidth", 0.0..1.0, 0.1).label("Bandwidth");
+///    let method = Signal::select("method", &["linear", "quad", "poly"]);
+///    let show_points = Signal::checkbox("show_points", true);
+/// ```
+#[derive(Serialize)]
+pub struct Signal {
+    name: String,
+    value: Value,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    on: Vec<EventHandler>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bind: Option<Binding>,
+}
+
+impl Signal {
+    /// Creates a signal holding `value` until an event updates it
+    pub fn new<V: Into<Value>>(name: &str, value: V) -> Signal {
+        Signal {
+            name: String::from(name),
+            value: value.into(),
+            on: vec![],
+            bind: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A signal set through a slider from the start to the end of `range`, starting out at
+    /// the start
+    pub fn slider(name: &str, range: Range<f64>, step: f64) -> Signal {
+        Signal::new(name, range.start).bind(Binding::range(range.start, range.end, step))
+    }
+
+    /// A signal set through a drop down menu of the options, starting out at the first one
+    pub fn select<V: Into<Value> + Clone>(name: &str, options: &[V]) -> Signal {
+        let value = options.first().cloned().map_or(Value::Null, Into::into);
+        Signal::new(name, value).bind(Binding::select(options))
+    }
+
+    /// A signal set through radio buttons for the options, starting out at the first one
+    pub fn radio<V: Into<Value> + Clone>(name: &str, options: &[V]) -> Signal {
+        let value = options.first().cloned().map_or(Value::Null, Into::into);
+        Signal::new(name, value).bind(Binding::radio(options))
+    }
+
+    /// A signal set through a checkbox, which is checked if `checked` is true
+    pub fn checkbox(name: &str, checked: bool) -> Signal {
+        Signal::new(name, checked).bind(Binding::checkbox())
+    }
+
+    /// The signal choosing the interpolation of the lines or areas of a chart from a menu,
+    /// which marks refer to as `interpolate`
+    pub fn interpolation(value: Interpolation) -> Signal {
+        let options: Vec<&str> = Interpolation::all().iter().map(|i| i.name()).collect();
+        Signal::select("interpolate", &options).value(value.name())
+    }
+
+    /// Sets the value the signal starts out with
+    pub fn value<V: Into<Value>>(mut self, value: V) -> Signal {
+        self.value = value.into();
+        self
+    }
+
+    /// Binds the signal to an input element below the chart, which sets its value
+    pub fn bind(mut self, binding: Binding) -> Signal {
+        self.bind = Some(binding);
+        self
+    }
+
+    /// Labels the input element the signal is bound to, instead of with its name
+    pub fn label(mut self, label: &str) -> Signal {
+        self.bind = self.bind.map(|b| b.label(label));
+        self
+    }
+
+    /// Sets the signal to the result of `update` whenever one of the `events` occurs
+    pub fn on(mut self, events: &str, update: &str) -> Signal {
+        self.on.push(EventHandler {
+            events: String::from(events),
+            update: String::from(update),
+        });
+        self
+    }
+}
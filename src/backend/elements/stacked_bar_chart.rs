@@ -1,128 +1,45 @@
 
 
 use backend::elements::general::*;
+use backend::elements::transform::{StackOffset, Transform};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::Value;
 
 #[derive(Serialize)]
 pub struct StackedBarData {
     name: String,
-    transform: Vec<StackedBarTransform>,
+    transform: Vec<Transform>,
     values: Vec<StackedBarDataValue>,
 }
 
 #[derive(Serialize)]
 pub struct StackedBarDataValue {
-    x: i64,
-    y: i64,
-    z: i64,
+    x: Value,
+    y: f64,
+    z: Value,
 }
 
 impl StackedBarData {
     pub fn new() -> StackedBarData {
         StackedBarData {
             name: String::from("table"),
-            transform: vec![StackedBarTransform::new()],
+            transform: vec![Transform::stack("x", "z", "y")],
             values: vec![],
         }
     }
-    pub fn add_data(&mut self, x: i64, y: i64, z: i64) {
-        self.values.push(StackedBarDataValue { x, y, z });
-    }
-}
-
-pub struct StackedBarTransform {
-    transform_type: String,
-    group_by: Vec<String>,
-    sort: KeyVal,
-    field: String,
-}
-impl StackedBarTransform {
-    pub fn new() -> StackedBarTransform {
-        StackedBarTransform {
-            transform_type: String::from("stack"),
-            group_by: vec![String::from("x")],
-            sort: KeyVal::new("field", "z"),
-            field: String::from("y"),
-        }
-    }
-}
-impl Serialize for StackedBarTransform {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_struct("transform", 3)?;
-        s.serialize_field("type", &self.transform_type)?;
-        s.serialize_field("groupby", &self.group_by)?;
-        s.serialize_field("sort", &self.sort)?;
-        s.serialize_field("field", &self.field)?;
-        s.end()
-    }
-}
-
-pub struct StackedBarScale {
-    name: String,
-    scale_type: String,
-    range: String,
-    domain: JSONDict,
-}
-impl StackedBarScale {
-    pub fn new_xscale() -> StackedBarScale {
-        StackedBarScale {
-            name: String::from("x"),
-            scale_type: String::from("band"),
-            range: String::from("width"),
-            domain: JSONDict::create("data", "table", "field", "x"),
-        }
-    }
-    pub fn new_yscale() -> StackedBarScale {
-        StackedBarScale {
-            name: String::from("y"),
-            scale_type: String::from("linear"),
-            range: String::from("height"),
-            domain: JSONDict::create("data", "table", "field", "y1"),
-        }
-    }
-    pub fn new_ordinal_scale() -> StackedBarScale {
-        StackedBarScale {
-            name: String::from("color"),
-            scale_type: String::from("ordinal"),
-            range: String::from("category"),
-            domain: JSONDict::create("data", "table", "field", "z"),
-        }
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional)
     }
-}
-impl Serialize for StackedBarScale {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut s = serializer.serialize_struct("scale", 10)?;
-        s.serialize_field("name", &self.name)?;
-        s.serialize_field("type", &self.scale_type)?;
-        s.serialize_field("range", &self.range)?;
-        s.serialize_field("domain", &self.domain)?;
-        s.end()
+    pub fn add_data(&mut self, x: Value, y: f64, z: Value) {
+        self.values.push(StackedBarDataValue { x, y, z });
     }
-}
-#[derive(Serialize)]
-pub struct StackedBarAxis {
-    orient: Orientation,
-    scale: String,
-}
-
-impl StackedBarAxis {
-    pub fn new_xaxis() -> StackedBarAxis {
-        StackedBarAxis {
-            orient: Orientation::Bottom,
-            scale: String::from("x"),
+    pub fn set_stack_offset(&mut self, stack_offset: StackOffset) {
+        if let Transform::Stack { ref mut offset, .. } = self.transform[0] {
+            *offset = Some(stack_offset);
         }
     }
-    pub fn new_yaxis() -> StackedBarAxis {
-        StackedBarAxis {
-            orient: Orientation::Left,
-            scale: String::from("y"),
-        }
+    pub fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        &mut self.transform
     }
 }
 
@@ -139,6 +56,9 @@ impl StackedBarMark {
             encode: StackedBarEncoding::new(),
         }
     }
+    pub fn set_color(&mut self, color: &str) {
+        self.encode.enter.fill = JSONDict::single("value", color);
+    }
 }
 
 impl Serialize for StackedBarMark {
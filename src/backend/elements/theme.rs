@@ -0,0 +1,229 @@
+
+/*
+ * Themes, which style every part of a chart through the Vega config
+ */
+
+use backend::elements::accessibility::{self, MIN_GRAPHICS_CONTRAST, MIN_TEXT_CONTRAST, OKABE_ITO};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::{Map, Value};
+
+/// Theme holds the styling of a chart. Everything that isn't set keeps Vega's default.
+///
+/// Themes can be loaded from TOML, YAML or JSON files through `Theme::from_file` (see
+/// `backend::config`), where the fields are named like the ones of this struct:
+/// ```toml
+/// background = "#fafafa"
+/// font = "Helvetica"
+/// palette = ["#1b9e77", "#d95f02", "#7570b3"]
+/// ```
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Theme {
+    /// The background color of the whole chart
+    pub background: Option<String>,
+    /// The font of all text, e.g. axis labels and titles
+    pub font: Option<String>,
+    /// The color of all text
+    pub text_color: Option<String>,
+    /// The color of marks that don't set a color of their own
+    pub mark_color: Option<String>,
+    /// The colors of the series of a chart, used by every scale over the "category" range
+    pub palette: Vec<String>,
+    /// The color of the domain lines and ticks of axes
+    pub axis_color: Option<String>,
+    /// The color of grid lines
+    pub grid_color: Option<String>,
+    /// Whether axes draw grid lines across the chart
+    pub grid: Option<bool>,
+    /// The font size in pixels of the labels and titles of axes and legends
+    pub font_size: Option<f64>,
+    /// The font size in pixels of the title of the chart
+    pub title_font_size: Option<f64>,
+    /// The opacity of marks that don't set one of their own, from 0 for invisible to 1 for
+    /// opaque
+    pub mark_opacity: Option<f64>,
+}
+
+impl Theme {
+    pub fn new() -> Theme {
+        Theme::default()
+    }
+
+    /// A theme coloring series with the colorblind-safe palette of Okabe and Ito
+    pub fn colorblind_safe() -> Theme {
+        Theme {
+            palette: OKABE_ITO.iter().map(|c| c.to_string()).collect(),
+            ..Theme::default()
+        }
+    }
+
+    /// A theme with light text and marks on a dark background
+    pub fn dark() -> Theme {
+        Theme {
+            background: Some(String::from("#2b2b2b")),
+            text_color: Some(String::from("#e0e0e0")),
+            mark_color: Some(String::from("#6fb1e0")),
+            palette: strings(&[
+                "#6fb1e0", "#f4a259", "#8cd17d", "#e15759", "#b9a0e8", "#f1ce63", "#ff9da7",
+            ]),
+            axis_color: Some(String::from("#9e9e9e")),
+            grid_color: Some(String::from("#444444")),
+            ..Theme::default()
+        }
+    }
+
+    /// A theme after ggplot2: a gray panel with white grid lines and no axis lines
+    pub fn ggplot() -> Theme {
+        Theme {
+            background: Some(String::from("#ebebeb")),
+            text_color: Some(String::from("#4d4d4d")),
+            mark_color: Some(String::from("#000000")),
+            palette: strings(&[
+                "#f8766d", "#c49a00", "#53b400", "#00c094", "#00b6eb", "#a58aff", "#fb61d7",
+            ]),
+            axis_color: Some(String::from("#ebebeb")),
+            grid_color: Some(String::from("#ffffff")),
+            grid: Some(true),
+            font_size: Some(11.0),
+            ..Theme::default()
+        }
+    }
+
+    /// A theme after the charts of FiveThirtyEight: a light gray background, light grid
+    /// lines, bold colors and large text
+    pub fn fivethirtyeight() -> Theme {
+        Theme {
+            background: Some(String::from("#f0f0f0")),
+            font: Some(String::from("Helvetica Neue")),
+            text_color: Some(String::from("#3c3c3c")),
+            mark_color: Some(String::from("#30a2da")),
+            palette: strings(&["#30a2da", "#fc4f30", "#e5ae38", "#6d904f", "#8b8b8b"]),
+            axis_color: Some(String::from("#cbcbcb")),
+            grid_color: Some(String::from("#cbcbcb")),
+            grid: Some(true),
+            font_size: Some(12.0),
+            title_font_size: Some(18.0),
+            ..Theme::default()
+        }
+    }
+
+    /// The colors of the theme that don't stand out enough from its background (white, if it
+    /// has none), following the minimum contrasts of WCAG: 4.5:1 for text and 3:1 for marks.
+    /// Colors that aren't written as "#rgb" or "#rrggbb" can't be checked and are left out.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::theme::Theme;
+    ///
+    ///    let mut theme = Theme::new();
+    ///    theme.palette = vec![String::from("#ffeb3b"), String::from("#1565c0")];
+    ///    assert_eq!(theme.low_contrast_colors(), vec![String::from("#ffeb3b")]);
+    /// ```
+    pub fn low_contrast_colors(&self) -> Vec<String> {
+        let background = self.background.as_ref().map_or("#ffffff", |b| b.as_str());
+        let checks = self.palette
+            .iter()
+            .chain(self.mark_color.iter())
+            .chain(self.axis_color.iter())
+            .map(|c| (c, MIN_GRAPHICS_CONTRAST))
+            .chain(self.text_color.iter().map(|c| (c, MIN_TEXT_CONTRAST)));
+
+        let mut low = vec![];
+        for (color, min) in checks {
+            let checkable = accessibility::contrast_ratio(color, background).is_some();
+            if checkable && !accessibility::has_contrast(color, background, min) {
+                low.push(color.clone());
+            }
+        }
+        low
+    }
+
+    /// The Vega config object applying the theme
+    fn config(&self) -> Map<String, Value> {
+        fn set(object: &mut Map<String, Value>, key: &str, value: &Option<String>) {
+            if let Some(ref value) = *value {
+                object.insert(String::from(key), Value::from(value.as_str()));
+            }
+        }
+
+        fn set_number(object: &mut Map<String, Value>, key: &str, value: Option<f64>) {
+            if let Some(value) = value {
+                object.insert(String::from(key), Value::from(value));
+            }
+        }
+
+        fn insert(config: &mut Map<String, Value>, key: &str, object: Map<String, Value>) {
+            if !object.is_empty() {
+                config.insert(String::from(key), Value::Object(object));
+            }
+        }
+
+        let mut config = Map::new();
+        set(&mut config, "background", &self.background);
+
+        let mut text = Map::new();
+        set(&mut text, "font", &self.font);
+        set(&mut text, "fill", &self.text_color);
+        insert(&mut config, "text", text);
+
+        let mut axis = Map::new();
+        set(&mut axis, "labelFont", &self.font);
+        set(&mut axis, "titleFont", &self.font);
+        set(&mut axis, "labelColor", &self.text_color);
+        set(&mut axis, "titleColor", &self.text_color);
+        set(&mut axis, "domainColor", &self.axis_color);
+        set(&mut axis, "tickColor", &self.axis_color);
+        set(&mut axis, "gridColor", &self.grid_color);
+        if let Some(grid) = self.grid {
+            axis.insert(String::from("grid"), Value::from(grid));
+        }
+        set_number(&mut axis, "labelFontSize", self.font_size);
+        set_number(&mut axis, "titleFontSize", self.font_size);
+        insert(&mut config, "axis", axis);
+
+        let mut legend = Map::new();
+        set(&mut legend, "labelFont", &self.font);
+        set(&mut legend, "titleFont", &self.font);
+        set(&mut legend, "labelColor", &self.text_color);
+        set(&mut legend, "titleColor", &self.text_color);
+        set_number(&mut legend, "labelFontSize", self.font_size);
+        set_number(&mut legend, "titleFontSize", self.font_size);
+        insert(&mut config, "legend", legend);
+
+        let mut title = Map::new();
+        set(&mut title, "font", &self.font);
+        set(&mut title, "color", &self.text_color);
+        set_number(&mut title, "fontSize", self.title_font_size);
+        insert(&mut config, "title", title);
+
+        let mut mark = Map::new();
+        set(&mut mark, "color", &self.mark_color);
+        set_number(&mut mark, "opacity", self.mark_opacity);
+        insert(&mut config, "mark", mark);
+
+        if !self.palette.is_empty() {
+            let mut range = Map::new();
+            let palette = self.palette.iter().map(|c| Value::from(c.as_str())).collect();
+            range.insert(String::from("category"), Value::Array(palette));
+            config.insert(String::from("range"), Value::Object(range));
+        }
+        config
+    }
+}
+
+impl Serialize for Theme {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let config = self.config();
+        let mut m = serializer.serialize_map(Some(config.len()))?;
+        for (key, value) in &config {
+            m.serialize_entry(key, value)?;
+        }
+        m.end()
+    }
+}
+
+fn strings(colors: &[&str]) -> Vec<String> {
+    colors.iter().map(|c| c.to_string()).collect()
+}
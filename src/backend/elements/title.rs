@@ -0,0 +1,87 @@
+/*
+ * The title of a chart, drawn above it along with an optional subtitle
+ */
+
+/// Anchor determines where a title is placed along the top of the chart
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+/// Title represents a Vega title. Only its text is always emitted, every other property is left
+/// out of the spec until it has been set, so Vega's defaults apply.
+#[derive(Serialize, Clone)]
+pub struct Title {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subtitle: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    anchor: Option<Anchor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font: Option<String>,
+    #[serde(rename = "fontSize", skip_serializing_if = "Option::is_none")]
+    font_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<f64>,
+}
+
+impl Title {
+    pub fn new(text: &str) -> Title {
+        Title {
+            text: String::from(text),
+            subtitle: None,
+            anchor: None,
+            font: None,
+            font_size: None,
+            color: None,
+            offset: None,
+        }
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = String::from(text);
+    }
+
+    /// Sets a second line of smaller text below the title
+    pub fn set_subtitle(&mut self, subtitle: &str) {
+        self.subtitle = Some(String::from(subtitle));
+    }
+
+    /// Places the title at the start, the middle or the end of the chart, Vega centers it
+    /// by default, and charts laid out right-to-left place it at the end
+    pub fn set_anchor(&mut self, anchor: Anchor) {
+        self.anchor = Some(anchor);
+    }
+
+    /// Sets the font family of the title, e.g. "Georgia, serif"
+    pub fn set_font(&mut self, font: &str) {
+        self.font = Some(String::from(font));
+    }
+
+    /// Sets the font size of the title in pixels
+    pub fn set_font_size(&mut self, size: f64) {
+        self.font_size = Some(size);
+    }
+
+    pub fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+    }
+
+    /// Sets the distance in pixels between the title and the chart
+    pub fn set_offset(&mut self, offset: f64) {
+        self.offset = Some(offset);
+    }
+
+    /// The title as written into a chart laid out right-to-left: anchored at the end, where
+    /// right-to-left text starts, unless an anchor was set
+    pub fn right_to_left(&self) -> Title {
+        let mut title = self.clone();
+        title.anchor = title.anchor.or(Some(Anchor::End));
+        title
+    }
+}
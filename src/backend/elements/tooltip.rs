@@ -0,0 +1,92 @@
+/*
+ * Tooltips, which show the values of the mark under the pointer
+ */
+
+use backend::elements::mark::ValueRef;
+use serde_json;
+use serde_json::Value;
+
+/// Tooltip is the content of the tooltips of a chart, see `Graphable::enable_tooltips`
+/// # Example:
+/// ```rust
+///    use gust::backend::bar_chart::BarChart;
+///    use gust::backend::elements::tooltip::Tooltip;
+///    use gust::backend::traits::Graphable;
+///
+///    let mut b = BarChart::new();
+///    b.set_tooltip(Tooltip::expression("datum.x + ': ' + format(datum.y, ',.2f')"));
+/// ```
+pub enum Tooltip {
+    /// All fields of the datum, which Vega lists with their names
+    Datum,
+    /// The given fields of the datum, listed with their names
+    Fields(Vec<String>),
+    /// A Vega expression evaluated for the datum, e.g. "datum.name + ': ' + datum.value"
+    Expression(String),
+}
+
+impl Tooltip {
+    pub fn fields(fields: &[&str]) -> Tooltip {
+        Tooltip::Fields(fields.iter().map(|f| f.to_string()).collect())
+    }
+
+    pub fn expression(expression: &str) -> Tooltip {
+        Tooltip::Expression(String::from(expression))
+    }
+
+    /// The tooltip channel of a mark showing the tooltip
+    pub fn value_ref(&self) -> ValueRef {
+        match *self {
+            Tooltip::Datum => ValueRef::signal("datum"),
+            Tooltip::Fields(ref fields) => {
+                let entries: Vec<String> = fields
+                    .iter()
+                    .map(|f| {
+                        let name = Value::from(f.as_str());
+                        format!("{}: datum[{}]", name, name)
+                    })
+                    .collect();
+                ValueRef::signal(&format!("{{{}}}", entries.join(", ")))
+            }
+            Tooltip::Expression(ref expression) => ValueRef::signal(expression),
+        }
+    }
+
+    /// Adds the tooltip to the serialized mark `mark`, if it is drawn from data, and to the
+    /// marks nested in it. Marks that show a tooltip of their own, or don't react to the
+    /// pointer, are left as they are.
+    pub fn apply(&self, mark: &mut Value) -> Result<(), serde_json::Error> {
+        let object = match mark.as_object_mut() {
+            Some(object) => object,
+            None => return Ok(()),
+        };
+        if let Some(&mut Value::Array(ref mut marks)) = object.get_mut("marks") {
+            for m in marks.iter_mut() {
+                self.apply(m)?;
+            }
+        }
+        let is_group = object.get("type").and_then(Value::as_str) == Some("group");
+        let interactive = object.get("interactive").and_then(Value::as_bool) != Some(false);
+        if is_group || !interactive || !object.contains_key("from") {
+            return Ok(());
+        }
+        let encode = object
+            .entry(String::from("encode"))
+            .or_insert_with(|| Value::Object(Default::default()));
+        let has_tooltip = encode
+            .as_object()
+            .map_or(false, |e| e.values().any(|set| set.get("tooltip").is_some()));
+        if has_tooltip {
+            return Ok(());
+        }
+        if let Some(encode) = encode.as_object_mut() {
+            let enter = encode
+                .entry(String::from("enter"))
+                .or_insert_with(|| Value::Object(Default::default()));
+            if let Some(enter) = enter.as_object_mut() {
+                enter.insert(String::from("tooltip"), serde_json::to_value(self.value_ref())?);
+            }
+        }
+        Ok(())
+    }
+}
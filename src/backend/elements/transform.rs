@@ -0,0 +1,954 @@
+
+/*
+ * Data transforms which can be attached to the data sets of any chart
+ */
+
+use backend::elements::general::KeyVal;
+use backend::elements::mark::ValueRef;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// TimeUnit lists the units a timestamp can be truncated to by a timeunit transform
+#[derive(Serialize)]
+pub enum TimeUnit {
+    #[serde(rename = "year")]
+    Year,
+    #[serde(rename = "quarter")]
+    Quarter,
+    #[serde(rename = "month")]
+    Month,
+    #[serde(rename = "week")]
+    Week,
+    /// The day of the month
+    #[serde(rename = "date")]
+    Date,
+    /// The day of the week
+    #[serde(rename = "day")]
+    Day,
+    #[serde(rename = "dayofyear")]
+    DayOfYear,
+    #[serde(rename = "hours")]
+    Hour,
+    #[serde(rename = "minutes")]
+    Minute,
+    #[serde(rename = "seconds")]
+    Second,
+    #[serde(rename = "milliseconds")]
+    Millisecond,
+}
+
+/// StackOffset determines the baseline of stacked values
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StackOffset {
+    /// Every stack starts at zero
+    Zero,
+    /// Every stack is centered around zero
+    Center,
+    /// Every stack is scaled to span the range from zero to one, showing proportions instead
+    /// of absolute values
+    Normalize,
+    /// The baseline is shifted to minimize the change in slope of the layers, which is how
+    /// stream graphs are laid out
+    Wiggle,
+}
+
+/// Order is the direction values are sorted in
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    Ascending,
+    Descending,
+}
+
+/// Compare describes how to sort data objects: by the first field, and by the following ones
+/// whenever the previous fields are equal
+#[derive(Serialize)]
+pub struct Compare {
+    field: Vec<String>,
+    order: Vec<Order>,
+}
+
+impl Compare {
+    pub fn new() -> Compare {
+        Compare {
+            field: vec![],
+            order: vec![],
+        }
+    }
+    pub fn by(mut self, field: &str, order: Order) -> Compare {
+        self.field.push(String::from(field));
+        self.order.push(order);
+        self
+    }
+    pub fn is_empty(&self) -> bool {
+        self.field.is_empty()
+    }
+}
+
+/// WindowOp lists the operations a window transform can compute. The ranking operations
+/// (`RowNumber` to `CumeDist`) don't read a field, all others do.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowOp {
+    RowNumber,
+    Rank,
+    DenseRank,
+    PercentRank,
+    CumeDist,
+    Lag,
+    Lead,
+    FirstValue,
+    LastValue,
+    Count,
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+    Stdev,
+    Variance,
+}
+
+/// Window is a builder for a Vega window transform, which computes running or rolling values,
+/// such as a cumulative sum, a moving average or a rank, over sorted data objects.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::transform::{Order, Transform, Window, WindowOp};
+///
+///    // the running total of y, ordered by x
+///    let cumulative = Window::new()
+///        .sort("x", Order::Ascending)
+///        .op(WindowOp::Sum, Some("y"), "total");
+///    let transform = Transform::Window(cumulative);
+/// ```
+#[derive(Serialize)]
+pub struct Window {
+    #[serde(skip_serializing_if = "Compare::is_empty")]
+    sort: Compare,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    groupby: Vec<String>,
+    ops: Vec<WindowOp>,
+    fields: Vec<Option<String>>,
+    #[serde(rename = "as")]
+    output: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frame: Option<[Option<i32>; 2]>,
+}
+
+impl Window {
+    pub fn new() -> Window {
+        Window {
+            sort: Compare::new(),
+            groupby: vec![],
+            ops: vec![],
+            fields: vec![],
+            output: vec![],
+            frame: None,
+        }
+    }
+
+    /// Adds an operation to compute over the window. The result is written to the `output`
+    /// field of every data object.
+    pub fn op(mut self, op: WindowOp, field: Option<&str>, output: &str) -> Window {
+        self.ops.push(op);
+        self.fields.push(field.map(String::from));
+        self.output.push(String::from(output));
+        self
+    }
+
+    /// Adds a field to sort the data objects by before the window is moved over them
+    pub fn sort(mut self, field: &str, order: Order) -> Window {
+        self.sort = self.sort.by(field, order);
+        self
+    }
+
+    /// Computes the window separately for every group of data objects with the same value of
+    /// `field`
+    pub fn groupby(mut self, field: &str) -> Window {
+        self.groupby.push(String::from(field));
+        self
+    }
+
+    /// Sets how many data objects before and after the current one are part of its window,
+    /// where None means unbounded. Vega defaults to `(None, Some(0))`, i.e. all preceding
+    /// objects, which yields cumulative values. `(Some(-2), Some(2))` gives a rolling window
+    /// of five objects.
+    pub fn frame(mut self, before: Option<i32>, after: Option<i32>) -> Window {
+        self.frame = Some([before, after]);
+        self
+    }
+}
+
+/// Method is the kind of model a regression transform fits to the data
+#[derive(Clone, Copy)]
+pub enum Method {
+    /// y = a + b * x
+    Linear,
+    /// y = a + b * log(x)
+    Log,
+    /// y = a * e^(b * x)
+    Exp,
+    /// y = a * x^b
+    Pow,
+    /// y = a + b * x + c * x^2
+    Quad,
+    /// A polynomial of the given order
+    Poly(u32),
+}
+
+impl Method {
+    fn name(&self) -> &'static str {
+        match *self {
+            Method::Linear => "linear",
+            Method::Log => "log",
+            Method::Exp => "exp",
+            Method::Pow => "pow",
+            Method::Quad => "quad",
+            Method::Poly(_) => "poly",
+        }
+    }
+
+    /// Builds a Vega expression which renders the fitted equation, given a `datum` holding the
+    /// `coef` array emitted by a regression transform with `params` turned on
+    pub fn equation_expression(&self) -> String {
+        let coef = |i: u32| format!("format(datum.coef[{}], '.2f')", i);
+        match *self {
+            Method::Log => format!("'y = ' + {} + ' + ' + {} + ' log(x)'", coef(0), coef(1)),
+            Method::Exp => format!("'y = ' + {} + ' e^(' + {} + 'x)'", coef(0), coef(1)),
+            Method::Pow => format!("'y = ' + {} + ' x^' + {}", coef(0), coef(1)),
+            Method::Linear => Method::polynomial_expression(1),
+            Method::Quad => Method::polynomial_expression(2),
+            Method::Poly(order) => Method::polynomial_expression(order),
+        }
+    }
+
+    fn polynomial_expression(order: u32) -> String {
+        let terms: Vec<String> = (0..order + 1)
+            .rev()
+            .map(|i| match i {
+                0 => format!("format(datum.coef[{}], '.2f')", i),
+                1 => format!("format(datum.coef[{}], '.2f') + 'x'", i),
+                _ => format!("format(datum.coef[{}], '.2f') + 'x^{}'", i, i),
+            })
+            .collect();
+        format!("'y = ' + {}", terms.join(" + ' + ' + "))
+    }
+}
+
+/// Regression fits a model of y as a function of x, and either emits points along the fitted
+/// curve (named after the x and y fields), or the fitted coefficients in a `coef` field
+pub struct Regression {
+    x: String,
+    y: String,
+    method: Method,
+    groupby: Vec<String>,
+    params: bool,
+}
+
+impl Regression {
+    pub fn new(x: &str, y: &str, method: Method) -> Regression {
+        Regression {
+            x: String::from(x),
+            y: String::from(y),
+            method,
+            groupby: vec![],
+            params: false,
+        }
+    }
+
+    /// Fits a separate model for every group of data objects with the same value of `field`
+    pub fn groupby(mut self, field: &str) -> Regression {
+        self.groupby.push(String::from(field));
+        self
+    }
+
+    /// Emits the coefficients and the R² of the fitted model instead of points along the curve
+    pub fn params(mut self, params: bool) -> Regression {
+        self.params = params;
+        self
+    }
+}
+
+impl Serialize for Regression {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("regression", 6)?;
+        s.serialize_field("x", &self.x)?;
+        s.serialize_field("y", &self.y)?;
+        s.serialize_field("method", self.method.name())?;
+        if let Method::Poly(order) = self.method {
+            s.serialize_field("order", &order)?;
+        }
+        if !self.groupby.is_empty() {
+            s.serialize_field("groupby", &self.groupby)?;
+        }
+        if self.params {
+            s.serialize_field("params", &true)?;
+        }
+        s.end()
+    }
+}
+
+/// AggregateOp lists the operations used to summarize the values of a group of data objects
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateOp {
+    Count,
+    Valid,
+    Missing,
+    Distinct,
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+    Stdev,
+    Variance,
+}
+
+/// Aggregate is a builder for a Vega aggregate transform, which summarizes the data objects of
+/// every group into a single data object, holding the fields of `groupby` and the results of
+/// the operations.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::transform::{Aggregate, AggregateOp, Transform};
+///
+///    // the number of data objects and the mean of y for every x
+///    let summary = Aggregate::new()
+///        .groupby("x")
+///        .op(AggregateOp::Count, None, "count")
+///        .op(AggregateOp::Mean, Some("y"), "mean_y");
+///    let transform = Transform::Aggregate(summary);
+/// ```
+#[derive(Serialize)]
+pub struct Aggregate {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    groupby: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ops: Vec<AggregateOp>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<Option<String>>,
+    #[serde(rename = "as", skip_serializing_if = "Vec::is_empty")]
+    output: Vec<String>,
+}
+
+impl Aggregate {
+    /// Creates an aggregate transform which, without any operations, counts the data objects
+    /// of every group into the field `count`
+    pub fn new() -> Aggregate {
+        Aggregate {
+            groupby: vec![],
+            ops: vec![],
+            fields: vec![],
+            output: vec![],
+        }
+    }
+
+    /// Adds a field whose distinct values form the groups
+    pub fn groupby(mut self, field: &str) -> Aggregate {
+        self.groupby.push(String::from(field));
+        self
+    }
+
+    /// Adds an operation over `field` to compute for every group, writing the result to the
+    /// field `output`. `AggregateOp::Count` doesn't need a field.
+    pub fn op(mut self, op: AggregateOp, field: Option<&str>, output: &str) -> Aggregate {
+        self.ops.push(op);
+        self.fields.push(field.map(String::from));
+        self.output.push(String::from(output));
+        self
+    }
+}
+
+/// Bin is a builder for a Vega bin transform, which sorts the values of a field into bins of
+/// equal width, writing the start and the end of the bin of every data object to `bin0` and
+/// `bin1`. Together with an aggregate transform this yields a histogram.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::transform::{Bin, Transform};
+///
+///    let bins = Bin::new("y", 0.0, 100.0).maxbins(20);
+///    let transform = Transform::Bin(bins);
+/// ```
+#[derive(Serialize)]
+pub struct Bin {
+    field: String,
+    extent: [f64; 2],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maxbins: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    step: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nice: Option<bool>,
+}
+
+impl Bin {
+    /// Creates a bin transform for the values of `field` between `min` and `max`
+    pub fn new(field: &str, min: f64, max: f64) -> Bin {
+        Bin {
+            field: String::from(field),
+            extent: [min, max],
+            maxbins: None,
+            step: None,
+            nice: None,
+        }
+    }
+
+    /// Sets the largest number of bins, Vega defaults to 20
+    pub fn maxbins(mut self, maxbins: u32) -> Bin {
+        self.maxbins = Some(maxbins);
+        self
+    }
+
+    /// Sets the exact width of the bins, instead of deriving it from `maxbins`
+    pub fn step(mut self, step: f64) -> Bin {
+        self.step = Some(step);
+        self
+    }
+
+    /// Toggles whether the bins are moved to human-friendly boundaries, which Vega does by
+    /// default
+    pub fn nice(mut self, nice: bool) -> Bin {
+        self.nice = Some(nice);
+        self
+    }
+}
+
+/// Kde is a builder for a kernel density estimation transform. It emits a smooth estimate of
+/// the distribution of a field as `value` and `density` pairs, the building block for violin
+/// plots, ridgelines or smooth histograms.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::transform::{Kde, Transform};
+///
+///    let density = Kde::new("y").groupby("z").bandwidth(0.5).extent(0.0, 100.0);
+///    let transform = Transform::Kde(density);
+/// ```
+#[derive(Serialize)]
+pub struct Kde {
+    field: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    groupby: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bandwidth: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extent: Option<[f64; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    steps: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cumulative: Option<bool>,
+}
+
+impl Kde {
+    pub fn new(field: &str) -> Kde {
+        Kde {
+            field: String::from(field),
+            groupby: vec![],
+            bandwidth: None,
+            extent: None,
+            steps: None,
+            cumulative: None,
+        }
+    }
+
+    /// Estimates the density separately for every group with the same value of `field`
+    pub fn groupby(mut self, field: &str) -> Kde {
+        self.groupby.push(String::from(field));
+        self
+    }
+
+    /// Sets the bandwidth of the kernel. Vega estimates one from the data if it isn't set.
+    pub fn bandwidth(mut self, bandwidth: f64) -> Kde {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    /// Sets the range to estimate the density over, instead of the extent of the data
+    pub fn extent(mut self, min: f64, max: f64) -> Kde {
+        self.extent = Some([min, max]);
+        self
+    }
+
+    /// Sets the number of points the density is sampled at
+    pub fn steps(mut self, steps: u32) -> Kde {
+        self.steps = Some(steps);
+        self
+    }
+
+    /// Emits the cumulative distribution instead of the density
+    pub fn cumulative(mut self, cumulative: bool) -> Kde {
+        self.cumulative = Some(cumulative);
+        self
+    }
+}
+
+/// TreeMethod picks how a tree layout places its nodes
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeMethod {
+    /// A compact layout, where nodes of the same depth are drawn at the same level
+    Tidy,
+    /// A dendrogram, where all leaves are drawn at the same level
+    Cluster,
+}
+
+/// LinkOrient is the direction links between the nodes of a tree run in
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkOrient {
+    Horizontal,
+    Vertical,
+    Radial,
+}
+
+/// LinkShape is the shape of the links between the nodes of a tree
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkShape {
+    Line,
+    Arc,
+    Curve,
+    Diagonal,
+    Orthogonal,
+}
+
+/// Force is a builder for a Vega force transform, which lays out the items of a mark, e.g. the
+/// nodes of a network, by simulating forces pulling and pushing them. Unlike most transforms it
+/// runs on a mark through `Mark::transform`, and writes the position of every item to its `x`
+/// and `y`. The layout is computed up front, so the chart doesn't move once it is drawn.
+/// # Example:
+/// ```rust
+///    use gust::backend::elements::mark::ValueRef;
+///    use gust::backend::elements::transform::{Force, Transform};
+///
+///    let layout = Force::new()
+///        .center(ValueRef::signal("width / 2"), ValueRef::signal("height / 2"))
+///        .nbody(-30.0)
+///        .link("links", "datum.id", 30.0)
+///        .signal("force");
+///    let transform = Transform::Force(layout);
+/// ```
+#[derive(Serialize)]
+pub struct Force {
+    #[serde(rename = "static")]
+    static_layout: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iterations: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signal: Option<String>,
+    forces: Vec<ForceKind>,
+}
+
+/// ForceKind is one of the forces of a force simulation
+#[derive(Serialize)]
+#[serde(tag = "force", rename_all = "lowercase")]
+enum ForceKind {
+    Center { x: ValueRef, y: ValueRef },
+    Collide { radius: f64 },
+    Nbody { strength: f64 },
+    Link {
+        links: String,
+        id: String,
+        distance: f64,
+    },
+}
+
+impl Force {
+    pub fn new() -> Force {
+        Force {
+            static_layout: true,
+            iterations: None,
+            signal: None,
+            forces: vec![],
+        }
+    }
+
+    /// Sets the number of steps the simulation runs for, Vega defaults to 300
+    pub fn iterations(mut self, iterations: u32) -> Force {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    /// Names a signal which is updated once the layout is done, which other transforms can
+    /// `require` to run after it
+    pub fn signal(mut self, name: &str) -> Force {
+        self.signal = Some(String::from(name));
+        self
+    }
+
+    /// Pulls the items towards the given center
+    pub fn center(mut self, x: ValueRef, y: ValueRef) -> Force {
+        self.forces.push(ForceKind::Center { x, y });
+        self
+    }
+
+    /// Keeps the items at least `radius` pixels apart
+    pub fn collide(mut self, radius: f64) -> Force {
+        self.forces.push(ForceKind::Collide { radius });
+        self
+    }
+
+    /// Makes all items attract (positive strength) or repel (negative strength) each other, like
+    /// electrical charges
+    pub fn nbody(mut self, strength: f64) -> Force {
+        self.forces.push(ForceKind::Nbody { strength });
+        self
+    }
+
+    /// Pulls the items linked by the data set `links` towards `distance` pixels apart. The
+    /// `source` and `target` of every link refer to the field `id` of the items, e.g.
+    /// "datum.id", and are replaced by the items themselves once the layout runs.
+    pub fn link(mut self, links: &str, id: &str, distance: f64) -> Force {
+        self.forces.push(ForceKind::Link {
+            links: String::from(links),
+            id: String::from(id),
+            distance,
+        });
+        self
+    }
+}
+
+/// Transform represents one step of a Vega data transform pipeline. Transforms are run in the
+/// order they were added to a data set.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Transform {
+    /// Computes the start (y0) and end (y1) of `field` when stacking the values of each group
+    Stack {
+        groupby: Vec<String>,
+        sort: KeyVal,
+        field: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        offset: Option<StackOffset>,
+    },
+    /// Truncates the timestamps in `field` to the given units. The start and the end of the
+    /// resulting time span are written to the `unit0` and `unit1` fields.
+    Timeunit { field: String, units: Vec<TimeUnit> },
+    /// Computes running or rolling values over sorted data objects, see `Window`
+    Window(Window),
+    /// Fits a regression model to the data, see `Regression`
+    Regression(Regression),
+    /// Reshapes long data into wide data: every distinct value of `field` becomes a new field,
+    /// holding the (aggregated) `value` of the data objects in each group of `groupby`
+    Pivot {
+        field: String,
+        value: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        groupby: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        op: Option<AggregateOp>,
+    },
+    /// Estimates the density of a field, see `Kde`
+    Kde(Kde),
+    /// Reshapes wide data into long data: every data object is split into one object per field
+    /// in `fields`, holding the name of the field in `key` and its value in `value`
+    Fold { fields: Vec<String> },
+    /// Joins the data objects of another data set onto this one, where `key` of the other data
+    /// set matches one of `fields`. Either the `values` fields, or the whole matching object,
+    /// are copied over
+    Lookup {
+        from: String,
+        key: String,
+        fields: Vec<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        values: Vec<String>,
+    },
+    /// Fits a smooth curve through the data using locally-estimated regression. The bandwidth
+    /// is the fraction of the data used for each local fit, Vega defaults to 0.3.
+    Loess {
+        x: String,
+        y: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        groupby: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        bandwidth: Option<f64>,
+    },
+    /// Computes quantiles of `field`, emitting one data object with the fields `prob` and
+    /// `value` for each probability. The probabilities are either listed in `probs`, or spaced
+    /// `step` apart between 0 and 1.
+    Quantile {
+        field: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        groupby: Vec<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        probs: Vec<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        step: Option<f64>,
+    },
+    /// Computes the SVG path of the GeoJSON feature in each data object (or in `field`) as
+    /// drawn by `projection`, and writes it to the field `path`
+    Geopath {
+        projection: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+    },
+    /// Generates the meridians and parallels of a map as a single GeoJSON feature, spaced
+    /// `step` degrees (longitude, latitude) apart; Vega defaults to 10 degrees
+    Graticule {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        step: Option<[f64; 2]>,
+    },
+    /// Turns flat data objects into a hierarchy, where `parent_key` holds the `key` of the
+    /// parent of each data object. Exactly one data object, the root, must not have a parent.
+    Stratify {
+        key: String,
+        #[serde(rename = "parentKey")]
+        parent_key: String,
+    },
+    /// Lays out a hierarchy as a tree of the given size, writing the position of each node to
+    /// the fields `x` and `y` (or to the fields in `output`), its depth to `depth` and its
+    /// number of children to `children`
+    Tree {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        method: Option<TreeMethod>,
+        size: [ValueRef; 2],
+        #[serde(rename = "as", skip_serializing_if = "Vec::is_empty")]
+        output: Vec<String>,
+    },
+    /// Lays out a hierarchy as nested circles within an area of the given size, where the area
+    /// of each circle is proportional to the sum of `field` over its leaves. The center of each
+    /// circle is written to `x` and `y`, its radius to `r`, and its depth to `depth`.
+    Pack {
+        field: String,
+        size: [ValueRef; 2],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        padding: Option<f64>,
+    },
+    /// Lays out a hierarchy as adjacent rectangles within an area of the given size, where the
+    /// width of each rectangle is proportional to the sum of `field` over its leaves, and every
+    /// level of the hierarchy is drawn one row further down. The corners of each rectangle are
+    /// written to `x0`, `y0`, `x1` and `y1`, and its depth to `depth`.
+    Partition {
+        field: String,
+        size: [ValueRef; 2],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        padding: Option<f64>,
+    },
+    /// Generates one data object with a `source` and a `target` node for every edge of a tree
+    /// that has been laid out
+    Treelinks,
+    /// Computes the SVG path of a link from `source` to `target`, and writes it to the field
+    /// `path`. The ends are read from `source.x`, `source.y`, `target.x` and `target.y`, unless
+    /// other fields are set for them. With `require` set, the paths are only computed once the
+    /// given signal is, see `Transform::force_links`.
+    Linkpath {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        orient: Option<LinkOrient>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        shape: Option<LinkShape>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        require: Option<ValueRef>,
+        #[serde(rename = "sourceX", skip_serializing_if = "Option::is_none")]
+        source_x: Option<String>,
+        #[serde(rename = "sourceY", skip_serializing_if = "Option::is_none")]
+        source_y: Option<String>,
+        #[serde(rename = "targetX", skip_serializing_if = "Option::is_none")]
+        target_x: Option<String>,
+        #[serde(rename = "targetY", skip_serializing_if = "Option::is_none")]
+        target_y: Option<String>,
+    },
+    /// Lays out the items of a mark through a force simulation, see `Force`
+    Force(Force),
+    /// Computes the voronoi cell around each point at `x` and `y` within an area of the given
+    /// size, and writes its SVG path to the field `path`. Every position within the area is
+    /// covered by the cell of the point closest to it.
+    Voronoi {
+        x: String,
+        y: String,
+        size: [ValueRef; 2],
+    },
+    /// Lays the items of a text mark out as a word cloud within an area of the given size,
+    /// sizing every word by `font_size` (scaled into `font_size_range`) and rotating it by
+    /// `rotate` degrees. Words that don't fit into the area are left out.
+    Wordcloud {
+        size: [ValueRef; 2],
+        text: ValueRef,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        rotate: Option<ValueRef>,
+        font: String,
+        #[serde(rename = "fontSize")]
+        font_size: ValueRef,
+        #[serde(rename = "fontSizeRange")]
+        font_size_range: [f64; 2],
+        padding: f64,
+    },
+    /// Places the items of a text mark next to their x and y within an area of the given size,
+    /// trying the anchors in order (e.g. "top" places a label above its point) at the given
+    /// offsets in pixels. Labels that would overlap other labels in every position are hidden.
+    Label {
+        size: [ValueRef; 2],
+        anchor: Vec<String>,
+        offset: Vec<f64>,
+    },
+    /// Writes the result of the Vega expression `expr`, which may refer to the data object as
+    /// `datum`, to the field `output`
+    Formula {
+        expr: String,
+        #[serde(rename = "as")]
+        output: String,
+    },
+    /// Keeps only the data objects for which the Vega expression `expr` is true
+    Filter { expr: String },
+    /// Summarizes the data objects of every group, see `Aggregate`
+    Aggregate(Aggregate),
+    /// Sorts the values of a field into bins, see `Bin`
+    Bin(Bin),
+    /// Sorts the data objects, which changes the order they are drawn in
+    Collect { sort: Compare },
+    /// Lays the values of `field` out as the slices of a pie, writing the angles (in radians)
+    /// each slice starts and ends at to `startAngle` and `endAngle`. With `sort` set, the
+    /// slices are ordered by size instead of by the order of the data objects.
+    Pie {
+        field: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sort: Option<bool>,
+    },
+}
+
+impl Transform {
+    /// Creates a stack transform, stacking `field` within every group of `groupby`, ordered by
+    /// `sort`
+    pub fn stack(groupby: &str, sort: &str, field: &str) -> Transform {
+        Transform::Stack {
+            groupby: vec![String::from(groupby)],
+            sort: KeyVal::new("field", sort),
+            field: String::from(field),
+            offset: None,
+        }
+    }
+
+    /// Creates a linkpath transform drawing straight links between the items a force layout
+    /// has placed, once the layout has updated the signal `signal`
+    pub fn force_links(signal: &str) -> Transform {
+        Transform::Linkpath {
+            orient: None,
+            shape: Some(LinkShape::Line),
+            require: Some(ValueRef::signal(signal)),
+            source_x: Some(String::from("datum.source.x")),
+            source_y: Some(String::from("datum.source.y")),
+            target_x: Some(String::from("datum.target.x")),
+            target_y: Some(String::from("datum.target.y")),
+        }
+    }
+
+    /// Creates a pivot transform, turning the distinct values of `key` into fields which hold
+    /// the sum of `value` for every group of `groupby`
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::transform::Transform;
+    ///
+    ///    // {x, z, y} rows become one {x, "0": y, "1": y, ...} row for each x
+    ///    let wide = Transform::pivot("z", "y", &["x"]);
+    /// ```
+    pub fn pivot(key: &str, value: &str, groupby: &[&str]) -> Transform {
+        Transform::Pivot {
+            field: String::from(key),
+            value: String::from(value),
+            groupby: groupby.iter().map(|g| g.to_string()).collect(),
+            op: None,
+        }
+    }
+
+    /// Creates a fold transform over the given fields
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::transform::Transform;
+    ///
+    ///    // {region, q1, q2, q3} rows become three {region, key, value} rows each
+    ///    let long = Transform::fold(&["q1", "q2", "q3"]);
+    /// ```
+    pub fn fold(fields: &[&str]) -> Transform {
+        Transform::Fold { fields: fields.iter().map(|f| f.to_string()).collect() }
+    }
+
+    /// Creates a lookup transform, enriching every data object with the fields `values` of the
+    /// object of the data set `from` whose `key` equals this object's `field`
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::transform::Transform;
+    ///
+    ///    // copies the name of the region with the code in the category field
+    ///    let join = Transform::lookup("regions", "code", "category", &["name"]);
+    /// ```
+    pub fn lookup(from: &str, key: &str, field: &str, values: &[&str]) -> Transform {
+        Transform::Lookup {
+            from: String::from(from),
+            key: String::from(key),
+            fields: vec![String::from(field)],
+            values: values.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    /// Creates a loess transform, smoothing y over x with the given bandwidth
+    pub fn loess(x: &str, y: &str, bandwidth: f64) -> Transform {
+        Transform::Loess {
+            x: String::from(x),
+            y: String::from(y),
+            groupby: vec![],
+            bandwidth: Some(bandwidth),
+        }
+    }
+
+    /// Creates a quantile transform, computing the quantiles of `field` at probabilities `step`
+    /// apart, e.g. at 0.01, 0.02, .., 0.99 for a step of 0.01
+    pub fn quantile(field: &str, step: f64) -> Transform {
+        Transform::Quantile {
+            field: String::from(field),
+            groupby: vec![],
+            probs: vec![],
+            step: Some(step),
+        }
+    }
+
+    /// Creates a formula transform, storing the result of `expr` in the field `output`
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::transform::Transform;
+    ///
+    ///    // the value a standard normal distribution takes at each quantile
+    ///    let expected = Transform::formula("quantileNormal(datum.prob)", "expected");
+    /// ```
+    pub fn formula(expr: &str, output: &str) -> Transform {
+        Transform::Formula {
+            expr: String::from(expr),
+            output: String::from(output),
+        }
+    }
+
+    /// Creates a filter transform, keeping the data objects matching `expr`
+    pub fn filter(expr: &str) -> Transform {
+        Transform::Filter { expr: String::from(expr) }
+    }
+
+    /// Creates a collect transform, sorting the data objects by `field`
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::elements::transform::{Order, Transform};
+    ///
+    ///    let largest_first = Transform::collect("amount", Order::Descending);
+    /// ```
+    pub fn collect(field: &str, order: Order) -> Transform {
+        Transform::Collect { sort: Compare::new().by(field, order) }
+    }
+
+    /// Creates a geopath transform, drawing the GeoJSON features of a data set through the
+    /// projection with the given name
+    pub fn geopath(projection: &str) -> Transform {
+        Transform::Geopath {
+            projection: String::from(projection),
+            field: None,
+        }
+    }
+
+    /// Creates a timeunit transform which bins the timestamps (in milliseconds) of `field` by
+    /// the given unit. Binning by `TimeUnit::Hour` for example allows counting events per hour
+    /// of the day.
+    pub fn timeunit(field: &str, unit: TimeUnit) -> Transform {
+        Transform::Timeunit {
+            field: String::from(field),
+            units: vec![unit],
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Align, Baseline, Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::SCHEMA;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::Graphable;
+
+/// A funnel chart shows how a quantity shrinks from stage to stage, e.g. the visitors of a shop
+/// who go on to buy something. Every stage is a bar centered on the chart, as wide as its value.
+/// # Example:
+/// ```rust
+///    use gust::backend::funnel_chart::FunnelChart;
+///
+///    let mut f = FunnelChart::new();
+///    f.add_stage("Visits", 5000.0);
+///    f.add_stage("Cart", 1200.0);
+///    f.add_stage("Checkout", 450.0);
+///    f.add_stage("Purchase", 380.0);
+/// ```
+pub struct FunnelChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: String,
+    scales: Vec<Scale>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl FunnelChart {
+    pub fn new() -> FunnelChart {
+        let mut chart = FunnelChart {
+            identifier: String::from("funnel_chart"),
+            description: String::from("Funnel Chart"),
+            width: 500,
+            height: 300,
+            padding: 5,
+            data: vec![DataSet::from_values("table", vec![])],
+            color: String::from("steelblue"),
+            scales: vec![
+                Scale::new("x", ScaleType::Linear, Domain::field("table", "value"), "width"),
+                Scale::new("y", ScaleType::Band, Domain::field("table", "label"), "height"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[0].include_zero(true);
+        chart.scales[1].set_padding(0.1);
+        chart.update_marks();
+        chart
+    }
+
+    /// Rebuilds the bars of the stages and their labels
+    fn update_marks(&mut self) {
+        let bars = Mark::new(MarkType::Rect)
+            .from_data("table")
+            .enter("xc", ValueRef::signal("width / 2"))
+            .enter("width", ValueRef::scaled("x", "value"))
+            .enter("y", ValueRef::scaled("y", "label"))
+            .enter("height", ValueRef::signal("bandwidth('y')"))
+            .enter("fill", ValueRef::value(self.color.as_str()))
+            .enter("tooltip", ValueRef::signal("datum.label + ': ' + datum.value"));
+        let labels = Mark::text(ValueRef::signal("datum.label + ': ' + datum.value"))
+            .from_data("table")
+            .at(
+                ValueRef::signal("width / 2"),
+                ValueRef::scaled("y", "label").band(0.5),
+            )
+            .align(Align::Center)
+            .baseline(Baseline::Middle)
+            .right_to_left(self.extras.is_right_to_left())
+            .interactive(false);
+        self.marks = vec![bars, labels];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a stage below the stages added before it. The widest stage spans the whole width of
+    /// the chart.
+    pub fn add_stage(&mut self, label: &str, value: f64) {
+        let mut m = Map::new();
+        m.insert(String::from("label"), Value::from(label));
+        m.insert(String::from("value"), Value::from(value));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for FunnelChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("funnel_chart", 7)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for FunnelChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    fn set_color(&mut self, color: &str) {
+        self.color = String::from(color);
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.update_marks();
+    }
+}
@@ -0,0 +1,195 @@
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::{Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// A gantt chart lays tasks out on a timeline: every task is a row, with a bar spanning from
+/// its start to its end.
+/// # Example:
+/// ```rust
+///    use gust::backend::gantt_chart::GanttChart;
+///
+///    let mut g = GanttChart::new();
+///    // the timestamps are given in milliseconds
+///    g.add_task("Design", 1514764800000, 1515369600000);
+///    g.add_task("Build", 1515369600000, 1517443200000);
+/// ```
+pub struct GanttChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: String,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl GanttChart {
+    pub fn new() -> GanttChart {
+        let mut chart = GanttChart {
+            identifier: String::from("gantt_chart"),
+            description: String::from("Gantt Chart"),
+            width: 600,
+            height: 300,
+            padding: 5,
+            data: vec![DataSet::from_values("table", vec![])],
+            color: String::from("steelblue"),
+            scales: vec![
+                Scale::new(
+                    "x",
+                    ScaleType::Time,
+                    Domain::fields("table", &["start", "end"]),
+                    "width",
+                ),
+                Scale::new("y", ScaleType::Band, Domain::field("table", "task"), "height"),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[1].set_padding(0.2);
+        chart.update_marks();
+        chart
+    }
+
+    /// Rebuilds the bars of the tasks
+    fn update_marks(&mut self) {
+        self.marks = vec![
+            Mark::new(MarkType::Rect)
+                .from_data("table")
+                .enter("x", ValueRef::scaled("x", "start"))
+                .enter("x2", ValueRef::scaled("x", "end"))
+                .enter("y", ValueRef::scaled("y", "task"))
+                .enter("height", ValueRef::signal("bandwidth('y')"))
+                .enter("fill", ValueRef::value(self.color.as_str()))
+                .enter("cornerRadius", ValueRef::value(2))
+                .enter(
+                    "tooltip",
+                    ValueRef::signal(
+                        "datum.task + ': ' + timeFormat(datum.start, '%b %d') + ' - ' + \
+                         timeFormat(datum.end, '%b %d')",
+                    ),
+                ),
+        ];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a task running from `start` to `end`, both timestamps in milliseconds. Tasks are
+    /// listed from top to bottom in the order they were added.
+    pub fn add_task(&mut self, name: &str, start: i64, end: i64) {
+        let mut m = Map::new();
+        m.insert(String::from("task"), Value::from(name));
+        m.insert(String::from("start"), Value::from(start));
+        m.insert(String::from("end"), Value::from(end));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for GanttChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("gantt_chart", 9)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for GanttChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    fn set_color(&mut self, color: &str) {
+        self.color = String::from(color);
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for GanttChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
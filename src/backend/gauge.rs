@@ -0,0 +1,250 @@
+use backend::data;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Align, Baseline, Mark, ValueRef};
+use backend::elements::scale::{Domain, Range, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::SCHEMA;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::Graphable;
+
+/// The center of the gauge, low enough for the half circle above it to fit the chart
+const CENTER_Y: &'static str = "height * 0.8";
+/// The radius of the gauge, including its ring of color bands
+const OUTER_RADIUS: &'static str = "min(width / 2, height * 0.8)";
+
+/// A gauge shows a single value as an arc along a half circle, which runs from the minimum on
+/// the left to the maximum on the right. Thresholds change the color of the arc once the value
+/// reaches them, color bands mark ranges of values on a thin ring around it.
+/// # Example:
+/// ```rust
+///    use gust::backend::gauge::Gauge;
+///
+///    let mut g = Gauge::new();
+///    g.set_range(0.0, 120.0);
+///    g.set_value(87.0);
+///    g.add_threshold(80.0, "orange");
+///    g.add_threshold(100.0, "red");
+///    g.add_band(100.0, 120.0, "red");
+/// ```
+pub struct Gauge {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: String,
+    thresholds: Vec<(f64, String)>,
+    scales: Vec<Scale>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl Gauge {
+    pub fn new() -> Gauge {
+        let mut chart = Gauge {
+            identifier: String::from("gauge"),
+            description: String::from("Gauge"),
+            width: 400,
+            height: 250,
+            padding: 5,
+            data: vec![
+                DataSet::from_values("table", vec![]),
+                DataSet::from_values("bands", vec![]),
+            ],
+            color: String::from("steelblue"),
+            thresholds: vec![],
+            scales: vec![Gauge::angle(0.0, 100.0)],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.set_value(0.0);
+        chart.update_colors();
+        chart.update_marks();
+        chart
+    }
+
+    /// The scale from values onto angles along the upper half circle, in radians
+    fn angle(min: f64, max: f64) -> Scale {
+        let mut angle = Scale::with_range(
+            "angle",
+            ScaleType::Linear,
+            Domain::Values(vec![min, max]),
+            Range::signal("[-PI / 2, PI / 2]"),
+        );
+        angle.set_clamp(true);
+        angle
+    }
+
+    /// Rebuilds the threshold scale coloring the value, after the thresholds or the color
+    /// changed
+    fn update_colors(&mut self) {
+        let mut colors = vec![self.color.as_str()];
+        colors.extend(self.thresholds.iter().map(|t| t.1.as_str()));
+        let color = Scale::threshold(
+            "color",
+            self.thresholds.iter().map(|t| t.0).collect(),
+            Range::values(&colors),
+        );
+        if self.scales.len() > 1 {
+            self.scales[1] = color;
+        } else {
+            self.scales.push(color);
+        }
+    }
+
+    /// Rebuilds the track, the color bands, the arc of the value and its label
+    fn update_marks(&mut self) {
+        let center = || (ValueRef::signal("width / 2"), ValueRef::signal(CENTER_Y));
+        let radius = |fraction: f64| ValueRef::signal(&format!("{} * {}", OUTER_RADIUS, fraction));
+        let (x, y) = center();
+        let track = Mark::arc(ValueRef::signal("-PI / 2"), ValueRef::signal("PI / 2"))
+            .at(x, y)
+            .radius(radius(0.6), radius(0.9))
+            .enter("fill", ValueRef::value("#e6e6e6"))
+            .interactive(false);
+        let (x, y) = center();
+        let bands = Mark::arc(ValueRef::scaled("angle", "from"), ValueRef::scaled("angle", "to"))
+            .from_data("bands")
+            .at(x, y)
+            .radius(radius(0.93), radius(1.0))
+            .enter("fill", ValueRef::field("color"))
+            .enter("tooltip", ValueRef::signal("datum.from + ' - ' + datum.to"));
+        let (x, y) = center();
+        let value = Mark::arc(ValueRef::signal("-PI / 2"), ValueRef::scaled("angle", "value"))
+            .from_data("table")
+            .at(x, y)
+            .radius(radius(0.6), radius(0.9))
+            .enter("fill", ValueRef::scaled("color", "value"))
+            .enter("tooltip", ValueRef::field("value"));
+        let (x, y) = center();
+        let label = Mark::text(ValueRef::field("value"))
+            .from_data("table")
+            .at(x, y)
+            .align(Align::Center)
+            .baseline(Baseline::Bottom)
+            .enter("fontSize", ValueRef::signal(&format!("{} * 0.25", OUTER_RADIUS)))
+            .right_to_left(self.extras.is_right_to_left())
+            .interactive(false);
+        self.marks = vec![track, bands, value, label];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Sets the value shown by the gauge. Values outside of the range of the gauge are drawn
+    /// at its ends.
+    pub fn set_value(&mut self, value: f64) {
+        let mut m = Map::new();
+        m.insert(String::from("value"), Value::from(value));
+        *self.data[0].values_mut() = vec![Value::Object(m)];
+    }
+
+    /// Sets the values at the left and the right end of the gauge, 0 and 100 by default
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        self.scales[0] = Gauge::angle(min, max);
+    }
+
+    /// Draws the arc of the value in `color` once the value reaches `threshold`, until it
+    /// reaches the next higher threshold. Below all thresholds the color of the chart is used.
+    /// A threshold that isn't a number is ignored.
+    pub fn add_threshold(&mut self, threshold: f64, color: &str) {
+        if threshold.is_nan() {
+            return;
+        }
+        self.thresholds.push((threshold, String::from(color)));
+        self.thresholds.sort_by(|a, b| data::nan_last(a.0, b.0));
+        self.update_colors();
+    }
+
+    /// Marks the values from `from` to `to` on a thin ring around the gauge, e.g. to show a
+    /// critical range
+    pub fn add_band(&mut self, from: f64, to: f64, color: &str) {
+        let mut m = Map::new();
+        m.insert(String::from("from"), Value::from(from));
+        m.insert(String::from("to"), Value::from(to));
+        m.insert(String::from("color"), Value::from(color));
+        self.data[1].add_value(Value::Object(m));
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for Gauge {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("gauge", 7)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for Gauge {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color sets the color of the arc below the first threshold
+    fn set_color(&mut self, color: &str) {
+        self.color = String::from(color);
+        self.update_colors();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.update_marks();
+    }
+}
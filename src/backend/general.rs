@@ -1,8 +1,75 @@
 
 
+use serde_json::Value;
+
 /// The FileType enum is used to keep track of the different file formats Gust can output to, as well
 /// specifying the type of file you want to output to, when actually building a visualization
 pub enum FileType {
     JSON,
     HTML,
 }
+
+/// DataFormat picks where the data of a chart ends up when it is rendered
+pub enum DataFormat {
+    /// The data is embedded into the specification of the chart
+    Inline,
+    /// Each data set is written to a json file next to the chart, which the specification
+    /// refers to by url
+    JSON,
+    /// Like JSON, but as csv files, which are smaller but lose any fields that hold objects
+    CSV,
+}
+
+/// Numeric is implemented by the primitive number types, so that charts accept integers and
+/// floating point values alike. Values are stored, and emitted into the specification, as f64.
+pub trait Numeric: Copy {
+    fn as_f64(self) -> f64;
+}
+
+macro_rules! numeric {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+numeric!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// Category is implemented by the integer and text types, so that charts accept both ids and
+/// names for their categories and series. Values are emitted into the specification as they
+/// are, as json numbers or strings.
+pub trait Category {
+    fn into_value(self) -> Value;
+}
+
+macro_rules! category {
+    ($as:ty: $($t:ty),*) => {
+        $(
+            impl Category for $t {
+                fn into_value(self) -> Value {
+                    Value::from(self as $as)
+                }
+            }
+        )*
+    };
+}
+
+category!(i64: i8, i16, i32, i64, isize);
+category!(u64: u8, u16, u32, u64, usize);
+
+impl<'a> Category for &'a str {
+    fn into_value(self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl Category for String {
+    fn into_value(self) -> Value {
+        Value::from(self)
+    }
+}
@@ -0,0 +1,225 @@
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Range, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::{Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// A grouped bar chart draws the bars of every series of a category side by side, instead of
+/// stacking them. Each category becomes a group mark, which splits its band of the x axis
+/// among the series through a band scale of its own.
+/// # Example:
+/// ```rust
+///    use gust::backend::grouped_bar_chart::GroupedBarChart;
+///
+///    let mut g = GroupedBarChart::new();
+///    g.add_data("2017", "North", 12.0);
+///    g.add_data("2017", "South", 9.5);
+///    g.add_data("2018", "North", 14.0);
+///    g.add_data("2018", "South", 11.0);
+/// ```
+pub struct GroupedBarChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: Option<String>,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl GroupedBarChart {
+    pub fn new() -> GroupedBarChart {
+        let mut chart = GroupedBarChart {
+            identifier: String::from("grouped_bar_chart"),
+            description: String::from("Grouped Bar Chart"),
+            width: 500,
+            height: 300,
+            padding: 5,
+            data: vec![DataSet::from_values("table", vec![])],
+            color: None,
+            scales: vec![
+                Scale::new("x", ScaleType::Band, Domain::field("table", "category"), "width"),
+                Scale::new("y", ScaleType::Linear, Domain::field("table", "value"), "height"),
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "series"),
+                    "category",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[0].set_padding(0.2);
+        chart.update_marks();
+        chart
+    }
+
+    /// Rebuilds the groups, one per category, each holding a bar per series
+    fn update_marks(&mut self) {
+        let fill = match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", "series"),
+        };
+        let series = Scale::with_range(
+            "series",
+            ScaleType::Band,
+            Domain::field("category", "series"),
+            Range::signal("[0, bandwidth('x')]"),
+        );
+        let bars = Mark::new(MarkType::Rect)
+            .from_data("category")
+            .enter("x", ValueRef::scaled("series", "series"))
+            .enter("width", ValueRef::signal("bandwidth('series')"))
+            .enter("y", ValueRef::scaled("y", "value"))
+            .enter("y2", ValueRef::scaled_value("y", 0))
+            .enter("fill", fill)
+            .enter(
+                "tooltip",
+                ValueRef::signal("datum.category + ', ' + datum.series + ': ' + datum.value"),
+            );
+        self.marks = vec![
+            Mark::new(MarkType::Group)
+                .facet("category", "table", "category")
+                .enter("x", ValueRef::scaled("x", "category"))
+                .enter("width", ValueRef::signal("bandwidth('x')"))
+                .scale(series)
+                .mark(bars),
+        ];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds the value of one series within a category. Categories are laid out along the x
+    /// axis in the order they first appear, and so are the series within every category.
+    pub fn add_data(&mut self, category: &str, series: &str, value: f64) {
+        let mut m = Map::new();
+        m.insert(String::from("category"), Value::from(category));
+        m.insert(String::from("series"), Value::from(series));
+        m.insert(String::from("value"), Value::from(value));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Adds every (category, series, value) triple of the iterator, as `add_data` would
+    pub fn add_data_iter<I, C, S>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (C, S, f64)>,
+        C: AsRef<str>,
+        S: AsRef<str>,
+    {
+        let iter = iter.into_iter();
+        self.data[0].values_mut().reserve(iter.size_hint().0);
+        for (category, series, value) in iter {
+            self.add_data(category.as_ref(), series.as_ref(), value);
+        }
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for GroupedBarChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("grouped_bar_chart", 8)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for GroupedBarChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color gives every series of the chart the same color
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for GroupedBarChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
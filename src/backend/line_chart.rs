@@ -1,7 +1,29 @@
 
 use backend::elements::line_chart::*;
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Align, Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Range, Scale, ScaleType};
+use backend::elements::signal::Signal;
+use backend::elements::transform::{Method, Transform};
+use backend::elements::overlay::{self, ErrorBar, Plot, ValueLabels};
+use backend::elements::general::{Interpolation, Orientation, SCHEMA};
+use backend::general::Numeric;
 use serde::ser::{Serialize, SerializeStruct, Serializer};
-use backend::traits::Graphable;
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// The main plot of a line chart, which overlays are drawn against
+const PLOT: Plot = Plot {
+    data: "table",
+    x: "x",
+    y: "y",
+    series: Some("z"),
+    x_scale: "x",
+    y_scale: "y",
+    color_scale: Some("color"),
+};
 
 pub struct LineChart {
     identifier: String,
@@ -9,11 +31,14 @@ pub struct LineChart {
     width: u32,
     height: u32,
     padding: u32,
-    signals: Vec<LineChartSignal>,
+    signals: Vec<Signal>,
     data: Vec<LineChartData>,
-    scales: Vec<LineChartScale>,
-    axes: Vec<LineChartAxis>,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
     marks: Vec<LineChartMark>,
+    error_bars: bool,
+    secondary: bool,
+    extras: Extras,
 }
 
 impl LineChart {
@@ -24,15 +49,26 @@ impl LineChart {
             width: 500,
             height: 300,
             padding: 5,
-            signals: vec![LineChartSignal::new()],
+            signals: vec![Signal::interpolation(Interpolation::Linear)],
             data: vec![LineChartData::new()],
             scales: vec![
-                LineChartScale::new_xscale(),
-                LineChartScale::new_yscale(),
-                LineChartScale::new_ordinal_scale(),
+                Scale::new("x", ScaleType::Point, Domain::field("table", "x"), "width"),
+                Scale::new("y", ScaleType::Linear, Domain::field("table", "y"), "height"),
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "z"),
+                    "category",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
             ],
-            axes: vec![LineChartAxis::new_xaxis(), LineChartAxis::new_yaxis()],
             marks: vec![LineChartMark::new()],
+            error_bars: false,
+            secondary: false,
+            extras: Extras::new(),
         }
 
     }
@@ -52,14 +88,163 @@ impl LineChart {
     }
 
     /// To add data to a line chart, the data must be formatted in the following fashion:
-    /// {Integer, Integer, Integer }.
+    /// {Number, Number, Integer }.
     ///
     /// The first two entries represent the x and y coordinates of the point
     /// which you're adding to the graph, and the third coordinate is the series identifier.
     /// For example, if you want to add 2 different lines on a single set of axes, then you can
     /// set the z of the first series to 0, and set the z of the second series to 1.
-    pub fn add_data(&mut self, x: i64, y: i64, z: i64) {
-        self.data[0].add_data(x, y, z);
+    pub fn add_data<X: Numeric, Y: Numeric>(&mut self, x: X, y: Y, z: i64) {
+        self.data[0].add_data(x.as_f64(), y.as_f64(), z);
+    }
+
+    /// Adds every (x, y, z) point of the iterator, as `add_data` would
+    pub fn add_data_iter<I, X, Y>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (X, Y, i64)>,
+        X: Numeric,
+        Y: Numeric,
+    {
+        let iter = iter.into_iter();
+        self.data[0].reserve(iter.size_hint().0);
+        for (x, y, z) in iter {
+            self.add_data(x, y, z);
+        }
+    }
+
+    /// Leaves a gap in the line of series z at x, e.g. where a measurement is missing. The
+    /// line stops at the point before the gap and starts again at the point after it.
+    pub fn add_gap<X: Numeric>(&mut self, x: X, z: i64) {
+        self.data[0].add_gap(x.as_f64(), z);
+    }
+
+    /// Reduces every line to at most `n_points` of the points added so far, picking the ones
+    /// that preserve its shape best, which keeps the specification of charts with very long
+    /// series small. Gaps are kept, and points added afterwards are not reduced.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    c.add_data_iter((0..100_000).map(|i| (i, (i as f64 / 1000.0).sin(), 0)));
+    ///    c.downsample_to(1000);
+    /// ```
+    pub fn downsample_to(&mut self, n_points: usize) {
+        self.data[0].downsample(n_points);
+    }
+
+    /// Adds a point like `add_data`, with an error bar showing the uncertainty of its y value
+    pub fn add_data_with_error<X, Y>(&mut self, x: X, y: Y, z: i64, error: ErrorBar)
+    where
+        X: Numeric,
+        Y: Numeric,
+    {
+        let (x, y) = (x.as_f64(), y.as_f64());
+        let (lo, hi) = error.bounds(y);
+        self.data[0].add_data_with_error(x, y, z, lo, hi);
+        if !self.error_bars {
+            self.error_bars = true;
+            overlay::add_error_bars(&mut self.extras, &PLOT);
+        }
+    }
+
+    /// Adds a series named `name` which is plotted against a second y scale, shown on an axis
+    /// on the right of the chart. This lets series of very different magnitudes, like a
+    /// temperature and a rainfall, share the x axis. The secondary series are drawn dashed and
+    /// colored by their name.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    c.add_data_iter(vec![(1, 21.5, 0), (2, 23.0, 0), (3, 19.5, 0)]);
+    ///    c.add_series_secondary("rainfall", vec![(1, 120), (2, 80), (3, 310)]);
+    /// ```
+    pub fn add_series_secondary<I, X, Y>(&mut self, name: &str, points: I)
+    where
+        I: IntoIterator<Item = (X, Y)>,
+        X: Numeric,
+        Y: Numeric,
+    {
+        if !self.secondary {
+            self.secondary = true;
+            self.add_secondary_axis();
+        }
+        if let Some(data) = self.extras.data_set_mut("secondary") {
+            for (x, y) in points {
+                let mut m = Map::new();
+                m.insert(String::from("x"), Value::from(x.as_f64()));
+                m.insert(String::from("y"), Value::from(y.as_f64()));
+                m.insert(String::from("series"), Value::from(name));
+                data.add_value(Value::Object(m));
+            }
+        }
+    }
+
+    /// The axis on the right of the chart, once a secondary series was added
+    pub fn secondary_axis_mut(&mut self) -> Option<&mut Axis> {
+        self.axes.get_mut(2)
+    }
+
+    /// Adds the data set, scales, axis and lines of the secondary series. The x scale is
+    /// extended over the secondary data as well, unless its domain was set by hand.
+    fn add_secondary_axis(&mut self) {
+        self.extras.add_data_set(DataSet::from_values("secondary", vec![]));
+        if self.scales[0].domain_field() == Some("x") {
+            self.scales[0].set_domain(Domain::union(&[("table", "x"), ("secondary", "x")]));
+        }
+        self.scales.push(Scale::new(
+            "y2",
+            ScaleType::Linear,
+            Domain::field("secondary", "y"),
+            "height",
+        ));
+        self.scales.push(Scale::with_range(
+            "secondary_color",
+            ScaleType::Ordinal,
+            Domain::field("secondary", "series"),
+            Range::scheme("dark2"),
+        ));
+        self.axes.push(Axis::new(Orientation::Right, "y2"));
+        self.mirror_secondary_axis();
+        let line = Mark::new(MarkType::Line)
+            .from_data("secondary_series")
+            .enter("x", ValueRef::scaled("x", "x"))
+            .enter("y", ValueRef::scaled("y2", "y"))
+            .enter("stroke", ValueRef::scaled("secondary_color", "series"))
+            .enter("strokeWidth", ValueRef::value(2))
+            .dash(&[6.0, 3.0]);
+        self.extras.add_mark(
+            Mark::new(MarkType::Group)
+                .facet("secondary_series", "secondary", "series")
+                .mark(line),
+        );
+    }
+
+    /// Places the secondary axis opposite the y axis, i.e. on the left of charts laid out
+    /// right-to-left
+    fn mirror_secondary_axis(&mut self) {
+        let rtl = self.extras.is_right_to_left();
+        if let Some(axis) = self.axes.get_mut(2) {
+            if rtl {
+                axis.set_orient(Orientation::Left);
+                axis.set_label_align(Align::Right);
+            } else {
+                axis.set_orient(Orientation::Right);
+                axis.clear_label_align();
+            }
+        }
+    }
+
+    /// Sets how the lines are drawn in between the data points, linear by default. The
+    /// interpolation can still be changed through the menu below the chart.
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.signals[0] = Signal::interpolation(interpolation);
+    }
+
+    /// Adds a point to a chart with a single line, which is the series 0
+    pub fn add_point<X: Numeric, Y: Numeric>(&mut self, x: X, y: Y) {
+        self.add_data(x, y, 0);
     }
 
 
@@ -74,6 +259,60 @@ impl LineChart {
     pub fn set_padding(&mut self, padding: u32) {
         self.padding = padding;
     }
+
+    /// Overlays a dashed trend line on every series, fitted with the given regression method.
+    /// With `label` set, the fitted equations are printed in the corner of the chart.
+    ///
+    /// The curve of the fit is sampled in between the x values of the data, so the x scale is
+    /// turned into a linear one.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///    use gust::backend::elements::transform::Method;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    for i in 0..20 {
+    ///        c.add_data(i, i * i, 0);
+    ///    }
+    ///    c.add_trendline(Method::Poly(2), true);
+    /// ```
+    pub fn add_trendline(&mut self, method: Method, label: bool) {
+        self.scales[0].set_scale_type(ScaleType::Linear);
+        overlay::add_trendline(&mut self.extras, &PLOT, method, label);
+    }
+
+    /// Overlays a loess curve on every series. The bandwidth is the fraction of the points used
+    /// for each local fit, smaller values follow the data more closely. Like a trend line, the
+    /// curve is sampled in between x values, so the x scale is turned into a linear one.
+    pub fn add_loess(&mut self, bandwidth: f64) {
+        self.scales[0].set_scale_type(ScaleType::Linear);
+        overlay::add_loess(&mut self.extras, &PLOT, bandwidth);
+    }
+
+    /// Overlays a smoothed line on every series, the mean of the last `window` points
+    pub fn add_moving_average(&mut self, window: u32) {
+        overlay::add_moving_average(&mut self.extras, &PLOT, window);
+    }
+
+    /// Prints the y value of every point next to it, e.g. `show_values(LabelPosition::OutsideEnd)`
+    /// for labels above the points. On crowded charts, `ValueLabels::avoid_overlap` keeps the
+    /// labels readable.
+    pub fn show_values<L: Into<ValueLabels>>(&mut self, labels: L) {
+        overlay::add_value_labels(&mut self.extras, &PLOT, &labels.into());
+    }
+
+    /// Shows a tooltip with the values of the point closest to the pointer, wherever it is on
+    /// the chart. This should be added last, so its hover targets are above all other marks.
+    pub fn add_voronoi(&mut self) {
+        overlay::add_voronoi(&mut self.extras, &PLOT);
+    }
+
+    /// Lets the points of the chart be stepped through with the arrow keys, highlighting the
+    /// current one, and makes Enter act like a click on it, see
+    /// `overlay::add_keyboard_navigation`
+    pub fn add_keyboard_navigation(&mut self) {
+        overlay::add_keyboard_navigation(&mut self.extras, &PLOT);
+    }
 }
 impl Serialize for LineChart {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -81,18 +320,15 @@ impl Serialize for LineChart {
         S: Serializer,
     {
         let mut s = serializer.serialize_struct("line_chart", 10)?;
-        s.serialize_field(
-            "$schema",
-            "https://vega.github.io/schema/vega/v3.0.json",
-        )?;
+        s.serialize_field("$schema", SCHEMA)?;
         s.serialize_field("width", &self.width)?;
         s.serialize_field("height", &self.height)?;
         s.serialize_field("padding", &self.padding)?;
-        s.serialize_field("signals", &self.signals)?;
-        s.serialize_field("data", &self.data)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
         s.serialize_field("scales", &self.scales)?;
         s.serialize_field("axes", &self.axes)?;
-        s.serialize_field("marks", &self.marks)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields_with(&mut s, &self.signals)?;
 
         s.end()
     }
@@ -104,4 +340,50 @@ impl Graphable for LineChart {
     fn get_identifier(&self) -> &str {
         &self.identifier
     }
+    /// set_color draws every series of the chart in the same color
+    fn set_color(&mut self, color: &str) {
+        self.marks[0].set_color(color);
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+        self.mirror_secondary_axis();
+    }
+}
+
+impl Cartesian for LineChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
 }
@@ -0,0 +1,213 @@
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Mark, SymbolShape, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::{Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::general::Numeric;
+use backend::traits::{Cartesian, Graphable};
+
+/// A lollipop chart is a lighter alternative to a bar chart: every category is drawn as a thin
+/// stick from zero up to its amount, topped with a dot. It takes its data just like `BarChart`.
+/// # Example:
+/// ```rust
+///    use gust::backend::lollipop_chart::LollipopChart;
+///
+///    let mut l = LollipopChart::new();
+///    l.add_data(String::from("A"), 28);
+///    l.add_data(String::from("B"), 55);
+///    l.add_data(String::from("C"), 43);
+///    l.set_dot_size(150.0);
+/// ```
+pub struct LollipopChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: String,
+    dot_size: f64,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl LollipopChart {
+    pub fn new() -> LollipopChart {
+        let mut chart = LollipopChart {
+            identifier: String::from("lollipop_chart"),
+            description: String::from("Lollipop Chart"),
+            width: 500,
+            height: 300,
+            padding: 5,
+            data: vec![DataSet::from_values("table", vec![])],
+            color: String::from("steelblue"),
+            dot_size: 100.0,
+            scales: vec![
+                Scale::new("x", ScaleType::Band, Domain::field("table", "category"), "width"),
+                Scale::new("y", ScaleType::Linear, Domain::field("table", "amount"), "height"),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[0].set_padding(0.05);
+        chart.update_marks();
+        chart
+    }
+
+    /// Rebuilds the sticks and the dots on top of them, which share the scales of the chart
+    fn update_marks(&mut self) {
+        let x = || ValueRef::scaled("x", "category").band(0.5);
+        let top = ValueRef::scaled("y", "amount");
+        let sticks = Mark::rule(x(), top, x(), ValueRef::scaled_value("y", 0))
+            .from_data("table")
+            .stroke(self.color.as_str(), 2.0)
+            .interactive(false);
+        let dots = Mark::symbol(SymbolShape::Circle, self.dot_size)
+            .from_data("table")
+            .at(x(), ValueRef::scaled("y", "amount"))
+            .enter("fill", ValueRef::value(self.color.as_str()))
+            .enter("tooltip", ValueRef::signal("datum.category + ': ' + datum.amount"));
+        self.marks = vec![sticks, dots];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a lollipop for the category with the given amount, like `BarChart::add_data`
+    pub fn add_data<T: Numeric>(&mut self, category: String, amount: T) {
+        let mut m = Map::new();
+        m.insert(String::from("category"), Value::from(category));
+        m.insert(String::from("amount"), Value::from(amount.as_f64()));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Adds a lollipop for every (category, amount) pair, like `BarChart::add_data_iter`
+    pub fn add_data_iter<I, T>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (String, T)>,
+        T: Numeric,
+    {
+        let iter = iter.into_iter();
+        self.data[0].values_mut().reserve(iter.size_hint().0);
+        for (category, amount) in iter {
+            self.add_data(category, amount);
+        }
+    }
+
+    /// Sets the area of the dots in square pixels, 100 by default
+    pub fn set_dot_size(&mut self, size: f64) {
+        self.dot_size = size;
+        self.update_marks();
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+
+    /// clear_data empties out all of the data entries
+    /// that currently exist in the given graph
+    pub fn clear_data(&mut self) {
+        self.data[0].values_mut().clear();
+    }
+}
+impl Serialize for LollipopChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("lollipop_chart", 8)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for LollipopChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color sets the color of the sticks and their dots
+    fn set_color(&mut self, color: &str) {
+        self.color = String::from(color);
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for LollipopChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
@@ -9,7 +9,38 @@ The backend module handles the actual Rust structs that are used to model differ
 pub mod elements;
 pub mod bar_chart;
 pub mod stacked_bar_chart;
+pub mod grouped_bar_chart;
+pub mod lollipop_chart;
 pub mod line_chart;
+pub mod multi_line_chart;
 pub mod traits;
 pub mod general;
+pub mod data;
 pub mod area_chart;
+pub mod stacked_area_chart;
+pub mod qq_plot;
+pub mod scatter_plot;
+pub mod pie_chart;
+pub mod gauge;
+pub mod candlestick_chart;
+pub mod gantt_chart;
+pub mod radar_chart;
+pub mod sankey_chart;
+pub mod network_graph;
+pub mod word_cloud;
+pub mod funnel_chart;
+pub mod violin_plot;
+pub mod ridgeline_plot;
+pub mod tree_chart;
+pub mod circle_packing;
+pub mod icicle_chart;
+pub mod config;
+pub mod spec;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "chrono")]
+pub mod time;
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+#[cfg(feature = "polars")]
+pub mod polars;
@@ -0,0 +1,208 @@
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::legend::Legend;
+use backend::elements::mark::{Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::{Interpolation, Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// A multi line chart draws one line for every named series, each in its own color, with a
+/// legend listing the names of the series.
+/// # Example:
+/// ```rust
+///    use gust::backend::multi_line_chart::MultiLineChart;
+///
+///    let mut c = MultiLineChart::new();
+///    c.add_series("Berlin", &[(1.0, 0.5), (2.0, 2.1), (3.0, 7.4)]);
+///    c.add_series("Lisbon", &[(1.0, 11.2), (2.0, 12.0), (3.0, 13.8)]);
+/// ```
+pub struct MultiLineChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: Option<String>,
+    interpolation: Interpolation,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl MultiLineChart {
+    pub fn new() -> MultiLineChart {
+        let mut chart = MultiLineChart {
+            identifier: String::from("multi_line_chart"),
+            description: String::from("Multi Line Chart"),
+            width: 500,
+            height: 300,
+            padding: 5,
+            data: vec![DataSet::from_values("table", vec![])],
+            color: None,
+            interpolation: Interpolation::Linear,
+            scales: vec![
+                Scale::new("x", ScaleType::Linear, Domain::field("table", "x"), "width"),
+                Scale::new("y", ScaleType::Linear, Domain::field("table", "y"), "height"),
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "series"),
+                    "category",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[0].include_zero(false);
+        chart.extras.add_legend(Legend::stroke("color"));
+        chart.update_marks();
+        chart
+    }
+
+    /// Rebuilds the lines, one per series
+    fn update_marks(&mut self) {
+        let stroke = match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", "series"),
+        };
+        let line = Mark::new(MarkType::Line)
+            .from_data("series")
+            .enter("x", ValueRef::scaled("x", "x"))
+            .enter("y", ValueRef::scaled("y", "y"))
+            .enter("stroke", stroke)
+            .enter("strokeWidth", ValueRef::value(2))
+            .enter("interpolate", ValueRef::value(self.interpolation.name()));
+        self.marks = vec![
+            Mark::new(MarkType::Group)
+                .facet("series", "table", "series")
+                .mark(line),
+        ];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a line through the given (x, y) points, which are connected in the order they are
+    /// given. The name of the series is shown in the legend. Adding points to a series that
+    /// already exists extends its line.
+    pub fn add_series(&mut self, name: &str, points: &[(f64, f64)]) {
+        for &(x, y) in points {
+            let mut m = Map::new();
+            m.insert(String::from("series"), Value::from(name));
+            m.insert(String::from("x"), Value::from(x));
+            m.insert(String::from("y"), Value::from(y));
+            self.data[0].add_value(Value::Object(m));
+        }
+    }
+
+    /// Sets how the lines are drawn in between the data points, linear by default
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+        self.update_marks();
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for MultiLineChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("multi_line_chart", 9)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for MultiLineChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every series of the chart in the same color
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for MultiLineChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
@@ -0,0 +1,69 @@
+/*!
+Charts straight from `ndarray` arrays, which needs the `ndarray` feature of gust.
+
+A one dimensional array is drawn as a single series, with the index of every element on the x
+axis and the element as its y value. The rows of a two dimensional array are drawn as series
+of their own, numbered from 0 like the rows.
+
+```rust,ignore
+extern crate ndarray;
+use ndarray::{arr1, arr2};
+use gust::backend::line_chart::LineChart;
+
+let c = LineChart::from_ndarray(&arr1(&[1.0, 4.0, 9.0, 16.0]));
+let d = LineChart::from_ndarray2(&arr2(&[[1.0, 2.0], [3.0, 5.0]]));
+```
+ */
+
+use backend::area_chart::AreaChart;
+use backend::general::Numeric;
+use backend::line_chart::LineChart;
+use ndarray::{ArrayBase, Data, Ix1, Ix2};
+
+impl LineChart {
+    /// Creates a line chart with a single line through the elements of the array
+    pub fn from_ndarray<S, A>(array: &ArrayBase<S, Ix1>) -> LineChart
+    where
+        S: Data<Elem = A>,
+        A: Numeric,
+    {
+        let mut chart = LineChart::new();
+        chart.add_ndarray(array, 0);
+        chart
+    }
+
+    /// Creates a line chart with a line through every row of the array
+    pub fn from_ndarray2<S, A>(array: &ArrayBase<S, Ix2>) -> LineChart
+    where
+        S: Data<Elem = A>,
+        A: Numeric,
+    {
+        let mut chart = LineChart::new();
+        for (z, row) in array.outer_iter().enumerate() {
+            chart.add_ndarray(&row, z as i64);
+        }
+        chart
+    }
+
+    /// Adds the elements of the array to the series z, at their index along the x axis
+    pub fn add_ndarray<S, A>(&mut self, array: &ArrayBase<S, Ix1>, z: i64)
+    where
+        S: Data<Elem = A>,
+        A: Numeric,
+    {
+        self.add_data_iter(array.iter().enumerate().map(|(x, &y)| (x, y, z)));
+    }
+}
+
+impl AreaChart {
+    /// Creates an area chart of the elements of the array, at their index along the x axis
+    pub fn from_ndarray<S, A>(array: &ArrayBase<S, Ix1>) -> AreaChart
+    where
+        S: Data<Elem = A>,
+        A: Numeric,
+    {
+        let mut chart = AreaChart::new();
+        chart.add_data_iter(array.iter().enumerate().map(|(u, &v)| (u, v)));
+        chart
+    }
+}
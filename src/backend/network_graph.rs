@@ -0,0 +1,203 @@
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Mark, MarkType, SymbolShape, ValueRef};
+use backend::elements::scale::Scale;
+use backend::elements::transform::{Force, Transform};
+use backend::elements::general::SCHEMA;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::Graphable;
+
+/// A network graph draws nodes as dots and the edges between them as lines. The nodes are
+/// placed by a force simulation: edges pull the nodes they connect together, while all nodes
+/// push each other apart.
+/// # Example:
+/// ```rust
+///    use gust::backend::network_graph::NetworkGraph;
+///
+///    let mut n = NetworkGraph::new();
+///    n.add_node("Alice");
+///    n.add_edge("Alice", "Bob");
+///    n.add_edge("Bob", "Carol");
+///    n.add_edge("Carol", "Alice");
+///    n.set_link_distance(60.0);
+/// ```
+pub struct NetworkGraph {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    nodes: Vec<String>,
+    color: String,
+    link_distance: f64,
+    charge: f64,
+    scales: Vec<Scale>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl NetworkGraph {
+    pub fn new() -> NetworkGraph {
+        let mut chart = NetworkGraph {
+            identifier: String::from("network_graph"),
+            description: String::from("Network Graph"),
+            width: 500,
+            height: 400,
+            padding: 5,
+            data: vec![
+                DataSet::from_values("nodes", vec![]),
+                DataSet::from_values("links", vec![]),
+            ],
+            nodes: vec![],
+            color: String::from("steelblue"),
+            link_distance: 30.0,
+            charge: -30.0,
+            scales: vec![],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.update_marks();
+        chart
+    }
+
+    /// Rebuilds the nodes, and the links drawn along their layout. The nodes come first, as
+    /// their layout defines the signal the links wait for.
+    fn update_marks(&mut self) {
+        let layout = Force::new()
+            .center(ValueRef::signal("width / 2"), ValueRef::signal("height / 2"))
+            .collide(8.0)
+            .nbody(self.charge)
+            .link("links", "datum.id", self.link_distance)
+            .signal("force");
+        let nodes = Mark::symbol(SymbolShape::Circle, 100.0)
+            .name("nodes")
+            .from_data("nodes")
+            .enter("fill", ValueRef::value(self.color.as_str()))
+            .stroke("white", 1.0)
+            .enter("tooltip", ValueRef::field("id"))
+            .transform(Transform::Force(layout));
+        let links = Mark::new(MarkType::Path)
+            .from_data("links")
+            .enter("stroke", ValueRef::value("#ccc"))
+            .enter("strokeWidth", ValueRef::value(1))
+            .transform(Transform::force_links("force"))
+            .interactive(false);
+        self.marks = vec![nodes, links];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a node with the given id, unless the graph holds it already
+    pub fn add_node(&mut self, id: &str) {
+        if self.nodes.iter().any(|n| n == id) {
+            return;
+        }
+        self.nodes.push(String::from(id));
+        let mut m = Map::new();
+        m.insert(String::from("id"), Value::from(id));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Adds an edge between the nodes with the ids `a` and `b`, adding the nodes if the graph
+    /// doesn't hold them yet
+    pub fn add_edge(&mut self, a: &str, b: &str) {
+        self.add_node(a);
+        self.add_node(b);
+        let mut m = Map::new();
+        m.insert(String::from("source"), Value::from(a));
+        m.insert(String::from("target"), Value::from(b));
+        self.data[1].add_value(Value::Object(m));
+    }
+
+    /// Sets the length in pixels the edges are pulled towards, 30 by default
+    pub fn set_link_distance(&mut self, distance: f64) {
+        self.link_distance = distance;
+        self.update_marks();
+    }
+
+    /// Sets the charge of the nodes, -30 by default. Negative charges push the nodes apart,
+    /// positive charges make them attract each other.
+    pub fn set_charge(&mut self, charge: f64) {
+        self.charge = charge;
+        self.update_marks();
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for NetworkGraph {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("network_graph", 7)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for NetworkGraph {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color sets the color of the nodes
+    fn set_color(&mut self, color: &str) {
+        self.color = String::from(color);
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the nodes, which run before they are laid out
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+}
@@ -0,0 +1,221 @@
+
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Align, Baseline, Mark, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::SCHEMA;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::Graphable;
+
+/// The radius of the pie, as large as fits the chart
+const OUTER_RADIUS: &'static str = "min(width, height) / 2";
+
+/// A pie chart draws every value as a slice of a circle, whose angle is proportional to its
+/// share of the sum of all values. With an inner radius, it becomes a donut chart.
+/// # Example:
+/// ```rust
+///    use gust::backend::pie_chart::PieChart;
+///
+///    let mut p = PieChart::new();
+///    p.add_slice("Rent", 1200.0);
+///    p.add_slice("Food", 400.0);
+///    p.add_slice("Travel", 250.0);
+///    p.set_inner_radius(0.5);
+/// ```
+pub struct PieChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    inner_radius: f64,
+    labels: bool,
+    color: Option<String>,
+    scales: Vec<Scale>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl PieChart {
+    pub fn new() -> PieChart {
+        let mut slices = DataSet::from_values("table", vec![]);
+        slices.add_transform(Transform::Pie {
+            field: String::from("value"),
+            sort: None,
+        });
+
+        let mut chart = PieChart {
+            identifier: String::from("pie_chart"),
+            description: String::from("Pie Chart"),
+            width: 400,
+            height: 400,
+            padding: 5,
+            data: vec![slices],
+            inner_radius: 0.0,
+            labels: false,
+            color: None,
+            scales: vec![
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "label"),
+                    "category",
+                ),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.update_marks();
+        chart
+    }
+
+    /// Rebuilds the slices of the pie and their labels
+    fn update_marks(&mut self) {
+        let center = || (ValueRef::signal("width / 2"), ValueRef::signal("height / 2"));
+        let fill = match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", "label"),
+        };
+        let inner = format!("{} * {}", OUTER_RADIUS, self.inner_radius);
+        let (x, y) = center();
+        let slices = Mark::arc(ValueRef::field("startAngle"), ValueRef::field("endAngle"))
+            .from_data("table")
+            .at(x, y)
+            .radius(ValueRef::signal(&inner), ValueRef::signal(OUTER_RADIUS))
+            .pad_angle(0.01)
+            .enter("fill", fill)
+            .enter("tooltip", ValueRef::signal("datum.label + ': ' + datum.value"))
+            .stroke("white", 1.0);
+        self.marks = vec![slices];
+
+        if self.labels {
+            // halfway through the ring, or at two thirds of the radius of a full pie
+            let radius = format!(
+                "{} * {}",
+                OUTER_RADIUS,
+                (1.0 + self.inner_radius.max(1.0 / 3.0)) / 2.0
+            );
+            let (x, y) = center();
+            self.marks.push(
+                Mark::text(ValueRef::field("label"))
+                    .from_data("table")
+                    .at(x, y)
+                    .enter("radius", ValueRef::signal(&radius))
+                    .enter("theta", ValueRef::signal("(datum.startAngle + datum.endAngle) / 2"))
+                    .align(Align::Center)
+                    .baseline(Baseline::Middle)
+                    .right_to_left(self.extras.is_right_to_left())
+                    .enter("fill", ValueRef::value("white"))
+                    .interactive(false),
+            );
+        }
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a slice with the given label. Slices are drawn clockwise from the top, in the
+    /// order they were added.
+    pub fn add_slice(&mut self, label: &str, value: f64) {
+        let mut m = Map::new();
+        m.insert(String::from("label"), Value::from(label));
+        m.insert(String::from("value"), Value::from(value));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Cuts a hole into the middle of the pie, turning it into a donut chart. The radius of the
+    /// hole is given as a fraction of the radius of the pie, from 0 (no hole) to 1.
+    pub fn set_inner_radius(&mut self, radius: f64) {
+        self.inner_radius = radius.max(0.0).min(1.0);
+        self.update_marks();
+    }
+
+    /// Prints the label of every slice onto the slice
+    pub fn show_labels(&mut self, labels: bool) {
+        self.labels = labels;
+        self.update_marks();
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for PieChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("pie_chart", 7)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for PieChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every slice in the same color, they are told apart by their borders
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the slices, which run after their angles are computed
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.update_marks();
+    }
+}
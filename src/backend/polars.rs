@@ -0,0 +1,202 @@
+/*!
+Charts straight from polars data frames, which needs the `polars` feature of gust.
+
+The scales of a chart follow the data types of the columns it is made from. Text columns name
+the categories of bar and lollipop charts, which are drawn on a band scale. The x column of
+line, area and scatter charts has to hold numbers, drawn on a linear scale, or dates and
+datetimes, drawn on a UTC time scale whose values are milliseconds since the unix epoch; a
+text x column is an error. Rows with a missing category or x value are left out, missing y
+values leave a gap in lines and areas.
+
+```rust,ignore
+extern crate polars;
+use polars::prelude::*;
+use gust::backend::bar_chart::BarChart;
+use gust::backend::line_chart::LineChart;
+
+let df = df!("region" => &["North", "South"], "revenue" => &[12.5, 7.0]).unwrap();
+let b = BarChart::from_dataframe(&df, "region", "revenue").unwrap();
+let c = LineChart::from_dataframe(&df, "revenue", "revenue").unwrap();
+```
+ */
+
+use backend::area_chart::AreaChart;
+use backend::bar_chart::BarChart;
+use backend::elements::scale::ScaleType;
+use backend::line_chart::LineChart;
+use backend::lollipop_chart::LollipopChart;
+use backend::scatter_plot::ScatterPlot;
+use backend::traits::Cartesian;
+use polars::prelude::{DataFrame, DataType, PolarsError, PolarsResult, Series, TimeUnit};
+
+/// ColumnKind groups the data types of polars by the scale that fits them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnKind {
+    /// Text, which can only name categories, drawn on a band scale
+    Text,
+    /// Integers and floating point numbers, drawn on a linear scale
+    Numeric,
+    /// Dates and datetimes, drawn on a UTC time scale
+    Temporal,
+}
+
+impl ColumnKind {
+    /// The kind of the column, or None for data types that can't be charted, like lists
+    pub fn of(series: &Series) -> Option<ColumnKind> {
+        match *series.dtype() {
+            DataType::Utf8 | DataType::Boolean => Some(ColumnKind::Text),
+            DataType::Date | DataType::Datetime(_, _) => Some(ColumnKind::Temporal),
+            ref dtype if dtype.is_numeric() => Some(ColumnKind::Numeric),
+            _ => None,
+        }
+    }
+
+    /// The type of scale the column is drawn on
+    pub fn scale_type(&self) -> ScaleType {
+        match *self {
+            ColumnKind::Text => ScaleType::Band,
+            ColumnKind::Numeric => ScaleType::Linear,
+            ColumnKind::Temporal => ScaleType::Utc,
+        }
+    }
+}
+
+fn mismatch(series: &Series, expected: &str) -> PolarsError {
+    PolarsError::SchemaMismatch(
+        format!(
+            "column '{}' of type {} can't be used as {}",
+            series.name(),
+            series.dtype(),
+            expected
+        ).into(),
+    )
+}
+
+/// The values of a numeric or temporal column as numbers, temporal values as milliseconds
+/// since the unix epoch
+fn numbers(series: &Series) -> PolarsResult<Vec<Option<f64>>> {
+    let millis = match *series.dtype() {
+        DataType::Date => 86_400_000.0,
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => 1e-6,
+        DataType::Datetime(TimeUnit::Microseconds, _) => 1e-3,
+        DataType::Datetime(TimeUnit::Milliseconds, _) => 1.0,
+        ref dtype if dtype.is_numeric() => {
+            return Ok(series.cast(&DataType::Float64)?.f64()?.into_iter().collect());
+        }
+        _ => return Err(mismatch(series, "numbers")),
+    };
+    let raw = series.cast(&DataType::Int64)?;
+    let values = raw.i64()?.into_iter();
+    Ok(values.map(|v| v.map(|v| v as f64 * millis)).collect())
+}
+
+/// The values of a column as text, numbers included
+fn text(series: &Series) -> PolarsResult<Vec<Option<String>>> {
+    let values = series.cast(&DataType::Utf8)?;
+    let values = values.utf8()?.into_iter();
+    Ok(values.map(|v| v.map(String::from)).collect())
+}
+
+/// The x and y values of the rows that have an x value, and the kind of the x column
+fn points(df: &DataFrame, x: &str, y: &str) -> PolarsResult<(ColumnKind, Vec<(f64, Option<f64>)>)> {
+    let xs = df.column(x)?;
+    let kind = match ColumnKind::of(xs) {
+        Some(ColumnKind::Text) | None => return Err(mismatch(xs, "x values")),
+        Some(kind) => kind,
+    };
+    let points = numbers(xs)?
+        .into_iter()
+        .zip(numbers(df.column(y)?)?)
+        .filter_map(|(x, y)| x.map(|x| (x, y)))
+        .collect();
+    Ok((kind, points))
+}
+
+/// The categories and amounts of the rows that have both
+fn categories(df: &DataFrame, category: &str, amount: &str) -> PolarsResult<Vec<(String, f64)>> {
+    let amounts = numbers(df.column(amount)?)?;
+    Ok(text(df.column(category)?)?
+        .into_iter()
+        .zip(amounts)
+        .filter_map(|(c, a)| match (c, a) {
+            (Some(c), Some(a)) => Some((c, a)),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Switches the x scale of the chart to the scale that fits the kind of its x column
+fn select_x_scale<C: Cartesian>(chart: &mut C, kind: ColumnKind) {
+    chart.x_scale_mut().set_scale_type(kind.scale_type());
+    if kind == ColumnKind::Temporal {
+        chart.x_axis_mut().set_utc(true);
+    }
+}
+
+impl BarChart {
+    /// Creates a bar chart with a bar for every row of the data frame, named by the column
+    /// `category` and as high as the number in the column `amount`
+    pub fn from_dataframe(df: &DataFrame, category: &str, amount: &str) -> PolarsResult<BarChart> {
+        let mut chart = BarChart::new();
+        chart.add_data_iter(categories(df, category, amount)?);
+        Ok(chart)
+    }
+}
+
+impl LollipopChart {
+    /// Creates a lollipop chart from the data frame, like `BarChart::from_dataframe`
+    pub fn from_dataframe(
+        df: &DataFrame,
+        category: &str,
+        amount: &str,
+    ) -> PolarsResult<LollipopChart> {
+        let mut chart = LollipopChart::new();
+        chart.add_data_iter(categories(df, category, amount)?);
+        Ok(chart)
+    }
+}
+
+impl LineChart {
+    /// Creates a line chart with a single line through the numbers or dates in the columns
+    /// `x` and `y`
+    pub fn from_dataframe(df: &DataFrame, x: &str, y: &str) -> PolarsResult<LineChart> {
+        let (kind, points) = points(df, x, y)?;
+        let mut chart = LineChart::new();
+        select_x_scale(&mut chart, kind);
+        chart.x_scale_mut().include_zero(false);
+        for (x, y) in points {
+            match y {
+                Some(y) => chart.add_data(x, y, 0),
+                None => chart.add_gap(x, 0),
+            }
+        }
+        Ok(chart)
+    }
+}
+
+impl AreaChart {
+    /// Creates an area chart of the numbers or dates in the columns `u` and `v`
+    pub fn from_dataframe(df: &DataFrame, u: &str, v: &str) -> PolarsResult<AreaChart> {
+        let (kind, points) = points(df, u, v)?;
+        let mut chart = AreaChart::new();
+        select_x_scale(&mut chart, kind);
+        for (u, v) in points {
+            match v {
+                Some(v) => chart.add_data(u, v),
+                None => chart.add_gap(u),
+            }
+        }
+        Ok(chart)
+    }
+}
+
+impl ScatterPlot {
+    /// Creates a scatter plot of the rows that have both a value in the column `x` and `y`
+    pub fn from_dataframe(df: &DataFrame, x: &str, y: &str) -> PolarsResult<ScatterPlot> {
+        let (kind, points) = points(df, x, y)?;
+        let mut chart = ScatterPlot::new();
+        select_x_scale(&mut chart, kind);
+        chart.add_data_iter(points.into_iter().filter_map(|(x, y)| y.map(|y| (x, y))));
+        Ok(chart)
+    }
+}
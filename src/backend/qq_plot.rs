@@ -0,0 +1,254 @@
+
+use backend::elements::qq_plot::*;
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::{Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// A Q-Q plot draws the quantiles of a sample against the quantiles of another distribution.
+/// When both follow the same distribution, the points line up along a straight line.
+///
+/// The sample is compared against a theoretical distribution, the standard normal distribution
+/// unless `set_distribution` is used, or against a second sample if any reference data was added.
+/// # Example:
+/// ```rust
+///    use gust::backend::qq_plot::QQPlot;
+///    use gust::backend::elements::qq_plot::Distribution;
+///
+///    let mut q = QQPlot::new();
+///    for v in &[2.1, 3.4, 1.9, 5.0, 2.8, 3.3] {
+///        q.add_data(*v);
+///    }
+///    q.set_distribution(Distribution::Normal { mean: 3.0, stdev: 1.0 });
+/// ```
+pub struct QQPlot {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    distribution: Distribution,
+    step: f64,
+    empirical: bool,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl QQPlot {
+    pub fn new() -> QQPlot {
+        let mut plot = QQPlot {
+            identifier: String::from("qq_plot"),
+            description: String::from("Q-Q Plot"),
+            width: 400,
+            height: 400,
+            padding: 5,
+            data: vec![
+                DataSet::from_values("sample", vec![]),
+                DataSet::from_values("reference", vec![]),
+            ],
+            distribution: Distribution::standard_normal(),
+            step: 0.01,
+            empirical: false,
+            scales: vec![
+                Scale::new("x", ScaleType::Linear, Domain::field("quantiles", "expected"), "width"),
+                Scale::new("y", ScaleType::Linear, Domain::field("quantiles", "value"), "height"),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![QQPlot::points("steelblue")],
+            extras: Extras::new(),
+        };
+        plot.scales[0].include_zero(false);
+        plot.scales[1].include_zero(false);
+        plot.update_quantiles();
+        plot
+    }
+
+    fn points(color: &str) -> Mark {
+        Mark::new(MarkType::Symbol)
+            .from_data("quantiles")
+            .enter("x", ValueRef::scaled("x", "expected"))
+            .enter("y", ValueRef::scaled("y", "value"))
+            .enter("size", ValueRef::value(20))
+            .enter("fill", ValueRef::value(color))
+    }
+
+    fn value(v: f64) -> Value {
+        let mut m = Map::new();
+        m.insert(String::from("v"), Value::from(v));
+        Value::Object(m)
+    }
+
+    /// Rebuilds the derived data sets computing the quantiles of the sample (`value`), and the
+    /// quantiles they are compared against (`expected`)
+    fn update_quantiles(&mut self) {
+        self.data.truncate(2);
+
+        let mut quantiles = DataSet::from_source("quantiles", "sample");
+        quantiles.add_transform(Transform::quantile("v", self.step));
+        if self.empirical {
+            let mut reference = DataSet::from_source("reference_quantiles", "reference");
+            reference.add_transform(Transform::quantile("v", self.step));
+            reference.add_transform(Transform::formula("datum.value", "expected"));
+            self.data.push(reference);
+            quantiles.add_transform(Transform::lookup(
+                "reference_quantiles",
+                "prob",
+                "prob",
+                &["expected"],
+            ));
+        } else {
+            let expected = self.distribution.quantile_expression();
+            quantiles.add_transform(Transform::formula(&expected, "expected"));
+        }
+        self.data.push(quantiles);
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a value to the sample
+    pub fn add_data(&mut self, v: f64) {
+        self.data[0].add_value(QQPlot::value(v));
+    }
+
+    /// Adds every value of the iterator to the sample
+    pub fn add_data_iter<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        let iter = iter.into_iter();
+        self.data[0].values_mut().reserve(iter.size_hint().0);
+        for v in iter {
+            self.add_data(v);
+        }
+    }
+
+    /// Adds a value to the reference sample. Once reference data was added, the sample is
+    /// compared against the reference sample instead of a theoretical distribution.
+    pub fn add_reference_data(&mut self, v: f64) {
+        self.data[1].add_value(QQPlot::value(v));
+        if !self.empirical {
+            self.empirical = true;
+            self.update_quantiles();
+        }
+    }
+
+    /// Sets the theoretical distribution the sample is compared against
+    pub fn set_distribution(&mut self, distribution: Distribution) {
+        self.distribution = distribution;
+        self.update_quantiles();
+    }
+
+    /// Sets the distance between the probabilities the quantiles are computed at, 0.01 by
+    /// default. Small samples look better with a coarser step.
+    pub fn set_step(&mut self, step: f64) {
+        self.step = step;
+        self.update_quantiles();
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for QQPlot {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("qq_plot", 8)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for QQPlot {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    fn set_color(&mut self, color: &str) {
+        self.marks[0] = QQPlot::points(color);
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the sample, which run before its quantiles are computed
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for QQPlot {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
@@ -0,0 +1,247 @@
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::legend::Legend;
+use backend::elements::mark::{Baseline, Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Range, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::SCHEMA;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::Graphable;
+
+/// The radius of the web, leaving room for the names of the axes around it
+const RADIUS: &'static str = "min(width, height) / 2 - 30";
+
+/// A radar chart compares series across several named axes, which start in the center and
+/// point outwards like the spokes of a wheel. The values of a series are connected into a
+/// polygon.
+/// # Example:
+/// ```rust
+///    use gust::backend::radar_chart::RadarChart;
+///
+///    let mut r = RadarChart::new(&["Speed", "Power", "Range", "Comfort", "Price"]);
+///    r.add_series("Model A", &[7.0, 5.0, 9.0, 6.0, 4.0]);
+///    r.add_series("Model B", &[5.0, 8.0, 6.0, 7.0, 6.0]);
+/// ```
+pub struct RadarChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    axes: Vec<String>,
+    data: Vec<DataSet>,
+    color: Option<String>,
+    scales: Vec<Scale>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl RadarChart {
+    /// Creates a radar chart with the given axes, arranged clockwise starting at the bottom
+    pub fn new(axes: &[&str]) -> RadarChart {
+        let keys = axes
+            .iter()
+            .map(|a| {
+                let mut m = Map::new();
+                m.insert(String::from("key"), Value::from(*a));
+                Value::Object(m)
+            })
+            .collect();
+
+        let mut chart = RadarChart {
+            identifier: String::from("radar_chart"),
+            description: String::from("Radar Chart"),
+            width: 400,
+            height: 400,
+            padding: 5,
+            axes: axes.iter().map(|a| a.to_string()).collect(),
+            data: vec![
+                DataSet::from_values("keys", keys),
+                DataSet::from_values("table", vec![]),
+            ],
+            color: None,
+            scales: vec![
+                Scale::with_range(
+                    "angular",
+                    ScaleType::Point,
+                    Domain::field("keys", "key"),
+                    Range::signal("[-PI, PI]"),
+                ),
+                Scale::with_range(
+                    "radial",
+                    ScaleType::Linear,
+                    Domain::field("table", "value"),
+                    Range::signal(&format!("[0, {}]", RADIUS)),
+                ),
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "series"),
+                    "category",
+                ),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[0].set_padding(0.5);
+        chart.scales[1].include_zero(true);
+        chart.extras.add_legend(Legend::stroke("color"));
+        chart.update_marks();
+        chart
+    }
+
+    /// The position of a point on the axis of the datum, at the given distance from the center
+    fn polar(radius: &str) -> (ValueRef, ValueRef) {
+        (
+            ValueRef::signal(&format!("{} * cos(scale('angular', datum.key))", radius)),
+            ValueRef::signal(&format!("{} * sin(scale('angular', datum.key))", radius)),
+        )
+    }
+
+    /// Rebuilds the web of axes and the polygons of the series, all within a group centered on
+    /// the chart
+    fn update_marks(&mut self) {
+        let color = || match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", "series"),
+        };
+
+        let (x, y) = RadarChart::polar("scale('radial', datum.value)");
+        let polygon = Mark::new(MarkType::Line)
+            .from_data("series")
+            .at(x, y)
+            .enter("interpolate", ValueRef::value("linear-closed"))
+            .enter("stroke", color())
+            .enter("strokeWidth", ValueRef::value(2))
+            .enter("fill", color())
+            .enter("fillOpacity", ValueRef::value(0.15));
+
+        let (x2, y2) = RadarChart::polar(RADIUS);
+        let spokes = Mark::rule(ValueRef::value(0), ValueRef::value(0), x2, y2)
+            .from_data("keys")
+            .stroke("lightgray", 1.0)
+            .interactive(false);
+
+        let (x, y) = RadarChart::polar(&format!("({} + 8)", RADIUS));
+        let labels = Mark::text(ValueRef::field("key"))
+            .from_data("keys")
+            .at(x, y)
+            .enter(
+                "align",
+                ValueRef::signal(
+                    "abs(cos(scale('angular', datum.key))) < 0.1 ? 'center' : \
+                     cos(scale('angular', datum.key)) > 0 ? 'left' : 'right'",
+                ),
+            )
+            .baseline(Baseline::Middle)
+            .right_to_left(self.extras.is_right_to_left())
+            .interactive(false);
+
+        self.marks = vec![
+            Mark::new(MarkType::Group)
+                .at(ValueRef::signal("width / 2"), ValueRef::signal("height / 2"))
+                .mark(spokes)
+                .mark(labels)
+                .mark(
+                    Mark::new(MarkType::Group)
+                        .facet("series", "table", "series")
+                        .mark(polygon),
+                ),
+        ];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a series with one value for each axis, in the order the axes were given. Values
+    /// beyond the number of axes are ignored.
+    pub fn add_series(&mut self, name: &str, values: &[f64]) {
+        for (key, value) in self.axes.iter().zip(values) {
+            let mut m = Map::new();
+            m.insert(String::from("key"), Value::from(key.as_str()));
+            m.insert(String::from("series"), Value::from(name));
+            m.insert(String::from("value"), Value::from(*value));
+            self.data[1].add_value(Value::Object(m));
+        }
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for RadarChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("radar_chart", 7)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for RadarChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every series in the same color
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[1].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.update_marks();
+    }
+}
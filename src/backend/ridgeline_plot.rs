@@ -0,0 +1,250 @@
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Range, Scale, ScaleType};
+use backend::elements::transform::{Kde, Transform};
+use backend::elements::general::{Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// A ridgeline plot, also known as a joyplot, draws the distribution of the samples of every
+/// group as a ridge in a row of its own. The rows are stacked from top to bottom, and every
+/// ridge may rise into the rows above it, which is set through `set_overlap`.
+/// # Example:
+/// ```rust
+///    use gust::backend::ridgeline_plot::RidgelinePlot;
+///
+///    let mut r = RidgelinePlot::new();
+///    r.add_samples("January", &[-2.0, 0.5, 1.0, 3.5, 4.0]);
+///    r.add_samples("July", &[18.0, 21.5, 22.0, 24.5, 27.0]);
+///    r.set_overlap(1.5);
+/// ```
+pub struct RidgelinePlot {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: Option<String>,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl RidgelinePlot {
+    pub fn new() -> RidgelinePlot {
+        let mut chart = RidgelinePlot {
+            identifier: String::from("ridgeline_plot"),
+            description: String::from("Ridgeline Plot"),
+            width: 500,
+            height: 300,
+            padding: 5,
+            data: vec![
+                DataSet::from_values("table", vec![]),
+                RidgelinePlot::density(None),
+            ],
+            color: None,
+            scales: vec![
+                Scale::new("x", ScaleType::Linear, Domain::field("density", "value"), "width"),
+                Scale::new("y", ScaleType::Band, Domain::field("table", "group"), "height"),
+                Scale::with_range(
+                    "density",
+                    ScaleType::Linear,
+                    Domain::field("density", "density"),
+                    RidgelinePlot::ridge_range(1.0),
+                ),
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "group"),
+                    "category",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[0].include_zero(false);
+        chart.update_marks();
+        chart
+    }
+
+    /// The data set holding the density of every group, sampled along the values
+    fn density(bandwidth: Option<f64>) -> DataSet {
+        let mut kde = Kde::new("value").groupby("group");
+        if let Some(bandwidth) = bandwidth {
+            kde = kde.bandwidth(bandwidth);
+        }
+        let mut density = DataSet::from_source("density", "table");
+        density.add_transform(Transform::Kde(kde));
+        density
+    }
+
+    /// The range of the density within a row: from its bottom up to the highest ridge, which
+    /// lies `overlap` rows higher
+    fn ridge_range(overlap: f64) -> Range {
+        Range::signal(&format!(
+            "[bandwidth('y'), bandwidth('y') * (1 - {})]",
+            overlap
+        ))
+    }
+
+    /// Rebuilds the ridges, one group mark per group
+    fn update_marks(&mut self) {
+        let color = || match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", "group"),
+        };
+        let ridge = Mark::new(MarkType::Area)
+            .from_data("ridge")
+            .enter("x", ValueRef::scaled("x", "value"))
+            .enter("y", ValueRef::scaled("density", "density"))
+            .enter("y2", ValueRef::scaled_value("density", 0))
+            .enter("fill", color())
+            .enter("fillOpacity", ValueRef::value(0.7))
+            .enter("stroke", color())
+            .enter("interpolate", ValueRef::value("monotone"));
+        self.marks = vec![
+            Mark::new(MarkType::Group)
+                .facet("ridge", "density", "group")
+                .enter("y", ValueRef::scaled("y", "group"))
+                .enter("width", ValueRef::signal("width"))
+                .enter("height", ValueRef::signal("bandwidth('y')"))
+                .mark(ridge),
+        ];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a single sample to the group with the given name. Groups are stacked from top to
+    /// bottom in the order they first appear.
+    pub fn add_sample(&mut self, group: &str, value: f64) {
+        let mut m = Map::new();
+        m.insert(String::from("group"), Value::from(group));
+        m.insert(String::from("value"), Value::from(value));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Adds all of the samples to the group with the given name
+    pub fn add_samples(&mut self, group: &str, values: &[f64]) {
+        for value in values {
+            self.add_sample(group, *value);
+        }
+    }
+
+    /// Sets the bandwidth of the density estimate, in units of the values. Smaller bandwidths
+    /// follow the samples more closely, Vega estimates one from the samples if it isn't set.
+    pub fn set_bandwidth(&mut self, bandwidth: f64) {
+        self.data[1] = RidgelinePlot::density(Some(bandwidth));
+    }
+
+    /// Sets how high the highest ridge rises, in rows. At the default of 1 it just touches
+    /// the row above, larger values let the ridges overlap.
+    pub fn set_overlap(&mut self, overlap: f64) {
+        self.scales[2].set_range(RidgelinePlot::ridge_range(overlap));
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for RidgelinePlot {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("ridgeline_plot", 9)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for RidgelinePlot {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every ridge in the same color
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the samples, which run before their density is estimated
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for RidgelinePlot {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
@@ -0,0 +1,240 @@
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::graph::{self, Flow};
+use backend::elements::mark::{Baseline, Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::SCHEMA;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use backend::traits::Graphable;
+
+/// The width of the bar of a node in pixels
+const NODE_WIDTH: f64 = 15.0;
+
+/// A sankey chart shows how quantities flow between nodes: every flow is a band from its source
+/// to its target node, as thick as its weight. Nodes are laid out in columns, from the sources
+/// of all flows on the left to the nodes without outgoing flows on the right.
+/// # Example:
+/// ```rust
+///    use gust::backend::sankey_chart::SankeyChart;
+///
+///    let mut s = SankeyChart::new();
+///    s.add_flow("Solar", "Grid", 30.0);
+///    s.add_flow("Wind", "Grid", 45.0);
+///    s.add_flow("Grid", "Homes", 50.0);
+///    s.add_flow("Grid", "Industry", 25.0);
+/// ```
+pub struct SankeyChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    flows: Vec<Flow>,
+    node_padding: f64,
+    data: Vec<DataSet>,
+    color: Option<String>,
+    scales: Vec<Scale>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl SankeyChart {
+    pub fn new() -> SankeyChart {
+        let mut chart = SankeyChart {
+            identifier: String::from("sankey_chart"),
+            description: String::from("Sankey Chart"),
+            width: 600,
+            height: 400,
+            padding: 5,
+            flows: vec![],
+            node_padding: 0.05,
+            data: vec![
+                DataSet::from_values("nodes", vec![]),
+                DataSet::from_values("links", vec![]),
+            ],
+            color: None,
+            scales: vec![
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("nodes", "name"),
+                    "category",
+                ),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.update_marks();
+        chart
+    }
+
+    /// Lays the nodes and links out again, after the flows changed
+    fn update_layout(&mut self) {
+        let (nodes, links) = graph::sankey(&self.flows, self.node_padding);
+        *self.data[0].values_mut() = nodes;
+        *self.data[1].values_mut() = links;
+    }
+
+    /// Rebuilds the bars of the nodes, their labels and the bands of the links
+    fn update_marks(&mut self) {
+        let color = |field: &str| match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", field),
+        };
+        // the columns are spread over the width left of the bars of the last column
+        let x = |column: &str| format!("{} * (width - {})", column, NODE_WIDTH);
+
+        let (x0, x1) = (format!("({} + {})", x("datum.column0"), NODE_WIDTH), x("datum.column1"));
+        let (y0, y1) = ("datum.y0 * height", "datum.y1 * height");
+        let middle = format!("({} + {}) / 2", x0, x1);
+        let path = format!(
+            "'M' + {x0} + ',' + {y0} + 'C' + {m} + ',' + {y0} + ' ' + {m} + ',' + {y1} + \
+             ' ' + {x1} + ',' + {y1}",
+            x0 = x0,
+            x1 = x1,
+            y0 = y0,
+            y1 = y1,
+            m = middle
+        );
+        let links = Mark::path(ValueRef::signal(&path))
+            .from_data("links")
+            .enter("stroke", color("source"))
+            .enter("strokeWidth", ValueRef::signal("max(1, datum.size * height)"))
+            .enter(
+                "tooltip",
+                ValueRef::signal("datum.source + ' → ' + datum.target + ': ' + datum.value"),
+            )
+            .update("strokeOpacity", ValueRef::value(0.3))
+            .hover("strokeOpacity", ValueRef::value(0.6));
+
+        let nodes = Mark::new(MarkType::Rect)
+            .from_data("nodes")
+            .enter("x", ValueRef::signal(&x("datum.column")))
+            .enter("width", ValueRef::value(NODE_WIDTH))
+            .enter("y", ValueRef::signal(y0))
+            .enter("y2", ValueRef::signal(y1))
+            .enter("fill", color("name"))
+            .enter("tooltip", ValueRef::signal("datum.name + ': ' + datum.value"));
+
+        // labels sit right of the nodes in the left half of the chart, and left of them in
+        // the right half
+        let label_x = format!(
+            "datum.column < 0.5 ? {x} + {w} + 5 : {x} - 5",
+            x = x("datum.column"),
+            w = NODE_WIDTH
+        );
+        let labels = Mark::text(ValueRef::field("name"))
+            .from_data("nodes")
+            .at(
+                ValueRef::signal(&label_x),
+                ValueRef::signal("(datum.y0 + datum.y1) / 2 * height"),
+            )
+            .enter("align", ValueRef::signal("datum.column < 0.5 ? 'left' : 'right'"))
+            .baseline(Baseline::Middle)
+            .right_to_left(self.extras.is_right_to_left())
+            .interactive(false);
+
+        self.marks = vec![links, nodes, labels];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a flow of the given weight from `source` to `target`, adding either node if it
+    /// doesn't exist yet. The flows should not form a cycle.
+    pub fn add_flow(&mut self, source: &str, target: &str, weight: f64) {
+        self.flows.push(Flow {
+            source: String::from(source),
+            target: String::from(target),
+            weight,
+        });
+        self.update_layout();
+    }
+
+    /// Sets the gap between the nodes of a column, as a fraction of the height of the chart
+    pub fn set_node_padding(&mut self, padding: f64) {
+        self.node_padding = padding;
+        self.update_layout();
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for SankeyChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("sankey_chart", 7)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for SankeyChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every node and link in the same color
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the links
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[1].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.update_marks();
+    }
+}
@@ -0,0 +1,317 @@
+
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::legend::Legend;
+use backend::elements::mark::{when, Mark, MarkType, Production, ValueRef};
+use backend::elements::overlay::{self, Plot, ValueLabels};
+use backend::elements::predicate::Predicate;
+use backend::elements::scale::{Domain, Range, Scale, ScaleType};
+use backend::elements::transform::{Method, Transform};
+use backend::elements::general::{Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// The points of a scatter plot, which overlays are drawn against
+const PLOT: Plot = Plot {
+    data: "table",
+    x: "x",
+    y: "y",
+    series: None,
+    x_scale: "x",
+    y_scale: "y",
+    color_scale: None,
+};
+
+/// A scatter plot draws every data point as a dot at its x and y value, in no particular order.
+/// Points may have a size, which scales the area of their dot, and a group, which colors it,
+/// turning the plot into a bubble chart.
+/// # Example:
+/// ```rust
+///    use gust::backend::scatter_plot::ScatterPlot;
+///
+///    let mut s = ScatterPlot::new();
+///    s.add_data(1.5, 2.0);
+///    s.add_data(0.3, 4.1);
+///    s.add_sized_data(2.2, 3.3, 10.0);
+///    s.add_bubble(1.1, 2.8, 25.0, "Europe");
+/// ```
+pub struct ScatterPlot {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: String,
+    sized: bool,
+    grouped: bool,
+    color_rules: Vec<(String, String)>,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl ScatterPlot {
+    pub fn new() -> ScatterPlot {
+        let mut plot = ScatterPlot {
+            identifier: String::from("scatter_plot"),
+            description: String::from("Scatter Plot"),
+            width: 500,
+            height: 400,
+            padding: 5,
+            data: vec![DataSet::from_values("table", vec![])],
+            color: String::from("steelblue"),
+            sized: false,
+            grouped: false,
+            color_rules: vec![],
+            scales: vec![
+                Scale::new("x", ScaleType::Linear, Domain::field("table", "x"), "width"),
+                Scale::new("y", ScaleType::Linear, Domain::field("table", "y"), "height"),
+                Scale::with_range(
+                    "size",
+                    ScaleType::Linear,
+                    Domain::field("table", "size"),
+                    Range::numbers(&[16.0, 400.0]),
+                ),
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "group"),
+                    "category",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        plot.scales[0].include_zero(false);
+        plot.scales[1].include_zero(false);
+        plot.update_marks();
+        plot
+    }
+
+    /// Rebuilds the dots, which are only scaled by size and colored by group once a point has
+    /// one
+    fn update_marks(&mut self) {
+        let size: Production = if self.sized {
+            when("isValid(datum.size)")
+                .then(ValueRef::scaled("size", "size"))
+                .otherwise(ValueRef::value(40))
+        } else {
+            ValueRef::value(40).into()
+        };
+        let mut fill: Production = if self.grouped {
+            when("isValid(datum.group)")
+                .then(ValueRef::scaled("color", "group"))
+                .otherwise(ValueRef::value(self.color.as_str()))
+        } else {
+            ValueRef::value(self.color.as_str()).into()
+        };
+        for &(ref test, ref color) in self.color_rules.iter().rev() {
+            fill = fill.first_when(test, ValueRef::value(color.as_str()));
+        }
+        self.marks = vec![
+            Mark::new(MarkType::Symbol)
+                .from_data("table")
+                .enter("x", ValueRef::scaled("x", "x"))
+                .enter("y", ValueRef::scaled("y", "y"))
+                .enter("size", size)
+                .enter("fill", fill)
+                .enter("fillOpacity", ValueRef::value(0.7)),
+        ];
+    }
+
+    fn point(x: f64, y: f64, size: Option<f64>, group: Option<&str>) -> Value {
+        let mut m = Map::new();
+        m.insert(String::from("x"), Value::from(x));
+        m.insert(String::from("y"), Value::from(y));
+        if let Some(size) = size {
+            m.insert(String::from("size"), Value::from(size));
+        }
+        if let Some(group) = group {
+            m.insert(String::from("group"), Value::from(group));
+        }
+        Value::Object(m)
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a point at the given x and y value
+    pub fn add_data(&mut self, x: f64, y: f64) {
+        self.data[0].add_value(ScatterPlot::point(x, y, None, None));
+    }
+
+    /// Adds every (x, y) point of the iterator, as `add_data` would
+    pub fn add_data_iter<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (f64, f64)>,
+    {
+        let iter = iter.into_iter();
+        self.data[0].values_mut().reserve(iter.size_hint().0);
+        for (x, y) in iter {
+            self.add_data(x, y);
+        }
+    }
+
+    /// Adds a point whose dot is scaled by `size`. The dots of the largest and the smallest
+    /// size in the plot span 400 and 16 square pixels, points without a size keep spanning 40.
+    pub fn add_sized_data(&mut self, x: f64, y: f64, size: f64) {
+        self.data[0].add_value(ScatterPlot::point(x, y, Some(size), None));
+        if !self.sized {
+            self.sized = true;
+            self.update_marks();
+        }
+    }
+
+    /// Adds a point whose dot is scaled by `size`, like `add_sized_data`, and colored by
+    /// `group`. Every group gets a color of its own, which a legend explains.
+    pub fn add_bubble(&mut self, x: f64, y: f64, size: f64, group: &str) {
+        self.data[0].add_value(ScatterPlot::point(x, y, Some(size), Some(group)));
+        if !self.grouped {
+            self.grouped = true;
+            self.sized = true;
+            self.extras.add_legend(Legend::fill("color"));
+            self.update_marks();
+        }
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+
+    /// Overlays a dashed trend line, fitted with the given regression method. With `label`
+    /// set, the fitted equation is printed in the corner of the chart.
+    pub fn add_trendline(&mut self, method: Method, label: bool) {
+        overlay::add_trendline(&mut self.extras, &PLOT, method, label);
+    }
+
+    /// Overlays a loess curve, see `LineChart::add_loess`
+    pub fn add_loess(&mut self, bandwidth: f64) {
+        overlay::add_loess(&mut self.extras, &PLOT, bandwidth);
+    }
+
+    /// Prints the y value of every point next to it, e.g. `show_values(LabelPosition::OutsideEnd)`
+    /// for labels above the points. On crowded charts, `ValueLabels::avoid_overlap` keeps the
+    /// labels readable.
+    pub fn show_values<L: Into<ValueLabels>>(&mut self, labels: L) {
+        overlay::add_value_labels(&mut self.extras, &PLOT, &labels.into());
+    }
+
+    /// Colors the points whose data passes the predicate with `color`, ahead of their group
+    /// color. The fields of the points are `x`, `y`, `size` and `group`. Where several
+    /// predicates pass, the one added first wins.
+    pub fn color_when(&mut self, predicate: Predicate, color: &str) {
+        self.color_rules.push((predicate.expression(), String::from(color)));
+        self.update_marks();
+    }
+
+    /// Shows a tooltip with the values of the point closest to the pointer, wherever it is on
+    /// the chart. This should be added last, so its hover targets are above all other marks.
+    pub fn add_voronoi(&mut self) {
+        overlay::add_voronoi(&mut self.extras, &PLOT);
+    }
+
+    /// Lets the points be stepped through with the arrow keys, in the order of x, see
+    /// `overlay::add_keyboard_navigation`
+    pub fn add_keyboard_navigation(&mut self) {
+        overlay::add_keyboard_navigation(&mut self.extras, &PLOT);
+    }
+}
+impl Serialize for ScatterPlot {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("scatter_plot", 8)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for ScatterPlot {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color colors the points without a group
+    fn set_color(&mut self, color: &str) {
+        self.color = String::from(color);
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for ScatterPlot {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
@@ -0,0 +1,281 @@
+/*!
+Loading existing Vega specifications, to change their data or properties and write them out
+again.
+
+The charts of gust can only be written, a specification isn't turned back into a chart type
+like `BarChart`. Instead a loaded specification is kept as json: `Spec` gives access to its
+data sets by name and to the properties at its top level. It is also `Graphable`, so scales,
+marks, legends or a title can be added to it like to any chart, and it can be rendered or
+saved like one.
+
+```rust
+use gust::backend::bar_chart::BarChart;
+use gust::backend::spec::Spec;
+use gust::backend::traits::Graphable;
+
+let json = BarChart::new().get_json_representation();
+let mut spec = Spec::from_json_str(&json).unwrap();
+spec.set_values("table", vec![]);
+spec.set("width", 600.into());
+spec.set_title("Revenue");
+let json = spec.to_json_string();
+```
+ */
+
+use backend::elements::extras::Extras;
+use backend::elements::scale::Scale;
+use backend::elements::transform::Transform;
+use backend::traits::Graphable;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Error, Serialize, Serializer};
+use serde_json;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Spec is a Vega specification that can be changed after it has been created, e.g. by loading
+/// it from a file or by taking over the specification of a chart. Whatever is added to it
+/// through `Graphable` is merged into the specification when it is written.
+pub struct Spec {
+    spec: Value,
+    identifier: String,
+    scales: Vec<Scale>,
+    transforms: Vec<Transform>,
+    extras: Extras,
+}
+
+impl Spec {
+    fn new(spec: Value) -> Spec {
+        Spec {
+            spec,
+            identifier: String::from("spec"),
+            scales: vec![],
+            transforms: vec![],
+            extras: Extras::new(),
+        }
+    }
+
+    /// Parses a Vega specification
+    pub fn from_json_str(json: &str) -> Result<Spec, serde_json::Error> {
+        serde_json::from_str(json).map(Spec::new)
+    }
+
+    /// The specification of the chart, including everything that was added on top of it
+    pub fn from_chart<G: Graphable>(graph: &G) -> Spec {
+        Spec::new(serde_json::to_value(graph).unwrap())
+    }
+
+    /// The specification with the scales, transforms, marks and other elements that were added
+    /// through `Graphable` merged into it. Added transforms go to the first data set.
+    fn merged(&self) -> Result<Value, serde_json::Error> {
+        let mut spec = self.spec.clone();
+        if let Value::Object(ref mut object) = spec {
+            if !self.scales.is_empty() {
+                append(object, "scales", serde_json::to_value(&self.scales)?);
+            }
+            if !self.transforms.is_empty() {
+                let first = object
+                    .get_mut("data")
+                    .and_then(|d| d.as_array_mut())
+                    .and_then(|d| d.first_mut())
+                    .and_then(|d| d.as_object_mut());
+                if let Some(first) = first {
+                    append(first, "transform", serde_json::to_value(&self.transforms)?);
+                }
+            }
+            self.extras.merge_into(object)?;
+        }
+        Ok(spec)
+    }
+
+    /// The property at the top level of the specification, e.g. "width" or "title"
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.spec.get(key)
+    }
+
+    /// Sets the property at the top level of the specification
+    pub fn set(&mut self, key: &str, value: Value) {
+        if let Some(object) = self.spec.as_object_mut() {
+            object.insert(String::from(key), value);
+        }
+    }
+
+    /// The names of all data sets
+    pub fn data_names(&self) -> Vec<&str> {
+        self.spec
+            .get("data")
+            .and_then(|d| d.as_array())
+            .map(|d| {
+                d.iter()
+                    .filter_map(|d| d.get("name").and_then(|n| n.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn data_mut(&mut self, name: &str) -> Option<&mut Value> {
+        self.spec
+            .get_mut("data")
+            .and_then(|d| d.as_array_mut())
+            .and_then(|d| {
+                d.iter_mut()
+                    .find(|d| d.get("name").and_then(|n| n.as_str()) == Some(name))
+            })
+    }
+
+    /// The values of the data set with the given name, unless it has none, e.g. because it
+    /// loads them from a url
+    pub fn values(&self, name: &str) -> Option<&Vec<Value>> {
+        self.spec
+            .get("data")
+            .and_then(|d| d.as_array())
+            .and_then(|d| {
+                d.iter()
+                    .find(|d| d.get("name").and_then(|n| n.as_str()) == Some(name))
+            })
+            .and_then(|d| d.get("values"))
+            .and_then(|v| v.as_array())
+    }
+
+    /// Gives access to the values of the data set with the given name, see `values`
+    pub fn values_mut(&mut self, name: &str) -> Option<&mut Vec<Value>> {
+        self.data_mut(name)
+            .and_then(|d| d.get_mut("values"))
+            .and_then(|v| v.as_array_mut())
+    }
+
+    /// Replaces the values of the data set with the given name. A data set that loaded its
+    /// values from a url holds the given values instead. Returns false if there is no such
+    /// data set.
+    pub fn set_values(&mut self, name: &str, values: Vec<Value>) -> bool {
+        match self.data_mut(name).and_then(|d| d.as_object_mut()) {
+            Some(data) => {
+                data.remove("url");
+                data.remove("format");
+                data.insert(String::from("values"), Value::Array(values));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The specification as json, with everything that was added to it
+    pub fn to_json_string(&self) -> String {
+        self.get_json_representation()
+    }
+
+    /// The top level object of the specification, which replaces it if it isn't an object
+    fn object_mut(&mut self) -> &mut Map<String, Value> {
+        as_object(&mut self.spec)
+    }
+}
+
+/// The json object `value` holds, which replaces it if it holds anything else
+fn as_object(value: &mut Value) -> &mut Map<String, Value> {
+    if !value.is_object() {
+        *value = Value::Object(Map::new());
+    }
+    match *value {
+        Value::Object(ref mut object) => object,
+        _ => unreachable!(),
+    }
+}
+
+/// The object `key` of `object`, which is created if `key` holds anything else
+fn object_entry<'a>(object: &'a mut Map<String, Value>, key: &str) -> &'a mut Map<String, Value> {
+    as_object(object.entry(String::from(key)).or_insert(Value::Null))
+}
+
+/// Appends the elements of the json array `added` to the array `key` of `object`
+fn append(object: &mut Map<String, Value>, key: &str, added: Value) {
+    let list = object
+        .entry(String::from(key))
+        .or_insert_with(|| Value::Array(vec![]));
+    if let (&mut Value::Array(ref mut list), Value::Array(added)) = (list, added) {
+        list.extend(added);
+    }
+}
+
+impl Serialize for Spec {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.merged().map_err(S::Error::custom)?.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Spec {
+    fn deserialize<D>(deserializer: D) -> Result<Spec, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Value::deserialize(deserializer).map(Spec::new)
+    }
+}
+
+/// Cloning a specification keeps everything that was added to it, merged into the json
+impl Clone for Spec {
+    fn clone(&self) -> Spec {
+        let mut spec = Spec::from_chart(self);
+        spec.identifier = self.identifier.clone();
+        spec
+    }
+}
+
+/// Two specifications are equal when they are written out as the same json
+impl PartialEq for Spec {
+    fn eq(&self, other: &Spec) -> bool {
+        self.merged().ok() == other.merged().ok()
+    }
+}
+
+impl fmt::Debug for Spec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Spec({})", self.get_json_representation())
+    }
+}
+
+impl Graphable for Spec {
+    /// The description of the specification, or an empty one
+    fn get_description(&self) -> &str {
+        self.spec
+            .get("description")
+            .and_then(|d| d.as_str())
+            .unwrap_or("")
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color sets the default color of all marks through the config of the specification
+    fn set_color(&mut self, color: &str) {
+        let config = object_entry(self.object_mut(), "config");
+        object_entry(config, "mark").insert(String::from("color"), Value::from(color));
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.set("height", t.0.into());
+        self.set("width", t.1.into());
+    }
+    /// The dimensions of the specification as (height, width), 0 where they aren't numbers
+    fn get_dimensions(&self) -> (i32, i32) {
+        let size = |key| self.spec.get(key).and_then(|v| v.as_i64()).unwrap_or(0) as i32;
+        (size("height"), size("width"))
+    }
+    fn set_description(&mut self, description: &str) {
+        self.set("description", description.into());
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    /// The scales that were added to the specification, which follow its own
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms that were added to the first data set of the specification, which
+    /// follow its own
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        &mut self.transforms
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+}
@@ -0,0 +1,247 @@
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::legend::Legend;
+use backend::elements::mark::{Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::{StackOffset, Transform};
+use backend::elements::general::{Interpolation, Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// A stacked area chart draws the values of every series as an area on top of the areas of
+/// the series before it. With `StackOffset::Wiggle` the stacks flow around a moving baseline,
+/// which turns the chart into a stream graph.
+/// # Example:
+/// ```rust
+///    use gust::backend::stacked_area_chart::StackedAreaChart;
+///    use gust::backend::elements::transform::StackOffset;
+///
+///    let mut s = StackedAreaChart::new();
+///    for i in 0..10 {
+///        s.add_data(i as f64, "rock", (i * 2) as f64);
+///        s.add_data(i as f64, "jazz", (10 - i) as f64);
+///    }
+///    s.set_stack_offset(StackOffset::Wiggle);
+/// ```
+pub struct StackedAreaChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: Option<String>,
+    interpolation: Interpolation,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl StackedAreaChart {
+    pub fn new() -> StackedAreaChart {
+        let mut table = DataSet::from_values("table", vec![]);
+        table.add_transform(Transform::stack("x", "series", "value"));
+
+        let mut chart = StackedAreaChart {
+            identifier: String::from("stacked_area_chart"),
+            description: String::from("Stacked Area Chart"),
+            width: 500,
+            height: 300,
+            padding: 5,
+            data: vec![table],
+            color: None,
+            interpolation: Interpolation::Monotone,
+            scales: vec![
+                Scale::new("x", ScaleType::Linear, Domain::field("table", "x"), "width"),
+                Scale::new(
+                    "y",
+                    ScaleType::Linear,
+                    Domain::fields("table", &["y0", "y1"]),
+                    "height",
+                ),
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "series"),
+                    "category",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[0].include_zero(false);
+        chart.extras.add_legend(Legend::fill("color"));
+        chart.update_marks();
+        chart
+    }
+
+    /// Rebuilds the areas, one per series
+    fn update_marks(&mut self) {
+        let fill = match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", "series"),
+        };
+        let area = Mark::new(MarkType::Area)
+            .from_data("series")
+            .enter("x", ValueRef::scaled("x", "x"))
+            .enter("y", ValueRef::scaled("y", "y0"))
+            .enter("y2", ValueRef::scaled("y", "y1"))
+            .enter("fill", fill)
+            .enter("interpolate", ValueRef::value(self.interpolation.name()))
+            .enter("tooltip", ValueRef::signal("datum.series"))
+            .update("fillOpacity", ValueRef::value(1))
+            .hover("fillOpacity", ValueRef::value(0.5));
+        self.marks = vec![
+            Mark::new(MarkType::Group)
+                .facet("series", "table", "series")
+                .mark(area),
+        ];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds the value of a series at x. Every series should have a value at each x, otherwise
+    /// the layers above it are stacked with a gap.
+    pub fn add_data(&mut self, x: f64, series: &str, value: f64) {
+        let mut m = Map::new();
+        m.insert(String::from("series"), Value::from(series));
+        m.insert(String::from("value"), Value::from(value));
+        m.insert(String::from("x"), Value::from(x));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Adds every (x, series, value) triple of the iterator, as `add_data` would
+    pub fn add_data_iter<I, S>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (f64, S, f64)>,
+        S: AsRef<str>,
+    {
+        let iter = iter.into_iter();
+        self.data[0].values_mut().reserve(iter.size_hint().0);
+        for (x, series, value) in iter {
+            self.add_data(x, series.as_ref(), value);
+        }
+    }
+
+    /// Sets the baseline of the stacks, see `StackedBarChart::set_stack_offset`.
+    /// `StackOffset::Wiggle` lays the chart out as a stream graph, where only the thickness of
+    /// the layers carries meaning, not their position on the y axis.
+    pub fn set_stack_offset(&mut self, offset: StackOffset) {
+        if let StackOffset::Normalize = offset {
+            self.axes[1].set_format(".0%");
+        }
+        if let Transform::Stack { offset: ref mut o, .. } = self.data[0].transforms_mut()[0] {
+            *o = Some(offset);
+        }
+    }
+
+    /// Sets how the areas are drawn in between the data points, monotone by default
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+        self.update_marks();
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for StackedAreaChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("stacked_area_chart", 9)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for StackedAreaChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color gives every series of the chart the same color
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the chart's data, which run after the stack transform
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for StackedAreaChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
@@ -4,10 +4,19 @@
  */
 
 use backend::elements::stacked_bar_chart::*;
-use backend::traits::Graphable;
+use backend::elements::axis::Axis;
+use backend::elements::extras::Extras;
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::{StackOffset, Transform};
+use backend::elements::general::{Orientation, SCHEMA};
+use backend::elements::legend::Legend;
+use backend::general::{Category, Numeric};
+use backend::traits::{Cartesian, Graphable};
 
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
+/// The format of the y axis of normalized stacks
+const PERCENT: &'static str = ".0%";
 
 pub struct StackedBarChart {
     identifier: String,
@@ -18,15 +27,16 @@ pub struct StackedBarChart {
 
     data: Vec<StackedBarData>,
 
-    scales: Vec<StackedBarScale>,
-    axes: Vec<StackedBarAxis>,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
     marks: Vec<StackedBarMark>,
+    extras: Extras,
 }
 
 
 impl StackedBarChart {
     pub fn new() -> StackedBarChart {
-        StackedBarChart {
+        let mut chart = StackedBarChart {
             identifier: String::from("stacked_bar_chart"),
             description: String::from("Stacked Bar Chart"),
             width: 500,
@@ -35,13 +45,24 @@ impl StackedBarChart {
             data: vec![StackedBarData::new()],
 
             scales: vec![
-                StackedBarScale::new_xscale(),
-                StackedBarScale::new_yscale(),
-                StackedBarScale::new_ordinal_scale(),
+                Scale::new("x", ScaleType::Band, Domain::field("table", "x"), "width"),
+                Scale::new("y", ScaleType::Linear, Domain::field("table", "y1"), "height"),
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "z"),
+                    "category",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
             ],
-            axes: vec![StackedBarAxis::new_xaxis(), StackedBarAxis::new_yaxis()],
             marks: vec![StackedBarMark::new()],
-        }
+            extras: Extras::new(),
+        };
+        chart.extras.add_legend(Legend::fill("color"));
+        chart
     }
 
     pub fn set_identifier(&mut self, id: &str) {
@@ -65,9 +86,9 @@ impl StackedBarChart {
 
     /// adds data to the stacked_bar chart
     /// x value denotes which bar the value is supposed to be on.
-    /// y values denotes the height of the bar
+    /// y values denotes the height of the bar, and can be any kind of number
     /// z is the stratification variable, meaning you can separate multiple stacked bars based on
-    /// z.
+    /// z. Both x and z can be integers or text.
     /// # Example:
     /// ```rust
     ///    use gust::backend::stacked_bar_chart::StackedBarChart;
@@ -77,11 +98,43 @@ impl StackedBarChart {
     ///        b.add_data(i, i * i, 1);
     ///        b.add_data(i, i + i, 0);
     ///    }
+    ///
+    ///    let mut sales = StackedBarChart::new();
+    ///    sales.add_data("North", 12.5, "Q1");
+    ///    sales.add_data("North", 7, "Q2");
     /// ```
     /// Here, we see there are two values entered for each i, one with a 1 value for z, and one with
-    /// a zero value. This is how gust splits the stacked bars into two.
-    pub fn add_data(&mut self, x: i64, y: i64, z: i64) {
-        self.data[0].add_data(x, y, z);
+    /// a zero value. This is how gust splits the stacked bars into two. Each z value is colored
+    /// by the "color" scale, which the legend of the chart explains.
+    pub fn add_data<X: Category, Y: Numeric, Z: Category>(&mut self, x: X, y: Y, z: Z) {
+        self.data[0].add_data(x.into_value(), y.as_f64(), z.into_value());
+    }
+
+    /// Adds every (x, y, z) value of the iterator, as `add_data` would
+    pub fn add_data_iter<I, X, Y, Z>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (X, Y, Z)>,
+        X: Category,
+        Y: Numeric,
+        Z: Category,
+    {
+        let iter = iter.into_iter();
+        self.data[0].reserve(iter.size_hint().0);
+        for (x, y, z) in iter {
+            self.add_data(x, y, z);
+        }
+    }
+
+    /// Sets the baseline of the stacks. With `StackOffset::Normalize` every bar spans the full
+    /// height of the chart and shows the proportion of each z value, so the y axis is
+    /// switched to percentages as well, and back when another offset is set.
+    pub fn set_stack_offset(&mut self, offset: StackOffset) {
+        match offset {
+            StackOffset::Normalize => self.axes[1].set_format(PERCENT),
+            _ if self.axes[1].format() == Some(PERCENT) => self.axes[1].clear_format(),
+            _ => (),
+        }
+        self.data[0].set_stack_offset(offset);
     }
 }
 impl Serialize for StackedBarChart {
@@ -90,18 +143,16 @@ impl Serialize for StackedBarChart {
         S: Serializer,
     {
         let mut s = serializer.serialize_struct("sb_graph", 9)?;
-        s.serialize_field(
-            "$schema",
-            "https://vega.github.io/schema/vega/v3.0.json",
-        )?;
+        s.serialize_field("$schema", SCHEMA)?;
         s.serialize_field("width", &self.width)?;
         s.serialize_field("height", &self.height)?;
         s.serialize_field("padding", &self.padding)?;
 
-        s.serialize_field("data", &self.data)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
         s.serialize_field("scales", &self.scales)?;
         s.serialize_field("axes", &self.axes)?;
-        s.serialize_field("marks", &self.marks)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
         s.end()
     }
 }
@@ -113,4 +164,49 @@ impl Graphable for StackedBarChart {
     fn get_identifier(&self) -> &str {
         &self.identifier
     }
+    /// set_color gives every series of the chart the same color
+    fn set_color(&mut self, color: &str) {
+        self.marks[0].set_color(color);
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for StackedBarChart {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
 }
@@ -0,0 +1,110 @@
+/*!
+Dates and times on the x axis, which needs the `chrono` feature of gust.
+
+Timestamps are emitted into the specification as milliseconds since the unix epoch, which is
+how Vega represents time. Adding the first timestamp to a chart turns its x scale into a UTC
+time scale, whose ticks Vega labels with a date or time depending on the span of the data.
+Dates and times without a time zone are read as UTC.
+
+```rust
+extern crate chrono;
+extern crate gust;
+
+use chrono::NaiveDate;
+use gust::backend::line_chart::LineChart;
+use gust::backend::traits::Graphable;
+
+fn main() {
+    let mut c = LineChart::new();
+    c.add_time_data(NaiveDate::from_ymd_opt(2018, 3, 1).unwrap(), 12.5, 0);
+    c.add_time_data(NaiveDate::from_ymd_opt(2018, 3, 2).unwrap(), 14.0, 0);
+    assert!(c.get_json_representation().contains("\"x\":1519862400000.0"));
+}
+```
+ */
+
+use backend::area_chart::AreaChart;
+use backend::elements::scale::ScaleType;
+use backend::general::Numeric;
+use backend::line_chart::LineChart;
+use backend::traits::Cartesian;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone};
+
+/// Temporal is implemented by the chrono types that can be placed on a time axis
+pub trait Temporal: Copy {
+    /// Milliseconds since the unix epoch
+    fn epoch_millis(self) -> i64;
+}
+
+impl Temporal for NaiveDateTime {
+    fn epoch_millis(self) -> i64 {
+        self.and_utc().timestamp_millis()
+    }
+}
+
+impl Temporal for NaiveDate {
+    fn epoch_millis(self) -> i64 {
+        self.and_hms_opt(0, 0, 0).unwrap().epoch_millis()
+    }
+}
+
+impl<Tz: TimeZone> Temporal for DateTime<Tz>
+where
+    Tz::Offset: Copy,
+{
+    fn epoch_millis(self) -> i64 {
+        self.naive_utc().epoch_millis()
+    }
+}
+
+/// Turns the x scale of the chart into a UTC time scale, labeling its ticks in UTC as well,
+/// unless it already is a time scale
+fn use_time_axis<C: Cartesian>(chart: &mut C) {
+    match chart.x_scale_mut().scale_type() {
+        ScaleType::Time | ScaleType::Utc => (),
+        _ => {
+            chart.x_scale_mut().set_scale_type(ScaleType::Utc);
+            chart.x_axis_mut().set_utc(true);
+        }
+    }
+}
+
+impl LineChart {
+    /// Adds a point at the time x to the series z, like `add_data`, and switches the x axis to
+    /// a time axis
+    pub fn add_time_data<X: Temporal, Y: Numeric>(&mut self, x: X, y: Y, z: i64) {
+        use_time_axis(self);
+        self.add_data(x.epoch_millis(), y, z);
+    }
+
+    /// Adds every (time, y, z) point of the iterator, as `add_time_data` would
+    pub fn add_time_data_iter<I, X, Y>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (X, Y, i64)>,
+        X: Temporal,
+        Y: Numeric,
+    {
+        use_time_axis(self);
+        self.add_data_iter(iter.into_iter().map(|(x, y, z)| (x.epoch_millis(), y, z)));
+    }
+}
+
+impl AreaChart {
+    /// Adds a point at the time u with a height of v, like `add_data`, and switches the x axis
+    /// to a time axis
+    pub fn add_time_data<U: Temporal, V: Numeric>(&mut self, u: U, v: V) {
+        use_time_axis(self);
+        self.add_data(u.epoch_millis(), v);
+    }
+
+    /// Adds every (time, v) point of the iterator, as `add_time_data` would
+    pub fn add_time_data_iter<I, U, V>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (U, V)>,
+        U: Temporal,
+        V: Numeric,
+    {
+        use_time_axis(self);
+        self.add_data_iter(iter.into_iter().map(|(u, v)| (u.epoch_millis(), v)));
+    }
+}
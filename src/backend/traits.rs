@@ -1,13 +1,101 @@
 
 
+use backend::elements::axis::{Axis, AxisLabels, AxisStyle, TickFormat};
+use backend::elements::color::ColorScheme;
+use backend::elements::config::Config;
+use backend::elements::data::{DataSet, Format};
+use backend::elements::extras::Extras;
+use backend::elements::general::Orientation;
+use backend::elements::geo::Projection;
+use backend::elements::legend::{Legend, LegendKind};
+use backend::elements::mark::{Align, Mark};
+use backend::elements::overlay::{self, Corner, Shading, Watermark};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::signal::Signal;
+use backend::elements::theme::Theme;
+use backend::elements::title::Title;
+use backend::elements::tooltip::Tooltip;
+use backend::elements::transform::Transform;
+#[cfg(feature = "export")]
+use frontend::export;
+use frontend::render;
 use serde::ser::Serialize;
 use serde_json;
+use serde_json::Value;
+use std;
+use std::io::Write;
+use std::path::Path;
 
 pub trait Graphable: Sized + Serialize {
     fn get_json_representation(&self) -> String {
         serde_json::to_string(self).unwrap()
     }
 
+    /// The Vega specification of the chart as json
+    fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Writes the Vega specification of the chart as json to `writer`
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.add_data(String::from("A"), 3);
+    ///    let mut spec = vec![];
+    ///    b.to_writer(&mut spec).unwrap();
+    /// ```
+    fn to_writer<W: Write>(&self, writer: W) -> Result<(), serde_json::Error> {
+        serde_json::to_writer(writer, self)
+    }
+
+    /// Writes the Vega specification of the chart as json to the file at `path`, creating the
+    /// folders leading up to it
+    fn save_json<P: AsRef<Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.to_writer(&mut file)?;
+        file.flush()
+    }
+
+    /// Writes the chart to the file at `path` as a self-contained html page, see
+    /// `render::to_html`
+    /// # Example:
+    /// ```rust,no_run
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.add_data(String::from("A"), 3);
+    ///    b.save_html("out.html").unwrap();
+    /// ```
+    fn save_html<P: AsRef<Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        render::save_html(self, path)
+    }
+
+    /// Renders the chart to a png image at `path`, `scale` times its size, see `export`
+    #[cfg(feature = "export")]
+    fn save_png<P: AsRef<Path>>(&self, path: P, scale: f64) -> Result<(), std::io::Error> {
+        export::save_png(self, path, scale)
+    }
+
+    /// Renders the chart to an svg image at `path`, see `export`
+    #[cfg(feature = "export")]
+    fn save_svg<P: AsRef<Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        export::save_svg(self, path)
+    }
+
+    /// Opens the chart in the default browser of the system, through an html page written to
+    /// the temporary directory (see `render::preview_path`)
+    fn show(&self) -> Result<(), std::io::Error> {
+        render::show(self)
+    }
+
     fn get_description(&self) -> &str;
     fn get_identifier(&self) -> &str;
 
@@ -22,4 +110,447 @@ pub trait Graphable: Sized + Serialize {
     fn set_description(&mut self, &str);
 
     fn set_identifier(&mut self, &str);
+
+    /// Gives access to all of the scales of the chart
+    fn scales_mut(&mut self) -> &mut Vec<Scale>;
+
+    /// Adds another scale to the chart, e.g. a threshold scale mapping values onto colors
+    fn add_scale(&mut self, scale: Scale) {
+        self.scales_mut().push(scale);
+    }
+
+    /// Gives access to the transforms that are run over the data of the chart
+    fn transforms_mut(&mut self) -> &mut Vec<Transform>;
+
+    /// Appends a transform to the data of the chart
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///    use gust::backend::elements::transform::{TimeUnit, Transform};
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    c.add_transform(Transform::timeunit("x", TimeUnit::Hour));
+    /// ```
+    fn add_transform(&mut self, transform: Transform) {
+        self.transforms_mut().push(transform);
+    }
+
+    /// Gives access to the data sets and marks that were added on top of the chart
+    fn extras_mut(&mut self) -> &mut Extras;
+
+    /// Adds a data set to the chart, which added marks can draw from
+    fn add_data_set(&mut self, data: DataSet) {
+        self.extras_mut().add_data_set(data);
+    }
+
+    /// Makes Vega load the data of the chart from `url` when the chart is drawn, instead of
+    /// embedding the values that were added to it. The data objects at the url need the fields
+    /// the chart's own data has, e.g. `category` and `amount` for a bar chart. Charts with
+    /// several data sets of their own load the first one from the url.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::elements::data::Format;
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.set_data_url("https://example.com/sales.json", Format::json());
+    /// ```
+    fn set_data_url(&mut self, url: &str, format: Format) {
+        self.extras_mut().set_data_url(url, format);
+    }
+
+    /// Fills the chart with the given records instead of the data added to it, e.g. a vector of
+    /// structs deriving `Serialize`. The records are embedded as they are, and `fields` maps the
+    /// fields of the chart's own data onto fields of the records as (chart field, record field)
+    /// pairs. Charts with several data sets of their own fill the first one with the records.
+    /// # Example:
+    /// ```rust
+    ///    #[macro_use]
+    ///    extern crate serde_derive;
+    ///    extern crate gust;
+    ///
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    #[derive(Serialize)]
+    ///    struct Sale {
+    ///        region: String,
+    ///        revenue: f64,
+    ///    }
+    ///
+    ///    fn main() {
+    ///        let sales = vec![
+    ///            Sale { region: String::from("North"), revenue: 12.5 },
+    ///            Sale { region: String::from("South"), revenue: 7.0 },
+    ///        ];
+    ///        let mut b = BarChart::new();
+    ///        b.set_records(&sales, &[("category", "region"), ("amount", "revenue")]).unwrap();
+    ///    }
+    /// ```
+    fn set_records<T: Serialize>(
+        &mut self,
+        records: &[T],
+        fields: &[(&str, &str)],
+    ) -> Result<(), serde_json::Error> {
+        let mut values = Vec::with_capacity(records.len());
+        for record in records {
+            values.push(serde_json::to_value(record)?);
+        }
+        self.extras_mut().set_records(values, fields);
+        Ok(())
+    }
+
+    /// Draws an additional mark on top of the marks of the chart
+    fn add_mark(&mut self, mark: Mark) {
+        self.extras_mut().add_mark(mark);
+    }
+
+    /// Colors the series or values of the chart with the given scheme instead of Vega's
+    /// default one, by setting the range of its color scale. Charts without a color scale,
+    /// and ones drawn in a single color through `set_color`, are left as they are.
+    fn set_colors(&mut self, scheme: ColorScheme) {
+        for scale in self.scales_mut().iter_mut() {
+            if scale.name() == "color" && scale.scale_type() != ScaleType::Threshold {
+                scale.set_range(scheme.range());
+            }
+        }
+    }
+
+    /// Adds a signal to the chart, which added marks and transforms can refer to
+    fn add_signal(&mut self, signal: Signal) {
+        self.extras_mut().add_signal(signal);
+    }
+
+    /// Styles the chart with the given theme, replacing any earlier theme
+    fn set_theme(&mut self, theme: Theme) {
+        self.extras_mut().set_theme(theme);
+    }
+
+    /// Sets the defaults of the marks, axes and legends of the chart, see `Config`. Where a
+    /// theme sets the same properties, the config takes precedence.
+    fn set_config(&mut self, config: Config) {
+        self.extras_mut().set_config(config);
+    }
+
+    /// Describes the chart to screen readers. Unlike `set_description`, which titles the page
+    /// the chart is rendered to, this becomes the ARIA label of the chart itself, and should say
+    /// what the chart shows, e.g. "Monthly revenue, rising from 2M to 3M in 2018".
+    fn set_aria_description(&mut self, description: &str) {
+        self.extras_mut().set_description(description);
+    }
+
+    /// Pins a watermark, text or a logo, to a corner of the chart, on top of all other marks.
+    /// Opacity ranges from 0 for invisible to 1 for opaque, around 0.3 keeps the chart readable.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::elements::overlay::{Corner, Watermark};
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.set_watermark(Watermark::Text(String::from("ACME Corp.")), Corner::BottomRight, 0.3);
+    /// ```
+    fn set_watermark(&mut self, content: Watermark, corner: Corner, opacity: f64) {
+        self.extras_mut().set_watermark(overlay::watermark(content, corner, opacity));
+    }
+
+    /// Adds a map projection, which geopath transforms can draw geographic data through
+    fn add_projection(&mut self, projection: Projection) {
+        self.extras_mut().add_projection(projection);
+    }
+
+    /// Draws a title above the chart. The description of the chart is only used to caption it
+    /// in html pages, it doesn't title the chart itself.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::elements::title::Anchor;
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.set_title("Revenue per Region");
+    ///    b.set_subtitle("Fiscal year 2018");
+    ///    b.title_mut().set_anchor(Anchor::Start);
+    /// ```
+    fn set_title(&mut self, title: &str) {
+        self.title_mut().set_text(title);
+    }
+
+    /// Draws a second line of smaller text below the title of the chart
+    fn set_subtitle(&mut self, subtitle: &str) {
+        self.title_mut().set_subtitle(subtitle);
+    }
+
+    /// Gives access to the title of the chart, e.g. to change its font or placement
+    fn title_mut(&mut self) -> &mut Title {
+        self.extras_mut().title_mut()
+    }
+
+    /// Adds a legend explaining the colors of one of the scales of the chart
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///    use gust::backend::elements::legend::Legend;
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    c.add_legend(Legend::stroke("color").title("Series"));
+    /// ```
+    fn add_legend(&mut self, legend: Legend) {
+        self.extras_mut().add_legend(legend);
+    }
+
+    /// Adds a titled legend explaining the colors, sizes or shapes the chart encodes its data
+    /// with, in place of any legend the chart already has for them. Nothing is added if the
+    /// chart has no scale for the kind of legend, which is reported by returning false.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::scatter_plot::ScatterPlot;
+    ///    use gust::backend::elements::legend::LegendKind;
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    let mut s = ScatterPlot::new();
+    ///    s.add_bubble(2.5, 1.0, 8.0, "Europe");
+    ///    assert!(s.add_legend_for(LegendKind::Size, "Population"));
+    /// ```
+    fn add_legend_for(&mut self, kind: LegendKind, title: &str) -> bool {
+        let name = kind.scale_name();
+        if !self.scales_mut().iter().any(|s| s.name() == name) {
+            return false;
+        }
+        self.extras_mut().replace_legend(name, Legend::of(kind).title(title));
+        true
+    }
+
+    /// Shows a tooltip listing the fields of the data behind a mark when hovering it, on all
+    /// marks that are drawn from data and don't show a tooltip of their own
+    fn enable_tooltips(&mut self) {
+        self.set_tooltip(Tooltip::Datum);
+    }
+
+    /// Shows the given tooltip on all marks that are drawn from data and don't show a tooltip
+    /// of their own, e.g. only some fields of the data, or a custom text
+    fn set_tooltip(&mut self, tooltip: Tooltip) {
+        self.extras_mut().set_tooltip(tooltip);
+    }
+
+    /// Lays the chart out for right-to-left scripts like Hebrew or Arabic: the labels the chart
+    /// draws itself, and those of overlays that are added afterwards, are set right-to-left,
+    /// and charts on x/y axes mirror them (see `Cartesian::mirror_axes`). Text marks added
+    /// through `add_mark` should use `Mark::right_to_left` themselves.
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras_mut().set_right_to_left(rtl);
+    }
+
+    /// Attaches metadata to the spec, like build information, where the data came from or
+    /// internal ids. Vega ignores it, it is kept in the `usermeta` block for downstream tools.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::traits::Graphable;
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.set_metadata("source", "sales.csv");
+    ///    b.set_metadata("revision", 42);
+    /// ```
+    fn set_metadata<V: Into<Value>>(&mut self, key: &str, value: V) {
+        self.extras_mut().set_metadata(key, value.into());
+    }
+}
+
+/// Cartesian is implemented by every chart that is drawn on an x and a y axis. It gives direct
+/// access to those axes and the scales behind them, so they can be configured the same way for
+/// any of these charts.
+/// # Example:
+/// ```rust
+///    use gust::backend::bar_chart::BarChart;
+///    use gust::backend::traits::Cartesian;
+///
+///    let mut b = BarChart::new();
+///    b.x_axis_mut().set_offset(10);
+///    b.y_axis_mut().set_zindex(1);
+///    b.y_scale_mut().include_zero(true);
+/// ```
+pub trait Cartesian: Graphable {
+    fn x_axis_mut(&mut self) -> &mut Axis;
+    fn y_axis_mut(&mut self) -> &mut Axis;
+    fn x_scale_mut(&mut self) -> &mut Scale;
+    fn y_scale_mut(&mut self) -> &mut Scale;
+
+    /// Titles the x axis, see `Axis::set_title` for the font of the title
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///    use gust::backend::traits::Cartesian;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    c.set_x_label("Day");
+    ///    c.set_y_label("Temperature (°C)");
+    ///    c.y_axis_mut().set_title_font_size(14.0);
+    /// ```
+    fn set_x_label(&mut self, label: &str) {
+        self.x_axis_mut().set_title(label);
+    }
+
+    /// Titles the y axis, like `set_x_label`
+    fn set_y_label(&mut self, label: &str) {
+        self.y_axis_mut().set_title(label);
+    }
+
+    /// Sets how the tick labels of the x axis are written, see `TickFormat`
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///    use gust::backend::elements::axis::TickFormat;
+    ///    use gust::backend::traits::Cartesian;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    c.set_x_format(TickFormat::DateTime(String::from("%b %d")));
+    ///    c.set_y_format(TickFormat::Percent(0));
+    ///    c.set_y_format(".1f");
+    /// ```
+    fn set_x_format<F: Into<TickFormat>>(&mut self, format: F) {
+        self.x_axis_mut().set_tick_format(format.into());
+    }
+
+    /// Sets how the tick labels of the y axis are written, like `set_x_format`
+    fn set_y_format<F: Into<TickFormat>>(&mut self, format: F) {
+        self.y_axis_mut().set_tick_format(format.into());
+    }
+
+    /// Applies the label options to the x axis, e.g. to tilt long category names
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::bar_chart::BarChart;
+    ///    use gust::backend::elements::axis::AxisLabels;
+    ///    use gust::backend::traits::Cartesian;
+    ///
+    ///    let mut b = BarChart::new();
+    ///    b.configure_x_axis(&AxisLabels::new().label_angle(-45.0).label_limit(80.0));
+    /// ```
+    fn configure_x_axis(&mut self, labels: &AxisLabels) {
+        self.x_axis_mut().apply_labels(labels);
+    }
+
+    /// Applies the label options to the y axis, like `configure_x_axis`
+    fn configure_y_axis(&mut self, labels: &AxisLabels) {
+        self.y_axis_mut().apply_labels(labels);
+    }
+
+    /// Applies the line options of the style to the x axis, e.g. to draw gridlines
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///    use gust::backend::elements::axis::AxisStyle;
+    ///    use gust::backend::traits::Cartesian;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    c.style_y_axis(&AxisStyle::new().grid(true).grid_color("#eee").domain(false));
+    /// ```
+    fn style_x_axis(&mut self, style: &AxisStyle) {
+        self.x_axis_mut().apply_style(style);
+    }
+
+    /// Applies the line options of the style to the y axis, like `style_x_axis`
+    fn style_y_axis(&mut self, style: &AxisStyle) {
+        self.y_axis_mut().apply_style(style);
+    }
+
+    /// Pins the x axis to run from `min` to `max`, instead of spanning the data. Data outside
+    /// of it is drawn beyond the axis, unless the scale is clamped (see `Scale::set_clamp`).
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///    use gust::backend::traits::Cartesian;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    c.set_y_domain(0.0, 100.0);
+    ///    c.y_scale_mut().set_nice(false);
+    /// ```
+    fn set_x_domain(&mut self, min: f64, max: f64) {
+        self.x_scale_mut().set_domain(Domain::Values(vec![min, max]));
+    }
+
+    /// Pins the y axis to run from `min` to `max`, like `set_x_domain`
+    fn set_y_domain(&mut self, min: f64, max: f64) {
+        self.y_scale_mut().set_domain(Domain::Values(vec![min, max]));
+    }
+
+    /// Changes the type of the x scale, see `set_y_scale_type`
+    fn set_x_scale_type(&mut self, scale_type: ScaleType) {
+        change_scale_type(self, Self::x_scale_mut, scale_type);
+    }
+
+    /// Changes the type of the y scale, e.g. to a log scale for values spanning several orders
+    /// of magnitude. As a log scale can't map zero or negative values, a log scale doesn't
+    /// extend its domain to zero, and data objects without a positive value are left out of
+    /// the chart. Switching to another type again brings both back. Marks starting at zero,
+    /// like bars, are better drawn on a symlog scale.
+    /// # Example:
+    /// ```rust
+    ///    use gust::backend::line_chart::LineChart;
+    ///    use gust::backend::elements::scale::ScaleType;
+    ///    use gust::backend::traits::Cartesian;
+    ///
+    ///    let mut c = LineChart::new();
+    ///    c.add_point(1, 10);
+    ///    c.add_point(2, 10000);
+    ///    c.set_y_scale_type(ScaleType::Log);
+    /// ```
+    fn set_y_scale_type(&mut self, scale_type: ScaleType) {
+        change_scale_type(self, Self::y_scale_mut, scale_type);
+    }
+
+    /// Mirrors the axes for right-to-left scripts: the x axis runs from right to left and the
+    /// y axis moves to the right side of the chart. Without `rtl` the axes are put back the way
+    /// charts draw them by default. Called by `Graphable::set_right_to_left`.
+    fn mirror_axes(&mut self, rtl: bool) {
+        if rtl {
+            self.x_scale_mut().set_reverse(true);
+            self.y_axis_mut().set_orient(Orientation::Right);
+            self.y_axis_mut().set_label_align(Align::Left);
+        } else {
+            self.x_scale_mut().clear_reverse();
+            self.y_axis_mut().set_orient(Orientation::Left);
+            self.y_axis_mut().clear_label_align();
+        }
+    }
+
+    /// Shades the part of the chart between `start` and `end` on the x axis, see
+    /// `overlay::add_region`
+    fn add_region<V: Into<Value>>(&mut self, start: V, end: V, shading: Shading) {
+        let scale = String::from(self.x_scale_mut().name());
+        overlay::add_region(self.extras_mut(), &scale, start.into(), end.into(), shading);
+    }
+
+    /// Shades the part of the chart between `low` and `high` on the y axis, see
+    /// `overlay::add_band`
+    fn add_band<V: Into<Value>>(&mut self, low: V, high: V, shading: Shading) {
+        let scale = String::from(self.y_scale_mut().name());
+        overlay::add_band(self.extras_mut(), &scale, low.into(), high.into(), shading);
+    }
+}
+
+/// Changes the type of the scale `scale` of the chart. A log scale stops extending its domain
+/// to zero (see `Scale::set_scale_type`) and gets a filter for the data objects it can't map,
+/// if its domain spans a single field. Leaving a log scale removes the filter again.
+fn change_scale_type<C, F>(chart: &mut C, scale: F, scale_type: ScaleType)
+where
+    C: Cartesian,
+    F: Fn(&mut C) -> &mut Scale,
+{
+    scale(chart).set_scale_type(scale_type);
+    let expr = match scale(chart).domain_field() {
+        Some(field) => format!("datum[{}] > 0", Value::from(field)),
+        None => return,
+    };
+    chart.transforms_mut().retain(|t| match *t {
+        Transform::Filter { expr: ref e } => *e != expr,
+        _ => true,
+    });
+    if scale_type == ScaleType::Log {
+        chart.add_transform(Transform::filter(&expr));
+    }
 }
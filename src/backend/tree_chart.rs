@@ -0,0 +1,227 @@
+
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::hierarchy;
+use backend::elements::mark::{Align, Baseline, Mark, SymbolShape, ValueRef};
+use backend::elements::scale::{Domain, Scale, ScaleType};
+use backend::elements::transform::{LinkOrient, LinkShape, Transform, TreeMethod};
+use backend::elements::general::SCHEMA;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use backend::traits::Graphable;
+
+/// A tree chart draws a hierarchy of nodes connected by links, such as an org chart or, with
+/// `TreeMethod::Cluster`, a dendrogram. Nodes are colored by their depth in the tree.
+/// # Example:
+/// ```rust
+///    use gust::backend::tree_chart::TreeChart;
+///
+///    let mut t = TreeChart::new();
+///    t.add_node("ceo", "Chief Executive");
+///    t.add_node("cto", "Chief Technology Officer");
+///    t.add_node("cfo", "Chief Financial Officer");
+///    t.add_edge("ceo", "cto");
+///    t.add_edge("ceo", "cfo");
+///    t.add_edge("cto", "Engineering");
+/// ```
+pub struct TreeChart {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    method: TreeMethod,
+    horizontal: bool,
+    color: Option<String>,
+    scales: Vec<Scale>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl TreeChart {
+    pub fn new() -> TreeChart {
+        let mut chart = TreeChart {
+            identifier: String::from("tree_chart"),
+            description: String::from("Tree Chart"),
+            width: 600,
+            height: 400,
+            padding: 5,
+            data: vec![DataSet::from_values("nodes", vec![])],
+            method: TreeMethod::Tidy,
+            horizontal: false,
+            color: None,
+            scales: vec![
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("tree", "depth"),
+                    "category",
+                ),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.update_layout();
+        chart
+    }
+
+    /// Rebuilds the layout of the tree, its links and the marks drawing them
+    fn update_layout(&mut self) {
+        self.data.truncate(1);
+
+        let (size, output, orient) = if self.horizontal {
+            let size = [ValueRef::signal("height"), ValueRef::signal("width - 100")];
+            (size, vec!["y", "x", "depth", "children"], LinkOrient::Horizontal)
+        } else {
+            let size = [ValueRef::signal("width"), ValueRef::signal("height - 20")];
+            (size, vec![], LinkOrient::Vertical)
+        };
+        let mut tree = hierarchy::stratify("tree", "nodes");
+        tree.add_transform(Transform::Tree {
+            method: Some(self.method),
+            size,
+            output: output.into_iter().map(String::from).collect(),
+        });
+        self.data.push(tree);
+
+        let mut links = DataSet::from_source("links", "tree");
+        links.add_transform(Transform::Treelinks);
+        links.add_transform(Transform::Linkpath {
+            orient: Some(orient),
+            shape: Some(LinkShape::Diagonal),
+            require: None,
+            source_x: None,
+            source_y: None,
+            target_x: None,
+            target_y: None,
+        });
+        self.data.push(links);
+
+        let fill = match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", "depth"),
+        };
+        let label = Mark::text(ValueRef::field("name"))
+            .from_data("tree")
+            .at(ValueRef::field("x"), ValueRef::field("y"))
+            .font("sans-serif", 11.0);
+        let label = if self.horizontal {
+            label.align(Align::Left).baseline(Baseline::Middle).shift(8.0, 0.0)
+        } else {
+            label.align(Align::Center).baseline(Baseline::Top).shift(0.0, 8.0)
+        };
+        self.marks = vec![
+            Mark::path(ValueRef::field("path"))
+                .from_data("links")
+                .stroke("#ccc", 1.0),
+            Mark::symbol(SymbolShape::Circle, 100.0)
+                .from_data("tree")
+                .at(ValueRef::field("x"), ValueRef::field("y"))
+                .enter("fill", fill)
+                .stroke("#fff", 1.0),
+            label,
+        ];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a node with the given id, labelled `name`
+    pub fn add_node(&mut self, id: &str, name: &str) {
+        hierarchy::add_node(&mut self.data[0], id, name);
+    }
+
+    /// Links `child` to its parent. Nodes that weren't added before are labelled with their id.
+    /// Every node but the root of the tree needs a parent.
+    pub fn add_edge(&mut self, parent: &str, child: &str) {
+        hierarchy::add_edge(&mut self.data[0], parent, child);
+    }
+
+    /// Switches between a compact layout (`TreeMethod::Tidy`, the default) and a dendrogram
+    pub fn set_method(&mut self, method: TreeMethod) {
+        self.method = method;
+        self.update_layout();
+    }
+
+    /// Grows the tree from left to right instead of from top to bottom
+    pub fn set_horizontal(&mut self, horizontal: bool) {
+        self.horizontal = horizontal;
+        self.update_layout();
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for TreeChart {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("tree_chart", 7)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for TreeChart {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every node in the same color, instead of coloring them by depth
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_layout();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the nodes, which run before they are laid out
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+}
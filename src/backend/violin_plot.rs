@@ -0,0 +1,234 @@
+use backend::elements::axis::Axis;
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Mark, MarkType, ValueRef};
+use backend::elements::scale::{Domain, Range, Scale, ScaleType};
+use backend::elements::transform::{Kde, Transform};
+use backend::elements::general::{Orientation, SCHEMA};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::{Cartesian, Graphable};
+
+/// A violin plot shows the distribution of the samples of every group as a smooth, mirrored
+/// curve: the violin is widest where most of the samples of its group lie. The curve is a
+/// kernel density estimate, computed by Vega when the chart is drawn.
+/// # Example:
+/// ```rust
+///    use gust::backend::violin_plot::ViolinPlot;
+///
+///    let mut v = ViolinPlot::new();
+///    v.add_samples("control", &[4.1, 5.0, 5.2, 5.9, 6.3, 7.0]);
+///    v.add_samples("treated", &[6.2, 6.8, 7.1, 7.4, 8.0, 9.5]);
+///    v.set_bandwidth(0.5);
+/// ```
+pub struct ViolinPlot {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    color: Option<String>,
+    scales: Vec<Scale>,
+    axes: Vec<Axis>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl ViolinPlot {
+    pub fn new() -> ViolinPlot {
+        let mut chart = ViolinPlot {
+            identifier: String::from("violin_plot"),
+            description: String::from("Violin Plot"),
+            width: 500,
+            height: 300,
+            padding: 5,
+            data: vec![
+                DataSet::from_values("table", vec![]),
+                ViolinPlot::density(None),
+            ],
+            color: None,
+            scales: vec![
+                Scale::new("x", ScaleType::Band, Domain::field("table", "group"), "width"),
+                Scale::new("y", ScaleType::Linear, Domain::field("density", "value"), "height"),
+                Scale::with_range(
+                    "density",
+                    ScaleType::Linear,
+                    Domain::field("density", "density"),
+                    Range::signal("[0, bandwidth('x')]"),
+                ),
+                Scale::new(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "group"),
+                    "category",
+                ),
+            ],
+            axes: vec![
+                Axis::new(Orientation::Bottom, "x"),
+                Axis::new(Orientation::Left, "y"),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.scales[0].set_padding(0.1);
+        chart.scales[1].include_zero(false);
+        chart.update_marks();
+        chart
+    }
+
+    /// The data set holding the density of every group, sampled along the values
+    fn density(bandwidth: Option<f64>) -> DataSet {
+        let mut kde = Kde::new("value").groupby("group");
+        if let Some(bandwidth) = bandwidth {
+            kde = kde.bandwidth(bandwidth);
+        }
+        let mut density = DataSet::from_source("density", "table");
+        density.add_transform(Transform::Kde(kde));
+        density
+    }
+
+    /// Rebuilds the violins, one group mark per group
+    fn update_marks(&mut self) {
+        let fill = match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", "group"),
+        };
+        let violin = Mark::new(MarkType::Area)
+            .from_data("violin")
+            .enter("orient", ValueRef::value("horizontal"))
+            .enter("y", ValueRef::scaled("y", "value"))
+            .enter("xc", ValueRef::signal("bandwidth('x') / 2"))
+            .enter("width", ValueRef::scaled("density", "density"))
+            .enter("fill", fill)
+            .enter("interpolate", ValueRef::value("monotone"));
+        self.marks = vec![
+            Mark::new(MarkType::Group)
+                .facet("violin", "density", "group")
+                .enter("x", ValueRef::scaled("x", "group"))
+                .enter("width", ValueRef::signal("bandwidth('x')"))
+                .enter("height", ValueRef::signal("height"))
+                .mark(violin),
+        ];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a single sample to the group with the given name
+    pub fn add_sample(&mut self, group: &str, value: f64) {
+        let mut m = Map::new();
+        m.insert(String::from("group"), Value::from(group));
+        m.insert(String::from("value"), Value::from(value));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Adds all of the samples to the group with the given name
+    pub fn add_samples(&mut self, group: &str, values: &[f64]) {
+        for value in values {
+            self.add_sample(group, *value);
+        }
+    }
+
+    /// Sets the bandwidth of the density estimate, in units of the values. Smaller bandwidths
+    /// follow the samples more closely, Vega estimates one from the samples if it isn't set.
+    pub fn set_bandwidth(&mut self, bandwidth: f64) {
+        self.data[1] = ViolinPlot::density(Some(bandwidth));
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for ViolinPlot {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("violin_plot", 9)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("axes", &self.axes)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for ViolinPlot {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every violin in the same color
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the samples, which run before their density is estimated
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+    fn set_right_to_left(&mut self, rtl: bool) {
+        self.extras.set_right_to_left(rtl);
+        self.mirror_axes(rtl);
+    }
+}
+
+impl Cartesian for ViolinPlot {
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[0]
+    }
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        &mut self.axes[1]
+    }
+    fn x_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[0]
+    }
+    fn y_scale_mut(&mut self) -> &mut Scale {
+        &mut self.scales[1]
+    }
+}
@@ -0,0 +1,209 @@
+use backend::elements::data::DataSet;
+use backend::elements::extras::Extras;
+use backend::elements::mark::{Align, Baseline, Mark, ValueRef};
+use backend::elements::scale::{Domain, Range, Scale, ScaleType};
+use backend::elements::transform::Transform;
+use backend::elements::general::SCHEMA;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{Map, Value};
+use backend::traits::Graphable;
+
+/// A word cloud scatters words across the chart, the heavier a word the larger its font. Words
+/// are placed at random, so the layout changes every time the chart is drawn.
+/// # Example:
+/// ```rust
+///    use gust::backend::word_cloud::WordCloud;
+///
+///    let mut w = WordCloud::new();
+///    w.add_word("rust", 40.0);
+///    w.add_word("vega", 25.0);
+///    w.add_word("chart", 12.0);
+///    w.set_rotations(&[0.0, 90.0]);
+///    w.set_color_scheme("tableau10");
+/// ```
+pub struct WordCloud {
+    identifier: String,
+    description: String,
+    width: u32,
+    height: u32,
+    padding: u32,
+    data: Vec<DataSet>,
+    font: String,
+    color: Option<String>,
+    scales: Vec<Scale>,
+    marks: Vec<Mark>,
+    extras: Extras,
+}
+
+impl WordCloud {
+    pub fn new() -> WordCloud {
+        let mut words = DataSet::from_values("table", vec![]);
+        words.add_transform(WordCloud::rotations(&[-45.0, 0.0, 45.0]));
+
+        let mut chart = WordCloud {
+            identifier: String::from("word_cloud"),
+            description: String::from("Word Cloud"),
+            width: 600,
+            height: 400,
+            padding: 5,
+            data: vec![words],
+            font: String::from("Helvetica Neue, Arial"),
+            color: None,
+            scales: vec![
+                Scale::with_range(
+                    "color",
+                    ScaleType::Ordinal,
+                    Domain::field("table", "text"),
+                    Range::scheme("category10"),
+                ),
+            ],
+            marks: vec![],
+            extras: Extras::new(),
+        };
+        chart.update_marks();
+        chart
+    }
+
+    /// A formula picking one of the angles at random for every word
+    fn rotations(angles: &[f64]) -> Transform {
+        let angles: Vec<String> = angles.iter().map(|a| a.to_string()).collect();
+        let expr = format!(
+            "[{}][floor(random() * {})]",
+            angles.join(", "),
+            angles.len()
+        );
+        Transform::formula(&expr, "angle")
+    }
+
+    /// Rebuilds the text mark and the layout of the words
+    fn update_marks(&mut self) {
+        let fill = match self.color {
+            Some(ref color) => ValueRef::value(color.as_str()),
+            None => ValueRef::scaled("color", "text"),
+        };
+        self.marks = vec![
+            Mark::text(ValueRef::field("text"))
+                .from_data("table")
+                .align(Align::Center)
+                .baseline(Baseline::Alphabetic)
+                .enter("fill", fill)
+                .enter("tooltip", ValueRef::signal("datum.text + ': ' + datum.weight"))
+                .update("fillOpacity", ValueRef::value(1))
+                .hover("fillOpacity", ValueRef::value(0.5))
+                .transform(Transform::Wordcloud {
+                    size: [ValueRef::signal("width"), ValueRef::signal("height")],
+                    text: ValueRef::field("text"),
+                    rotate: Some(ValueRef::field("datum.angle")),
+                    font: self.font.clone(),
+                    font_size: ValueRef::field("datum.weight"),
+                    font_size_range: [12.0, 56.0],
+                    padding: 2.0,
+                }),
+        ];
+    }
+
+    /// Sets the identifier for that graph. The identifier is used to form the
+    /// output file which the graph renders to. It will have the following format:
+    /// <identifier>.<extension>
+    pub fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+
+    /// Sets the description for the graph. The description is used to title
+    /// the graph when rendering
+    pub fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+
+    /// Adds a word, whose font size grows with its weight. The heaviest word is set in 56
+    /// pixels, the lightest in 12.
+    pub fn add_word(&mut self, text: &str, weight: f64) {
+        let mut m = Map::new();
+        m.insert(String::from("text"), Value::from(text));
+        m.insert(String::from("weight"), Value::from(weight));
+        self.data[0].add_value(Value::Object(m));
+    }
+
+    /// Sets the font family of the words, a CSS font list like "Georgia, serif"
+    pub fn set_font(&mut self, font: &str) {
+        self.font = String::from(font);
+        self.update_marks();
+    }
+
+    /// Sets the angles in degrees the words are rotated by, every word picks one at random.
+    /// Words are rotated by -45, 0 or 45 degrees by default, `&[0.0]` keeps them all level.
+    pub fn set_rotations(&mut self, angles: &[f64]) {
+        self.data[0].transforms_mut()[0] = WordCloud::rotations(angles);
+    }
+
+    /// Colors the words with the named Vega color scheme, like "tableau10" or "dark2"
+    pub fn set_color_scheme(&mut self, scheme: &str) {
+        self.scales[0].set_range(Range::scheme(scheme));
+    }
+
+    /// Sets the dimensions of the graph:
+    /// the dimensions are set as (height, width)
+    pub fn set_dimensions(&mut self, t: (u32, u32)) {
+        self.height = t.0;
+        self.width = t.1;
+    }
+
+    /// Sets the padding pixel count around the border of the graph
+    pub fn set_padding(&mut self, padding: u32) {
+        self.padding = padding;
+    }
+}
+impl Serialize for WordCloud {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("word_cloud", 7)?;
+        s.serialize_field("$schema", SCHEMA)?;
+        s.serialize_field("width", &self.width)?;
+        s.serialize_field("height", &self.height)?;
+        s.serialize_field("padding", &self.padding)?;
+        s.serialize_field("data", &self.extras.data_with(&self.data))?;
+        s.serialize_field("scales", &self.scales)?;
+        s.serialize_field("marks", &self.extras.marks_with(&self.marks))?;
+        self.extras.serialize_fields(&mut s)?;
+
+        s.end()
+    }
+}
+impl Graphable for WordCloud {
+    fn get_description(&self) -> &str {
+        &self.description
+    }
+    fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+    /// set_color draws every word in the same color
+    fn set_color(&mut self, color: &str) {
+        self.color = Some(String::from(color));
+        self.update_marks();
+    }
+    fn set_dimensions(&mut self, t: (i32, i32)) {
+        self.height = t.0 as u32;
+        self.width = t.1 as u32;
+    }
+    fn get_dimensions(&self) -> (i32, i32) {
+        (self.height as i32, self.width as i32)
+    }
+    fn set_description(&mut self, description: &str) {
+        self.description = String::from(description);
+    }
+    fn set_identifier(&mut self, id: &str) {
+        self.identifier = String::from(id);
+    }
+    fn scales_mut(&mut self) -> &mut Vec<Scale> {
+        &mut self.scales
+    }
+    /// The transforms of the words, which run after their angles are picked
+    fn transforms_mut(&mut self) -> &mut Vec<Transform> {
+        self.data[0].transforms_mut()
+    }
+    fn extras_mut(&mut self) -> &mut Extras {
+        &mut self.extras
+    }
+}
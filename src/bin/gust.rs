@@ -0,0 +1,414 @@
+/*
+ * The gust command line tool, which turns a CSV or JSON file into a chart.
+ * Built only with the `cli` feature:  cargo install gust --features cli
+ */
+
+extern crate csv;
+extern crate gust;
+extern crate serde_json;
+
+use gust::backend::area_chart::AreaChart;
+use gust::backend::bar_chart::BarChart;
+use gust::backend::grouped_bar_chart::GroupedBarChart;
+use gust::backend::line_chart::LineChart;
+use gust::backend::pie_chart::PieChart;
+use gust::backend::qq_plot::QQPlot;
+use gust::backend::scatter_plot::ScatterPlot;
+use gust::backend::stacked_bar_chart::StackedBarChart;
+use gust::backend::traits::Graphable;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::process;
+
+const USAGE: &'static str = "usage: gust <file.csv|file.json> --chart <bar|line|area|stacked|grouped|qq|scatter|pie> \
+--y <field> [--x <field>] [--series <field>] [--title <title>] [--out <file.html|file.json|file.png|file.svg>]
+
+Reads the rows of a CSV file, or the objects of a JSON array, and charts the given fields.
+The chart is written to the --out file, chart.html by default. Writing png and svg images
+needs gust built with the export feature, and vega-cli installed.";
+
+/// A row of the input file, holding the text of every field
+type Record = HashMap<String, String>;
+
+struct Options {
+    input: String,
+    chart: String,
+    x: Option<String>,
+    y: Option<String>,
+    series: Option<String>,
+    title: Option<String>,
+    out: String,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        input: String::new(),
+        chart: String::from("bar"),
+        x: None,
+        y: None,
+        series: None,
+        title: None,
+        out: String::from("chart.html"),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            options.input = arg.clone();
+            continue;
+        }
+        let value = match args.next() {
+            Some(value) => value.clone(),
+            None => return Err(format!("{} needs a value", arg)),
+        };
+        match arg.as_str() {
+            "--chart" => options.chart = value,
+            "--x" => options.x = Some(value),
+            "--y" => options.y = Some(value),
+            "--series" => options.series = Some(value),
+            "--title" => options.title = Some(value),
+            "--out" => options.out = value,
+            _ => return Err(format!("unknown option {}", arg)),
+        }
+    }
+    if options.input.is_empty() {
+        return Err(String::from("no input file given"));
+    }
+    Ok(options)
+}
+
+fn read_csv(path: &str) -> Result<Vec<Record>, String> {
+    let mut reader = csv::Reader::from_path(path).map_err(|e| e.to_string())?;
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let mut records = vec![];
+    for row in reader.records() {
+        let row = row.map_err(|e| e.to_string())?;
+        records.push(
+            headers
+                .iter()
+                .zip(row.iter())
+                .map(|(h, v)| (h.to_string(), v.to_string()))
+                .collect(),
+        );
+    }
+    Ok(records)
+}
+
+fn read_json(path: &str) -> Result<Vec<Record>, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let rows: Vec<HashMap<String, Value>> =
+        serde_json::from_reader(file).map_err(|e| e.to_string())?;
+    Ok(rows.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|(k, v)| match v {
+                    Value::String(s) => (k, s),
+                    v => (k, v.to_string()),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// The text of `field` in `record`
+fn text(record: &Record, field: &Option<String>, flag: &str) -> Result<String, String> {
+    let field = match *field {
+        Some(ref field) => field,
+        None => return Err(format!("this chart needs {}", flag)),
+    };
+    match record.get(field) {
+        Some(value) => Ok(value.clone()),
+        None => Err(format!("a row has no field {}", field)),
+    }
+}
+
+/// The value of `field` in `record` as a number
+fn number(record: &Record, field: &Option<String>, flag: &str) -> Result<f64, String> {
+    let value = text(record, field, flag)?;
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} is not a number", value))
+}
+
+/// Reads `value` as a whole number, rejecting fractions rather than cutting them off
+fn whole_number(value: &str) -> Result<i64, String> {
+    match value.trim().parse() {
+        Ok(n) => Ok(n),
+        Err(_) if value.trim().parse::<f64>().is_ok() => {
+            Err(format!("{} is not a whole number", value))
+        }
+        Err(_) => Err(format!("{} is not a number", value)),
+    }
+}
+
+/// The value of `field` in `record` as a whole number
+fn integer(record: &Record, field: &Option<String>, flag: &str) -> Result<i64, String> {
+    whole_number(&text(record, field, flag)?)
+}
+
+/// The series of every record, all in the first series unless --series was given. Series
+/// given as whole numbers are kept, while names like "north" are numbered in the order they
+/// first appear.
+fn series(records: &[Record], options: &Options) -> Result<Vec<i64>, String> {
+    if options.series.is_none() {
+        return Ok(vec![0; records.len()]);
+    }
+    let names = records
+        .iter()
+        .map(|r| text(r, &options.series, "--series"))
+        .collect::<Result<Vec<String>, String>>()?;
+    if names.iter().all(|n| n.trim().parse::<f64>().is_ok()) {
+        return names.iter().map(|n| whole_number(n)).collect();
+    }
+    let mut indices: HashMap<&str, i64> = HashMap::new();
+    Ok(names
+        .iter()
+        .map(|n| {
+            let next = indices.len() as i64;
+            *indices.entry(n.as_str()).or_insert(next)
+        })
+        .collect())
+}
+
+/// Renders the chart as a png or svg image at `path`
+#[cfg(feature = "export")]
+fn save_image<G: Graphable>(graph: &G, path: &Path, format: &str) -> io::Result<()> {
+    match format {
+        "png" => graph.save_png(path, 1.0),
+        _ => graph.save_svg(path),
+    }
+}
+
+#[cfg(not(feature = "export"))]
+fn save_image<G: Graphable>(_: &G, _: &Path, format: &str) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        format!("writing {} images needs gust built with the export feature", format),
+    ))
+}
+
+/// Writes the chart to the --out file, in the format its extension names
+fn render<G: Graphable>(mut graph: G, options: &Options) -> Result<(), String> {
+    let out = Path::new(&options.out);
+    let format = match out.extension().and_then(|e| e.to_str()) {
+        Some(format @ "html") | Some(format @ "json") => format,
+        Some(format @ "png") | Some(format @ "svg") => format,
+        _ => return Err(String::from("--out must end in .html, .json, .png or .svg")),
+    };
+    if let Some(name) = out.file_stem().and_then(|s| s.to_str()) {
+        graph.set_identifier(name);
+    }
+    if let Some(ref title) = options.title {
+        graph.set_description(title);
+    }
+    let written = match format {
+        "html" => graph.save_html(out),
+        "json" => graph.save_json(out),
+        _ => save_image(&graph, out, format),
+    };
+    written.map_err(|e| format!("could not write {}: {}", out.display(), e))
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let records = if options.input.ends_with(".json") {
+        read_json(&options.input)?
+    } else {
+        read_csv(&options.input)?
+    };
+
+    match options.chart.as_str() {
+        "bar" => {
+            let mut chart = BarChart::new();
+            for r in &records {
+                chart.add_data(text(r, &options.x, "--x")?, number(r, &options.y, "--y")?);
+            }
+            render(chart, options)
+        }
+        "line" => {
+            let mut chart = LineChart::new();
+            for (r, z) in records.iter().zip(series(&records, options)?) {
+                let x = number(r, &options.x, "--x")?;
+                chart.add_data(x, number(r, &options.y, "--y")?, z);
+            }
+            render(chart, options)
+        }
+        "area" => {
+            let mut chart = AreaChart::new();
+            for r in &records {
+                let x = number(r, &options.x, "--x")?;
+                chart.add_data(x, number(r, &options.y, "--y")?);
+            }
+            render(chart, options)
+        }
+        "stacked" => {
+            let mut chart = StackedBarChart::new();
+            for (r, z) in records.iter().zip(series(&records, options)?) {
+                let x = integer(r, &options.x, "--x")?;
+                chart.add_data(x, number(r, &options.y, "--y")?, z);
+            }
+            render(chart, options)
+        }
+        "grouped" => {
+            let mut chart = GroupedBarChart::new();
+            for r in &records {
+                let x = text(r, &options.x, "--x")?;
+                let series = text(r, &options.series, "--series")?;
+                chart.add_data(&x, &series, number(r, &options.y, "--y")?);
+            }
+            render(chart, options)
+        }
+        "qq" => {
+            let mut chart = QQPlot::new();
+            for r in &records {
+                chart.add_data(number(r, &options.y, "--y")?);
+            }
+            render(chart, options)
+        }
+        "scatter" => {
+            let mut chart = ScatterPlot::new();
+            for r in &records {
+                chart.add_data(number(r, &options.x, "--x")?, number(r, &options.y, "--y")?);
+            }
+            render(chart, options)
+        }
+        "pie" => {
+            let mut chart = PieChart::new();
+            for r in &records {
+                chart.add_slice(&text(r, &options.x, "--x")?, number(r, &options.y, "--y")?);
+            }
+            render(chart, options)
+        }
+        chart => Err(format!("unknown chart type {}", chart)),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+    let result = parse_args(&args).and_then(|options| run(&options));
+    if let Err(e) = result {
+        eprintln!("gust: {}\n\n{}", e, USAGE);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    /// Writes `contents` to a file named `name` in the gust_cli folder of the temporary directory
+    fn input(name: &str, contents: &str) -> PathBuf {
+        let path = env::temp_dir().join("gust_cli").join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&args(&[
+            "sales.csv", "--chart", "line", "--x", "month", "--y", "amount", "--series", "region",
+            "--out", "out/sales.json",
+        ])).unwrap();
+        assert_eq!(options.input, "sales.csv");
+        assert_eq!(options.chart, "line");
+        assert_eq!(options.x, Some(String::from("month")));
+        assert_eq!(options.series, Some(String::from("region")));
+        assert_eq!(options.out, "out/sales.json");
+
+        let defaults = parse_args(&args(&["sales.csv", "--y", "amount"])).unwrap();
+        assert_eq!(defaults.chart, "bar");
+        assert_eq!(defaults.out, "chart.html");
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert_eq!(parse_args(&args(&["--y", "amount"])).err().unwrap(), "no input file given");
+        assert_eq!(parse_args(&args(&["a.csv", "--y"])).err().unwrap(), "--y needs a value");
+        assert_eq!(
+            parse_args(&args(&["a.csv", "--z", "b"])).err().unwrap(),
+            "unknown option --z"
+        );
+    }
+
+    #[test]
+    fn test_read_csv_and_json() {
+        let csv = input("read.csv", "month,amount\n1,2.5\n2,3\n");
+        let records = read_csv(csv.to_str().unwrap()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["amount"], "2.5");
+
+        let json = input("read.json", r#"[{"month": 1, "region": "north"}]"#);
+        let records = read_json(json.to_str().unwrap()).unwrap();
+        assert_eq!(records[0]["month"], "1");
+        assert_eq!(records[0]["region"], "north");
+
+        assert!(read_csv("no_such_file.csv").is_err());
+        assert!(read_json(csv.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_series() {
+        let csv = input("series.csv", "x,y,region\n1,2,north\n2,3,south\n3,4,north\n");
+        let mut options = parse_args(&args(&["series.csv", "--series", "region"])).unwrap();
+        let records = read_csv(csv.to_str().unwrap()).unwrap();
+        assert_eq!(series(&records, &options).unwrap(), vec![0, 1, 0]);
+
+        options.series = Some(String::from("x"));
+        assert_eq!(series(&records, &options).unwrap(), vec![1, 2, 3]);
+        options.series = None;
+        assert_eq!(series(&records, &options).unwrap(), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_whole_numbers() {
+        assert_eq!(whole_number(" 4 "), Ok(4));
+        assert_eq!(whole_number("1.5"), Err(String::from("1.5 is not a whole number")));
+        assert_eq!(whole_number("north"), Err(String::from("north is not a number")));
+
+        let csv = input("fractions.csv", "x,y,z\n1.5,2,0\n");
+        let mut options = parse_args(&args(&[csv.to_str().unwrap(), "--chart", "stacked"])).unwrap();
+        options.x = Some(String::from("x"));
+        options.y = Some(String::from("y"));
+        assert_eq!(run(&options), Err(String::from("1.5 is not a whole number")));
+        options.x = Some(String::from("y"));
+        options.series = Some(String::from("x"));
+        assert_eq!(run(&options), Err(String::from("1.5 is not a whole number")));
+    }
+
+    #[test]
+    fn test_run() {
+        let csv = input("run.csv", "month,amount,region\n1,2.5,north\n1,3,south\n2,4,north\n");
+        let out = env::temp_dir().join("gust_cli").join("charts").join("run.json");
+        let _ = fs::remove_file(&out);
+        let options = parse_args(&args(&[
+            csv.to_str().unwrap(), "--chart", "stacked", "--x", "month", "--y", "amount",
+            "--series", "region", "--out", out.to_str().unwrap(),
+        ])).unwrap();
+        run(&options).unwrap();
+        let json = fs::read_to_string(&out).unwrap();
+        assert!(json.contains("{\"x\":1,\"y\":3.0,\"z\":1}"));
+
+        let mut options = options;
+        options.y = Some(String::from("region"));
+        assert_eq!(run(&options), Err(String::from("north is not a number")));
+        options.y = Some(String::from("amount"));
+        options.out = String::from("chart.pdf");
+        assert_eq!(run(&options), Err(String::from("--out must end in .html, .json, .png or .svg")));
+        options.chart = String::from("pie3d");
+        assert_eq!(run(&options), Err(String::from("unknown chart type pie3d")));
+    }
+}
@@ -0,0 +1,111 @@
+/*
+ * Dashboards, which lay several charts out in a grid on a single html page
+ */
+
+use backend::traits::Graphable;
+use frontend::report::{escape, render_template};
+use liquid;
+use std;
+
+const TEMPLATE: &'static str = include_str!("dashboard_template.html");
+
+/// A chart on the dashboard: its specification, description and the cells it spans
+struct Cell {
+    spec: String,
+    description: String,
+    columns: u32,
+    rows: u32,
+}
+
+/// Dashboard is a single html page holding several charts in a grid. The charts fill the grid
+/// row by row, each taking up one cell unless it spans more. Like a `Report`, the
+/// specifications of the charts are embedded into the page.
+/// # Example:
+/// ```rust
+///    use gust::backend::bar_chart::BarChart;
+///    use gust::backend::line_chart::LineChart;
+///    use gust::frontend::dashboard::Dashboard;
+///
+///    let mut b = BarChart::new();
+///    b.add_data(String::from("North"), 30);
+///    let mut c = LineChart::new();
+///    c.add_point(1, 2);
+///
+///    let mut dashboard = Dashboard::new("sales_dashboard", "Sales");
+///    dashboard.set_columns(2);
+///    dashboard.add_chart(&b);
+///    dashboard.add_chart(&c);
+///    dashboard.add_chart_spanning(&b, 2, 1);
+///    let html = dashboard.to_html().unwrap();
+/// ```
+pub struct Dashboard {
+    identifier: String,
+    title: String,
+    columns: u32,
+    cells: Vec<Cell>,
+}
+
+impl Dashboard {
+    /// Creates an empty dashboard with two columns. The identifier names the file the
+    /// dashboard is rendered to, the title is shown at its top.
+    pub fn new(identifier: &str, title: &str) -> Dashboard {
+        Dashboard {
+            identifier: String::from(identifier),
+            title: String::from(title),
+            columns: 2,
+            cells: vec![],
+        }
+    }
+
+    pub fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// Sets the number of columns of the grid
+    pub fn set_columns(&mut self, columns: u32) {
+        self.columns = columns.max(1);
+    }
+
+    /// Adds a chart to the next free cell of the grid, captioned with its description
+    pub fn add_chart<T: Graphable>(&mut self, graph: &T) {
+        self.add_chart_spanning(graph, 1, 1);
+    }
+
+    /// Adds a chart spanning the given number of columns and rows of the grid
+    pub fn add_chart_spanning<T: Graphable>(&mut self, graph: &T, columns: u32, rows: u32) {
+        self.cells.push(Cell {
+            spec: graph.get_json_representation(),
+            description: String::from(graph.get_description()),
+            columns: columns.max(1),
+            rows: rows.max(1),
+        });
+    }
+
+    /// Renders the dashboard into a single html document
+    pub fn to_html(&self) -> Result<String, std::io::Error> {
+        let mut cells = String::new();
+        let mut specs = vec![];
+        for (i, cell) in self.cells.iter().enumerate() {
+            cells += &format!(
+                "<figure class=\"chart\" style=\"grid-column: span {}; grid-row: span {};\"><div id=\"chart{}\"></div><figcaption>{}</figcaption></figure>\n",
+                cell.columns.min(self.columns),
+                cell.rows,
+                i,
+                escape(&cell.description)
+            );
+            // keeps a "</script>" within the data of a chart from ending the script
+            specs.push(cell.spec.replace("</", "<\\/"));
+        }
+
+        let mut globals = liquid::Object::new();
+        globals.insert("title".to_owned(), liquid::Value::str(&escape(&self.title)));
+        globals.insert("columns".to_owned(), liquid::Value::str(&self.columns.to_string()));
+        globals.insert("cells".to_owned(), liquid::Value::str(&cells));
+        globals.insert(
+            "specs".to_owned(),
+            liquid::Value::str(&format!("[{}]", specs.join(","))),
+        );
+
+        render_template(TEMPLATE, &globals)
+    }
+}
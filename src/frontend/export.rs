@@ -0,0 +1,64 @@
+/*!
+Static images of charts, which needs the `export` feature of gust.
+
+The charts are rendered by the command line tools `vg2png` and `vg2svg` of vega-cli, which
+have to be installed and on the path, e.g. through `npm install -g vega-cli`.
+
+```rust,no_run
+use gust::backend::bar_chart::BarChart;
+use gust::backend::traits::Graphable;
+
+let mut b = BarChart::new();
+b.add_data(String::from("A"), 3);
+b.save_png("chart.png", 2.0).unwrap();
+b.save_svg("chart.svg").unwrap();
+```
+ */
+
+use backend::traits::Graphable;
+use std;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::process::Command;
+
+/// Runs `tool` of vega-cli over the specification of the chart, writing its output to `path`
+fn run<T: Graphable>(graph: &T, tool: &str, args: &[&str], path: &Path) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let spec = std::env::temp_dir()
+        .join("gust")
+        .join(format!("{}.vg.json", graph.get_identifier()));
+    graph.save_json(&spec)?;
+
+    let output = Command::new(tool)
+        .args(args)
+        .arg(&spec)
+        .arg(path)
+        .output()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => Error::new(
+                ErrorKind::NotFound,
+                format!("{} not found, it is part of vega-cli (npm install -g vega-cli)", tool),
+            ),
+            _ => e,
+        })?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorKind::Other,
+            format!("{} failed: {}", tool, String::from_utf8_lossy(&output.stderr).trim()),
+        ))
+    }
+}
+
+/// Renders the chart to a png image at `path`, `scale` times the size of the chart
+pub fn save_png<T: Graphable, P: AsRef<Path>>(graph: &T, path: P, scale: f64) -> Result<(), Error> {
+    run(graph, "vg2png", &["-s", &scale.to_string()], path.as_ref())
+}
+
+/// Renders the chart to an svg image at `path`
+pub fn save_svg<T: Graphable, P: AsRef<Path>>(graph: &T, path: P) -> Result<(), Error> {
+    run(graph, "vg2svg", &[], path.as_ref())
+}
@@ -3,3 +3,9 @@
 
 pub mod write;
 pub mod html_render;
+pub mod report;
+pub mod dashboard;
+pub mod render;
+pub mod serve;
+#[cfg(feature = "export")]
+pub mod export;
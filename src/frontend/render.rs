@@ -0,0 +1,78 @@
+/*
+ * Standalone html pages holding a single chart, which can be opened without any other files
+ */
+
+use backend::traits::Graphable;
+use frontend::report::escape;
+use liquid;
+use std;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const TEMPLATE: &'static str = include_str!("standalone_template.html");
+
+/// Renders the chart into an html page, titled with its description. The specification is
+/// embedded into the page, while Vega and vega-embed are loaded from a CDN.
+pub fn to_html<T: Graphable>(graph: &T) -> String {
+    let mut globals = liquid::Object::new();
+    globals.insert("title".to_owned(), liquid::Value::str(&escape(graph.get_description())));
+    // keeps a "</script>" within the data of the chart from ending the script
+    let spec = graph.get_json_representation().replace("</", "<\\/");
+    globals.insert("spec".to_owned(), liquid::Value::str(&spec));
+
+    let template = liquid::ParserBuilder::with_liquid()
+        .build()
+        .parse(TEMPLATE)
+        .unwrap();
+    template.render(&globals).unwrap()
+}
+
+/// Writes the html page of the chart (see `to_html`) to the file at `path`, creating the
+/// folders leading up to it
+pub fn save_html<T: Graphable, P: AsRef<Path>>(graph: &T, path: P) -> Result<(), std::io::Error> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut f = std::fs::File::create(path)?;
+    f.write_all(to_html(graph).as_bytes())
+}
+
+/// The file a chart is written to by `show`: <identifier>.html in the gust folder of the
+/// temporary directory
+pub fn preview_path<T: Graphable>(graph: &T) -> PathBuf {
+    std::env::temp_dir()
+        .join("gust")
+        .join(format!("{}.html", graph.get_identifier()))
+}
+
+/// Writes the html page of the chart to its `preview_path` and opens it with the default
+/// browser of the system
+pub fn show<T: Graphable>(graph: &T) -> Result<(), std::io::Error> {
+    let path = preview_path(graph);
+    save_html(graph, &path)?;
+    open_in_browser(&path)
+}
+
+/// Opens the file with the program the system associates with it
+fn open_in_browser(path: &Path) -> Result<(), std::io::Error> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(&["/C", "start", ""]);
+        c
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command.arg(path).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("could not open {} in a browser", path.display()),
+        ))
+    }
+}
@@ -0,0 +1,155 @@
+
+/*
+ * Reports, which put many charts together with headings and text into a single html document
+ */
+
+use backend::traits::Graphable;
+use liquid;
+use std;
+
+const TEMPLATE: &'static str = include_str!("report_template.html");
+
+enum Section {
+    Heading(String),
+    Text(String),
+    /// The Vega specification of a chart and its description
+    Chart(String, String),
+}
+
+/// Report is a single html page holding several charts, along with headings and text
+/// describing them. The specifications of the charts are embedded into the page, so it can be
+/// passed around as a single file.
+/// # Example:
+/// ```rust
+///    use gust::backend::bar_chart::BarChart;
+///    use gust::frontend::report::Report;
+///
+///    let mut b = BarChart::new();
+///    b.add_data(String::from("North"), 30);
+///    b.add_data(String::from("South"), 12);
+///
+///    let mut report = Report::new("sales_report", "Sales 2018");
+///    report.add_heading("Revenue per Region");
+///    report.add_text("The north outsold the south by far.");
+///    report.add_chart(&b);
+///    let html = report.to_html().unwrap();
+/// ```
+pub struct Report {
+    identifier: String,
+    title: String,
+    table_of_contents: bool,
+    sections: Vec<Section>,
+}
+
+/// Escapes text for use in html
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders the liquid template `template` with the given globals
+pub fn render_template(
+    template: &str,
+    globals: &liquid::Object,
+) -> Result<String, std::io::Error> {
+    let error = |e: liquid::Error| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string())
+    };
+    let template = liquid::ParserBuilder::with_liquid()
+        .build()
+        .parse(template)
+        .map_err(error)?;
+    template.render(globals).map_err(error)
+}
+
+impl Report {
+    /// Creates an empty report. The identifier names the file the report is rendered to, the
+    /// title is shown at the top of the report.
+    pub fn new(identifier: &str, title: &str) -> Report {
+        Report {
+            identifier: String::from(identifier),
+            title: String::from(title),
+            table_of_contents: false,
+            sections: vec![],
+        }
+    }
+
+    pub fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// Starts a new section of the report
+    pub fn add_heading(&mut self, heading: &str) {
+        self.sections.push(Section::Heading(String::from(heading)));
+    }
+
+    /// Adds text to the report. Blank lines separate paragraphs.
+    pub fn add_text(&mut self, text: &str) {
+        self.sections.push(Section::Text(String::from(text)));
+    }
+
+    /// Adds a chart to the report, captioned with its description
+    pub fn add_chart<T: Graphable>(&mut self, graph: &T) {
+        self.sections.push(Section::Chart(
+            graph.get_json_representation(),
+            String::from(graph.get_description()),
+        ));
+    }
+
+    /// Lists the headings of the report at its top, linking to each section
+    pub fn show_table_of_contents(&mut self, show: bool) {
+        self.table_of_contents = show;
+    }
+
+    /// Renders the report into a single html document
+    pub fn to_html(&self) -> Result<String, std::io::Error> {
+        let mut contents = String::new();
+        let mut sections = String::new();
+        let mut specs = vec![];
+        let mut headings = 0;
+
+        for section in &self.sections {
+            match *section {
+                Section::Heading(ref heading) => {
+                    let heading = escape(heading);
+                    sections += &format!("<h2 id=\"section{}\">{}</h2>\n", headings, heading);
+                    contents +=
+                        &format!("<li><a href=\"#section{}\">{}</a></li>\n", headings, heading);
+                    headings += 1;
+                }
+                Section::Text(ref text) => {
+                    for paragraph in text.split("\n\n").filter(|p| !p.trim().is_empty()) {
+                        sections += &format!("<p>{}</p>\n", escape(paragraph.trim()));
+                    }
+                }
+                Section::Chart(ref spec, ref description) => {
+                    sections += &format!(
+                        "<figure class=\"chart\"><div id=\"chart{}\"></div><figcaption>{}</figcaption></figure>\n",
+                        specs.len(),
+                        escape(description)
+                    );
+                    // keeps a "</script>" within the data of a chart from ending the script
+                    specs.push(spec.replace("</", "<\\/"));
+                }
+            }
+        }
+        if !self.table_of_contents || contents.is_empty() {
+            contents.clear();
+        } else {
+            contents = format!("<nav><ul>\n{}</ul></nav>", contents);
+        }
+
+        let mut globals = liquid::Object::new();
+        globals.insert("title".to_owned(), liquid::Value::str(&escape(&self.title)));
+        globals.insert("contents".to_owned(), liquid::Value::str(&contents));
+        globals.insert("sections".to_owned(), liquid::Value::str(&sections));
+        globals.insert(
+            "specs".to_owned(),
+            liquid::Value::str(&format!("[{}]", specs.join(","))),
+        );
+
+        render_template(TEMPLATE, &globals)
+    }
+}
@@ -0,0 +1,187 @@
+/*!
+A small http server previewing charts in the browser while a program is running.
+
+The server shows all of its charts on a single page. Whenever the program passes a chart to
+`refresh` again, e.g. after adding data to it, the page reloads its charts through a stream
+of server-sent events.
+
+```rust,no_run
+use gust::backend::line_chart::LineChart;
+use gust::frontend::serve::Server;
+
+let server = Server::start("127.0.0.1:8080").unwrap();
+println!("open {}", server.url());
+
+let mut c = LineChart::new();
+for i in 0..100 {
+    c.add_point(i, i * i);
+    server.refresh(&c).unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(1));
+}
+```
+ */
+
+use backend::traits::Graphable;
+use serde_json;
+use serde_json::{Map, Value};
+use std;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+const TEMPLATE: &'static str = include_str!("serve_template.html");
+
+/// How often a stream of events checks for changes
+const POLL: u64 = 250;
+/// The number of checks between two keep-alive comments on a stream of events
+const KEEP_ALIVE: u32 = 60;
+
+/// The charts being served, along with a version that is increased on every change
+struct State {
+    charts: Vec<(String, Value)>,
+    version: u64,
+}
+
+/// Server is an http server, running in a thread of its own, that serves a page showing its
+/// charts. It stops with the program.
+pub struct Server {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+}
+
+impl Server {
+    /// Starts a server listening on `addr`. Port 0 picks a free port, see `url`.
+    pub fn start<A: ToSocketAddrs>(addr: A) -> Result<Server, std::io::Error> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            charts: vec![],
+            version: 0,
+        }));
+        let shared = state.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().filter_map(|s| s.ok()) {
+                let state = shared.clone();
+                thread::spawn(move || handle(stream, &state));
+            }
+        });
+        Ok(Server { addr, state })
+    }
+
+    /// The address of the page showing the charts
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// Shows the chart on the page, replacing the chart with the same identifier if there is
+    /// one, and reloads the charts of every open page. Fails if the chart can't be serialized.
+    pub fn refresh<T: Graphable>(&self, graph: &T) -> Result<(), std::io::Error> {
+        let mut chart = Map::new();
+        chart.insert(
+            String::from("description"),
+            Value::from(graph.get_description()),
+        );
+        let spec = serde_json::to_value(graph)
+            .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))?;
+        chart.insert(String::from("spec"), spec);
+        let identifier = String::from(graph.get_identifier());
+
+        let mut state = lock(&self.state)?;
+        match state.charts.iter().position(|c| c.0 == identifier) {
+            Some(i) => state.charts[i].1 = Value::Object(chart),
+            None => state.charts.push((identifier, Value::Object(chart))),
+        }
+        state.version += 1;
+        Ok(())
+    }
+
+    /// Removes the chart with the given identifier from the page
+    pub fn remove(&self, identifier: &str) -> Result<(), std::io::Error> {
+        let mut state = lock(&self.state)?;
+        state.charts.retain(|c| c.0 != identifier);
+        state.version += 1;
+        Ok(())
+    }
+}
+
+/// The charts being served, which are only unavailable if a thread panicked while changing them
+fn lock(state: &Mutex<State>) -> Result<MutexGuard<State>, std::io::Error> {
+    state
+        .lock()
+        .map_err(|_| std::io::Error::new(ErrorKind::Other, "the charts of the server were lost"))
+}
+
+/// Answers a single request: the page, the charts as json or the stream of events
+fn handle(stream: TcpStream, state: &Mutex<State>) {
+    let mut reader = match stream.try_clone() {
+        Ok(s) => BufReader::new(s),
+        Err(_) => return,
+    };
+    let mut request = String::new();
+    if reader.read_line(&mut request).is_err() {
+        return;
+    }
+    // the headers don't matter, but have to be read before answering
+    let mut line = String::new();
+    while reader.read_line(&mut line).map(|n| n > 2).unwrap_or(false) {
+        line.clear();
+    }
+
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+    let _ = match path {
+        "/" => respond(stream, "200 OK", "text/html; charset=utf-8", TEMPLATE),
+        "/charts.json" => {
+            let charts = lock(state).map(|state| {
+                let charts: Vec<Value> = state.charts.iter().map(|c| c.1.clone()).collect();
+                Value::Array(charts).to_string()
+            });
+            match charts {
+                Ok(charts) => respond(stream, "200 OK", "application/json", &charts),
+                Err(e) => respond(stream, "500 Internal Server Error", "text/plain", &e.to_string()),
+            }
+        }
+        "/events" => events(stream, state),
+        _ => respond(stream, "404 Not Found", "text/plain", "not found"),
+    };
+}
+
+fn respond(
+    mut stream: TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> Result<(), std::io::Error> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// Sends an event with the version of the charts whenever it changes, until the page is closed
+fn events(mut stream: TcpStream, state: &Mutex<State>) -> Result<(), std::io::Error> {
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\r\n"
+    )?;
+    let mut sent = None;
+    let mut checks = 0;
+    loop {
+        let version = lock(state)?.version;
+        if sent != Some(version) {
+            write!(stream, "data: {}\n\n", version)?;
+            sent = Some(version);
+        } else if checks % KEEP_ALIVE == 0 {
+            // writing fails once the page is closed, which ends the stream
+            write!(stream, ": keep-alive\n\n")?;
+        }
+        stream.flush()?;
+        checks += 1;
+        thread::sleep(Duration::from_millis(POLL));
+    }
+}
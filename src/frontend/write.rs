@@ -2,8 +2,12 @@
 /* Author: Samuel Resendez */
 
 use backend::traits::Graphable;
-use backend::general::FileType;
+use backend::general::{DataFormat, FileType};
 use frontend::html_render::create_html;
+use frontend::dashboard::Dashboard;
+use frontend::report::Report;
+use serde_json;
+use serde_json::{Map, Value};
 use std;
 use std::io::Write;
 use std::fs::DirBuilder;
@@ -11,6 +15,18 @@ use std::fs::DirBuilder;
 
 
 pub fn render_graph<T: Graphable>(graph: &T, ft: FileType) -> Result<(), std::io::Error> {
+    render_graph_with_data(graph, ft, DataFormat::Inline)
+}
+
+/// Renders a graph like `render_graph`, but with `DataFormat::JSON` or `DataFormat::CSV` the
+/// data sets holding values are written to files of their own, in a data folder next to the
+/// rendered file. The specification loads them by url, which keeps it small and lets browsers
+/// cache the data.
+pub fn render_graph_with_data<T: Graphable>(
+    graph: &T,
+    ft: FileType,
+    data_format: DataFormat,
+) -> Result<(), std::io::Error> {
     create_folder_structure(DirBuilder::new().recursive(true));
 
     let extension = match ft {
@@ -18,7 +34,14 @@ pub fn render_graph<T: Graphable>(graph: &T, ft: FileType) -> Result<(), std::io
         FileType::JSON => "json",
     };
 
-    let s = graph.get_json_representation();
+    let s = match data_format {
+        DataFormat::Inline => graph.get_json_representation(),
+        _ => {
+            let mut spec: Value = serde_json::from_str(&graph.get_json_representation())?;
+            write_data_files(&mut spec, extension, graph.get_identifier(), &data_format)?;
+            spec.to_string()
+        }
+    };
     let mut f = std::fs::File::create(format!(
         "gust_build/{}/{}.{}",
         extension,
@@ -47,6 +70,106 @@ pub fn render_graph<T: Graphable>(graph: &T, ft: FileType) -> Result<(), std::io
     Ok(())
 }
 
+/// Moves the values of every data set of `spec` into a file in gust_build/<folder>/data, and
+/// points the data set at that file instead. Urls are relative to the rendered file, which is
+/// also what Vega resolves them against when the html page loads the chart.
+fn write_data_files(
+    spec: &mut Value,
+    folder: &str,
+    identifier: &str,
+    data_format: &DataFormat,
+) -> Result<(), std::io::Error> {
+    let data_sets = match spec.get_mut("data").and_then(|d| d.as_array_mut()) {
+        Some(data_sets) => data_sets,
+        None => return Ok(()),
+    };
+    std::fs::create_dir_all(format!("gust_build/{}/data", folder))?;
+
+    for data in data_sets.iter_mut().filter_map(|d| d.as_object_mut()) {
+        let values = match data.remove("values") {
+            Some(Value::Array(values)) => values,
+            Some(other) => {
+                data.insert(String::from("values"), other);
+                continue;
+            }
+            None => continue,
+        };
+        let name = data.get("name").and_then(|n| n.as_str()).unwrap_or("data").to_string();
+        let (contents, extension) = match *data_format {
+            DataFormat::CSV => (to_csv(&values), "csv"),
+            _ => (Value::Array(values).to_string(), "json"),
+        };
+        let file = format!("data/{}_{}.{}", identifier, name, extension);
+        let mut f = std::fs::File::create(format!("gust_build/{}/{}", folder, file))?;
+        f.write_all(contents.as_bytes())?;
+
+        let mut format = Map::new();
+        format.insert(String::from("type"), Value::from(extension));
+        if extension == "csv" {
+            format.insert(String::from("parse"), Value::from("auto"));
+        }
+        data.insert(String::from("url"), Value::from(file));
+        data.insert(String::from("format"), Value::Object(format));
+    }
+    Ok(())
+}
+
+/// Writes flat data objects as csv, with a column for every field that any of them has
+fn to_csv(values: &[Value]) -> String {
+    fn quote(text: &str) -> String {
+        if text.contains(',') || text.contains('"') || text.contains('\n') {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            String::from(text)
+        }
+    }
+    fn cell(value: Option<&Value>) -> String {
+        match value {
+            Some(&Value::String(ref s)) => quote(s),
+            Some(&Value::Null) | None => String::new(),
+            Some(v) => quote(&v.to_string()),
+        }
+    }
+
+    let mut columns: Vec<String> = vec![];
+    for object in values.iter().filter_map(|v| v.as_object()) {
+        for key in object.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    let header: Vec<String> = columns.iter().map(|c| quote(c)).collect();
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for value in values {
+        let row: Vec<String> = columns.iter().map(|c| cell(value.get(c))).collect();
+        csv += &row.join(",");
+        csv.push('\n');
+    }
+    csv
+}
+
+
+/// Renders a report to gust_build/html/<identifier>.html
+pub fn render_report(report: &Report) -> Result<(), std::io::Error> {
+    create_folder_structure(DirBuilder::new().recursive(true));
+
+    let html = report.to_html()?;
+    let mut f = std::fs::File::create(format!("gust_build/html/{}.html", report.get_identifier()))?;
+    f.write_all(html.as_bytes())?;
+    Ok(())
+}
+
+/// Renders a dashboard to gust_build/html/<identifier>.html
+pub fn render_dashboard(dashboard: &Dashboard) -> Result<(), std::io::Error> {
+    create_folder_structure(DirBuilder::new().recursive(true));
+
+    let html = dashboard.to_html()?;
+    let mut f = std::fs::File::create(format!("gust_build/html/{}.html", dashboard.get_identifier()))?;
+    f.write_all(html.as_bytes())?;
+    Ok(())
+}
 
 
 fn create_folder_structure(builder: &DirBuilder) {
@@ -67,10 +67,24 @@ let mut b = BarChart::new();
 
 pub mod backend;
 pub mod frontend;
+pub mod testing;
 
 extern crate liquid;
 extern crate serde;
 extern crate serde_json;
+#[cfg(feature = "toml")]
+extern crate toml;
+#[cfg(feature = "yaml")]
+extern crate serde_yaml;
+#[cfg(feature = "csv")]
+extern crate csv;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+#[cfg(feature = "polars")]
+#[macro_use]
+extern crate polars;
 #[macro_use]
 extern crate serde_derive;
 
@@ -83,8 +97,41 @@ mod tests {
     use super::backend::stacked_bar_chart::StackedBarChart;
     use super::backend::line_chart::LineChart;
     use super::backend::area_chart::AreaChart;
-    use super::frontend::write::render_graph;
-    use super::backend::general::FileType;
+    use super::backend::qq_plot::QQPlot;
+    use super::backend::tree_chart::TreeChart;
+    use super::backend::icicle_chart::IcicleChart;
+    use super::backend::circle_packing::CirclePacking;
+    use super::frontend::write::{render_dashboard, render_graph, render_graph_with_data, render_report};
+    use super::frontend::report::Report;
+    use super::backend::general::{DataFormat, FileType};
+    use super::backend::traits::{Cartesian, Graphable};
+    use super::backend::elements::scale::{Range, Scale};
+    use super::backend::elements::transform::{Method, StackOffset, Transform};
+    use super::backend::elements::data::{DataSet, Format};
+    use super::backend::elements::mark::{when, Mark, ValueRef};
+    use super::backend::elements::geo::{self, Projection, ProjectionType};
+    use super::backend::config::ChartSpec;
+    use super::backend::scatter_plot::ScatterPlot;
+    use super::backend::pie_chart::PieChart;
+    use super::backend::gauge::Gauge;
+    use super::backend::grouped_bar_chart::GroupedBarChart;
+    use super::backend::lollipop_chart::LollipopChart;
+    use super::backend::multi_line_chart::MultiLineChart;
+    use super::backend::stacked_area_chart::StackedAreaChart;
+    use super::backend::candlestick_chart::CandlestickChart;
+    use super::backend::gantt_chart::GanttChart;
+    use super::backend::radar_chart::RadarChart;
+    use super::backend::sankey_chart::SankeyChart;
+    use super::backend::network_graph::NetworkGraph;
+    use super::backend::word_cloud::WordCloud;
+    use super::backend::funnel_chart::FunnelChart;
+    use super::backend::violin_plot::ViolinPlot;
+    use super::backend::ridgeline_plot::RidgelinePlot;
+    use super::backend::elements::overlay::ErrorBar;
+    use super::backend::elements::general::Interpolation;
+    use super::backend::elements::config::{AxisConfig, Config};
+    use super::testing;
+    use std::fs;
     #[test]
     fn test_bar_chart() {
         let mut b = BarChart::new();
@@ -97,12 +144,26 @@ mod tests {
     }
     #[test]
     fn test_stacked_bar_chart() {
+        use super::backend::elements::legend::LegendKind;
         let mut b = StackedBarChart::new();
         for i in 0..10 {
             b.add_data(i, i * i, 1);
             b.add_data(i, i + i, 0);
         }
+        let json = b.get_json_representation();
+        assert!(json.contains("{\"type\":\"stack\",\"groupby\":[\"x\"]"));
+        assert!(json.contains("\"name\":\"color\""));
+        assert!(json.contains("\"legends\":[{\"fill\":\"color\"}]"));
+        assert!(b.add_legend_for(LegendKind::Color, "Series"));
+        let json = b.get_json_representation();
+        assert!(json.contains("\"legends\":[{\"fill\":\"color\",\"title\":\"Series\"}]"));
         render_graph(&b, FileType::HTML).unwrap();
+
+        let mut sales = StackedBarChart::new();
+        sales.add_data_iter(vec![("North", 12.5, "Q1"), ("North", 7.0, "Q2"), ("South", 3.0, "Q1")]);
+        let json = sales.get_json_representation();
+        assert!(json.contains("{\"x\":\"North\",\"y\":12.5,\"z\":\"Q1\"}"));
+        assert!(json.contains("{\"x\":\"South\",\"y\":3.0,\"z\":\"Q1\"}"));
     }
     #[test]
     fn test_line_chart() {
@@ -136,5 +197,1359 @@ mod tests {
         render_graph(&area_chart, FileType::HTML).unwrap();
 
     }
+    #[test]
+    fn test_mark_styling() {
+        let mut a = AreaChart::new();
+        a.set_fill_opacity(0.8);
+        a.set_stroke_color("navy");
+        a.set_stroke_width(2.0);
+        let json = a.get_json_representation();
+        assert!(json.contains("\"update\":{\"interpolate\":{\"signal\":\"interpolate\"},\"fillOpacity\":{\"value\":0.8}}"));
+        assert!(json.contains("\"hover\":{\"fillOpacity\":{\"value\":0.4}}"));
+        assert!(json.contains("\"stroke\":{\"value\":\"navy\"},\"strokeWidth\":{\"value\":2.0}"));
+
+        let mut b = BarChart::new();
+        b.set_corner_radius(3.0);
+        b.set_fill_opacity(0.5);
+        let json = b.get_json_representation();
+        assert!(json.contains("\"cornerRadius\":{\"value\":3.0}"));
+        assert!(json.contains("\"fillOpacity\":{\"value\":0.5}"));
+    }
+    #[test]
+    fn test_conditional_colors() {
+        use super::backend::elements::predicate::Predicate;
+        let mut b = BarChart::new();
+        b.add_data(String::from("Q1"), -12.5);
+        b.color_when(Predicate::less_than("amount", 0.0), "firebrick");
+        let json = b.get_json_representation();
+        assert!(json.contains("\"update\":{\"fill\":[{\"test\":\"datum[\\\"amount\\\"] < 0\",\"value\":\"firebrick\"},{"));
+
+        let mut s = ScatterPlot::new();
+        s.add_data(1.0, 4.0);
+        s.color_when(Predicate::between("y", 3.0, 5.0).negate(), "gray");
+        let json = s.get_json_representation();
+        assert!(json.contains("\"fill\":[{\"test\":\"!(datum[\\\"y\\\"] >= 3 && datum[\\\"y\\\"] <= 5)\",\"value\":\"gray\"},{\"value\":\"steelblue\"}]"));
+    }
+    #[test]
+    fn test_configuring_axes() {
+        let mut c = LineChart::new();
+        for i in 0..20 {
+            c.add_data(i, i * i, 0);
+        }
+        c.set_identifier("configured_axes_line_chart");
+        c.x_axis_mut().set_offset(10);
+        c.y_axis_mut().set_position(20);
+        c.y_axis_mut().set_zindex(1);
+        c.y_axis_mut().show_domain(false);
+        let json = c.get_json_representation();
+        assert!(json.contains("\"offset\":10"));
+        assert!(json.contains("\"domain\":false"));
+        render_graph(&c, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_tick_formats() {
+        use super::backend::elements::axis::TickFormat;
+        let mut c = LineChart::new();
+        c.set_x_format(TickFormat::DateTime(String::from("%b %d")));
+        c.set_y_format(TickFormat::Currency(2));
+        let json = c.get_json_representation();
+        assert!(json.contains("\"format\":\"%b %d\",\"formatType\":\"time\""));
+        assert!(json.contains("\"format\":\"$,.2f\""));
+        c.set_x_format(TickFormat::Fixed(0));
+        let json = c.get_json_representation();
+        assert!(json.contains("\"format\":\",.0f\""));
+        assert!(!json.contains("\"formatType\""));
+
+        let mut b = BarChart::new();
+        b.set_y_format(".1f");
+        assert!(b.get_json_representation().contains("\"format\":\".1f\"}"));
+        assert_eq!(TickFormat::Percent(1).pattern(), ".1%");
+        assert_eq!(TickFormat::Si.pattern(), "~s");
+    }
+    #[test]
+    fn test_axis_labels() {
+        use super::backend::elements::axis::{AxisLabels, LabelOverlap};
+        let mut b = BarChart::new();
+        b.configure_x_axis(&AxisLabels::new().label_angle(-45.0).label_limit(80.0));
+        b.configure_x_axis(&AxisLabels::new().label_overlap(LabelOverlap::Off));
+        b.configure_y_axis(&AxisLabels::new().label_overlap(LabelOverlap::Greedy).tick_count(5));
+        let json = b.get_json_representation();
+        assert!(json.contains("\"labelAngle\":-45.0,\"labelLimit\":80.0,\"labelOverlap\":false}"));
+        assert!(json.contains("\"labelOverlap\":\"greedy\",\"tickCount\":5}"));
+    }
+    #[test]
+    fn test_axis_style() {
+        use super::backend::elements::axis::AxisStyle;
+        let mut c = LineChart::new();
+        c.style_y_axis(&AxisStyle::new().grid(true).grid_color("#ddd").grid_dash(&[4.0, 2.0]));
+        c.style_y_axis(&AxisStyle::new().domain(false).tick_size(3.0));
+        c.style_x_axis(&AxisStyle::new().ticks(false).domain_color("gray"));
+        let json = c.get_json_representation();
+        assert!(json.contains(
+            "{\"orient\":\"left\",\"scale\":\"y\",\"domain\":false,\"grid\":true,\"gridColor\":\"#ddd\",\"gridDash\":[4.0,2.0],\"tickSize\":3.0}"
+        ));
+        assert!(json.contains("\"domainColor\":\"gray\",\"ticks\":false"));
+    }
+    #[test]
+    fn test_manual_domain() {
+        let mut c = ScatterPlot::new();
+        c.add_data(1.0, 2.0);
+        c.set_x_domain(0.0, 10.0);
+        c.set_y_domain(-5.0, 5.0);
+        c.y_scale_mut().include_zero(false);
+        c.y_scale_mut().set_nice(false);
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"name\":\"x\",\"type\":\"linear\",\"domain\":[0.0,10.0]"));
+        assert!(json.contains("\"domain\":[-5.0,5.0],\"range\":\"height\",\"zero\":false,\"nice\":false}"));
+    }
+    #[test]
+    fn test_secondary_axis() {
+        let mut c = LineChart::new();
+        c.add_data(1, 21.5, 0);
+        c.add_series_secondary("rainfall", vec![(1, 120), (2, 80)]);
+        c.secondary_axis_mut().unwrap().set_title("mm");
+        let json = c.get_json_representation();
+        assert!(json.contains("\"domain\":{\"fields\":[{\"data\":\"table\",\"field\":\"x\"},{\"data\":\"secondary\",\"field\":\"x\"}]}"));
+        assert!(json.contains("{\"name\":\"y2\",\"type\":\"linear\",\"domain\":{\"data\":\"secondary\",\"field\":\"y\"},\"range\":\"height\"}"));
+        assert!(json.contains("\"orient\":\"right\",\"scale\":\"y2\""));
+        assert!(json.contains("{\"series\":\"rainfall\",\"x\":2.0,\"y\":80.0}"));
+        assert!(json.contains("\"facet\":{\"name\":\"secondary_series\",\"data\":\"secondary\",\"groupby\":\"series\"}"));
+    }
+    #[test]
+    fn test_log_scale() {
+        use super::backend::elements::scale::ScaleType;
+        let mut c = LineChart::new();
+        c.add_point(1, 0);
+        c.add_point(2, 1000);
+        c.set_y_scale_type(ScaleType::Log);
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"name\":\"y\",\"type\":\"log\",\"domain\":{\"data\":\"table\",\"field\":\"y\"},\"range\":\"height\",\"zero\":false}"));
+        assert!(json.contains("{\"type\":\"filter\",\"expr\":\"datum[\\\"y\\\"] > 0\"}"));
+        c.set_y_scale_type(ScaleType::Log);
+        assert_eq!(c.get_json_representation().matches("datum[\\\"y\\\"] > 0").count(), 1);
+        c.set_y_scale_type(ScaleType::Linear);
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"name\":\"y\",\"type\":\"linear\",\"domain\":{\"data\":\"table\",\"field\":\"y\"},\"range\":\"height\"}"));
+        assert!(!json.contains("datum[\\\"y\\\"] > 0"));
+
+        let mut a = AreaChart::new();
+        a.set_y_scale_type(ScaleType::Sqrt);
+        assert!(a.get_json_representation().contains("\"type\":\"sqrt\""));
+        assert!(!a.get_json_representation().contains("filter"));
+    }
+    #[test]
+    fn test_tooltips() {
+        use super::backend::elements::tooltip::Tooltip;
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 3);
+        b.enable_tooltips();
+        assert!(b.get_json_representation().contains("\"tooltip\":{\"signal\":\"datum\"}"));
+
+        let mut c = LineChart::new();
+        c.add_point(1, 2);
+        c.set_tooltip(Tooltip::fields(&["x", "y"]));
+        let json = c.get_json_representation();
+        assert!(json.contains("\"tooltip\":{\"signal\":\"{\\\"x\\\": datum[\\\"x\\\"], \\\"y\\\": datum[\\\"y\\\"]}\"}"));
+
+        let mut l = LollipopChart::new();
+        l.add_data(String::from("A"), 28);
+        l.enable_tooltips();
+        let json = l.get_json_representation();
+        assert_eq!(json.matches("\"tooltip\"").count(), 1);
+        assert!(json.contains("\"tooltip\":{\"signal\":\"datum.category + ': ' + datum.amount\"}"));
+    }
+    #[test]
+    fn test_value_labels() {
+        use super::backend::elements::overlay::{LabelPosition, ValueLabels};
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 1250.5);
+        b.show_values(LabelPosition::OutsideEnd);
+        let json = b.get_json_representation();
+        assert!(json.contains("\"text\":{\"field\":\"amount\"}"));
+        assert!(json.contains("\"y\":{\"field\":\"amount\",\"scale\":\"yscale\",\"offset\":-3.0}"));
+        assert!(json.contains("\"baseline\":{\"value\":\"bottom\"}"));
+
+        let mut c = LineChart::new();
+        c.add_point(1, 0.25);
+        c.show_values(ValueLabels::new(LabelPosition::OutsideEnd).format(".0%").avoid_overlap(true));
+        let json = c.get_json_representation();
+        assert!(json.contains("\"text\":{\"signal\":\"format(datum.y, \\\".0%\\\")\"}"));
+        assert!(json.contains("{\"type\":\"label\",\"size\":[{\"signal\":\"width\"},{\"signal\":\"height\"}],\"anchor\":[\"top\",\"right\",\"left\",\"bottom\"],\"offset\":[3.0]}"));
+    }
+    #[test]
+    fn test_color_schemes() {
+        use super::backend::elements::color::{Color, ColorScheme};
+        let mut b = StackedBarChart::new();
+        b.set_colors(ColorScheme::Tableau10);
+        assert!(b.get_json_representation().contains("\"range\":{\"scheme\":\"tableau10\"}"));
+        b.set_colors(ColorScheme::Custom(vec![Color::from("#1b9e77"), Color::rgb(1, 2, 3)]));
+        assert!(b.get_json_representation().contains("\"range\":[\"#1b9e77\",\"rgb(1, 2, 3)\"]"));
+        assert_eq!(Color::hex("fff").to_string(), "#fff");
+    }
+    #[test]
+    fn test_theme_presets() {
+        use super::backend::elements::theme::Theme;
+        assert!(Theme::dark().low_contrast_colors().is_empty());
+        let mut b = BarChart::new();
+        b.set_theme(Theme::fivethirtyeight());
+        let json = b.get_json_representation();
+        assert!(json.contains("\"background\":\"#f0f0f0\""));
+        assert!(json.contains("\"title\":{\"color\":\"#3c3c3c\",\"font\":\"Helvetica Neue\",\"fontSize\":18.0}"));
+        assert!(json.contains("\"grid\":true"));
+        assert!(json.contains("\"labelFontSize\":12.0"));
+    }
+    #[test]
+    fn test_config_title_and_locale() {
+        use super::backend::elements::config::{Config, Locale, TitleConfig};
+        use super::backend::elements::title::Anchor;
+        let mut b = BarChart::new();
+        b.set_config(
+            Config::new()
+                .title(TitleConfig::new().anchor(Anchor::Start).font("Georgia", 16.0, "#222"))
+                .locale(Locale::new(",", ".").currency("", " €")),
+        );
+        let json = b.get_json_representation();
+        assert!(json.contains("\"title\":{\"anchor\":\"start\",\"color\":\"#222\",\"font\":\"Georgia\",\"fontSize\":16.0}"));
+        assert!(json.contains("\"locale\":{\"number\":{\"currency\":[\"\",\" €\"],\"decimal\":\",\",\"grouping\":[3],\"thousands\":\".\"}}"));
+    }
+    #[test]
+    fn test_chart_title() {
+        use super::backend::elements::title::Anchor;
+        let mut b = BarChart::new();
+        b.set_subtitle("Fiscal year 2018");
+        b.set_title("Revenue");
+        b.title_mut().set_anchor(Anchor::Start);
+        b.title_mut().set_font_size(18.0);
+        b.title_mut().set_offset(8.0);
+        assert!(b.get_json_representation().contains(
+            "\"title\":{\"text\":\"Revenue\",\"subtitle\":\"Fiscal year 2018\",\"anchor\":\"start\",\"fontSize\":18.0,\"offset\":8.0}"
+        ));
+    }
+    #[test]
+    fn test_right_to_left_title() {
+        use super::backend::elements::title::Anchor;
+        let mut b = BarChart::new();
+        b.set_title("Revenue");
+        b.set_right_to_left(true);
+        assert!(b.get_json_representation().contains("\"title\":{\"text\":\"Revenue\",\"anchor\":\"end\"}"));
+        b.title_mut().set_anchor(Anchor::Middle);
+        assert!(b.get_json_representation().contains("\"title\":{\"text\":\"Revenue\",\"anchor\":\"middle\"}"));
+    }
+    #[test]
+    fn test_axis_titles() {
+        let mut b = BarChart::new();
+        b.set_x_label("Region");
+        b.set_y_label("Revenue");
+        b.y_axis_mut().set_title_font_size(14.0);
+        b.y_axis_mut().set_title_color("#333");
+        let json = b.get_json_representation();
+        assert!(json.contains("{\"orient\":\"bottom\",\"scale\":\"xscale\",\"title\":\"Region\"}"));
+        assert!(json.contains("\"title\":\"Revenue\",\"titleFontSize\":14.0,\"titleColor\":\"#333\""));
+    }
+    #[test]
+    fn test_discretizing_scales() {
+        let mut b = BarChart::new();
+        for i in 0..10 {
+            b.add_data(i.to_string(), i * 10);
+        }
+        b.add_scale(Scale::quantize("buckets", "table", "amount", Range::scheme("blues")));
+        b.add_scale(Scale::threshold(
+            "levels",
+            vec![30.0, 60.0],
+            Range::values(&["green", "orange", "red"]),
+        ));
+        let json = b.get_json_representation();
+        assert!(json.contains("\"type\":\"quantize\""));
+        assert!(json.contains("\"domain\":[30.0,60.0]"));
+    }
+    #[test]
+    fn test_normalized_stacked_bar_chart() {
+        let mut b = StackedBarChart::new();
+        for i in 0..10 {
+            b.add_data(i, i * i, 1);
+            b.add_data(i, i + i, 0);
+        }
+        b.set_identifier("normalized_stacked_bar_chart");
+        b.set_stack_offset(StackOffset::Normalize);
+        let json = b.get_json_representation();
+        assert!(json.contains("\"offset\":\"normalize\""));
+        assert!(json.contains("\"format\":\".0%\""));
+        render_graph(&b, FileType::HTML).unwrap();
+
+        b.set_stack_offset(StackOffset::Zero);
+        let json = b.get_json_representation();
+        assert!(json.contains("\"offset\":\"zero\""));
+        assert!(!json.contains("\"format\":\".0%\""));
+    }
+    #[test]
+    fn test_line_chart_trendline() {
+        let mut c = LineChart::new();
+        for i in 0..20 {
+            c.add_data(i, i * i, 0);
+            c.add_data(i, 3 * i, 1);
+        }
+        c.set_identifier("trendline_line_chart");
+        c.add_trendline(Method::Linear, true);
+        let json = c.get_json_representation();
+        assert!(json.contains("\"type\":\"regression\""));
+        render_graph(&c, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_keyboard_navigation() {
+        use super::backend::elements::signal::Signal;
+        let mut c = LineChart::new();
+        for i in 0..20 {
+            c.add_data(i, i * i, 0);
+        }
+        c.add_keyboard_navigation();
+        let json = c.get_json_representation();
+        assert!(json.contains("\"signals\":[{\"name\":\"interpolate\""));
+        assert!(json.contains("{\"name\":\"focus\",\"value\":-1,\"on\":["));
+        assert!(json.contains("{\"name\":\"click\",\"value\":null,\"on\":["));
+        assert!(json.contains("\"from\":{\"data\":\"focused0\"}"));
+
+        c.add_keyboard_navigation();
+        assert_eq!(c.get_json_representation(), json);
+
+        let mut s = ScatterPlot::new();
+        s.add_data(1.0, 2.0);
+        s.add_signal(Signal::new("click", 0));
+        s.add_keyboard_navigation();
+        let json = s.get_json_representation();
+        assert_eq!(json.matches("\"name\":\"click\"").count(), 1);
+        assert!(!json.contains("\"name\":\"focus\""));
+    }
+    #[test]
+    fn test_line_chart_voronoi() {
+        let mut c = LineChart::new();
+        for i in 0..20 {
+            c.add_data(i, i * i, 0);
+        }
+        c.add_voronoi();
+        let json = c.get_json_representation();
+        assert!(json.contains("\"name\":\"voronoi_points0\",\"from\":{\"data\":\"table\"}"));
+        assert!(json.contains("\"from\":{\"data\":\"voronoi_points0\"}"));
+        assert!(json.contains("\"transform\":[{\"type\":\"voronoi\""));
+    }
+    #[test]
+    fn test_line_chart_moving_average() {
+        let mut c = LineChart::new();
+        for i in 0..20 {
+            c.add_data(i, i * i, 0);
+            c.add_data(i, 3 * i, 1);
+        }
+        c.add_moving_average(5);
+        let json = c.get_json_representation();
+        assert!(json.contains("\"source\":\"table\""));
+        assert!(json.contains("\"ops\":[\"mean\"],\"fields\":[\"y\"]"));
+        assert!(json.contains("\"as\":[\"moving_average\"]"));
+        assert!(json.contains("\"frame\":[-4,0]"));
+    }
+    #[derive(Serialize)]
+    struct Region {
+        code: String,
+        name: String,
+    }
+    #[test]
+    fn test_lookup_data_set() {
+        let mut b = BarChart::new();
+        b.add_data(String::from("N"), 10);
+        b.add_data(String::from("S"), 20);
+        let regions = vec![
+            Region { code: String::from("N"), name: String::from("North") },
+            Region { code: String::from("S"), name: String::from("South") },
+        ];
+        b.add_data_set(DataSet::from_records("regions", &regions).unwrap());
+        b.add_transform(Transform::lookup("regions", "code", "category", &["name"]));
+        let json = b.get_json_representation();
+        assert!(json.contains("\"name\":\"regions\""));
+        assert!(json.contains("\"from\":\"regions\""));
+    }
+    #[test]
+    fn test_bar_chart_rule() {
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 10);
+        b.add_data(String::from("B"), 30);
+        b.add_mark(Mark::horizontal_rule(ValueRef::scaled_value("yscale", 20)).stroke("red", 2.0));
+        let json = b.get_json_representation();
+        assert!(json.contains("\"type\":\"rule\""));
+        assert!(json.contains("\"x2\":{\"signal\":\"width\"}"));
+        let labels = Mark::text(ValueRef::field("amount")).from_data("table").update(
+            "fill",
+            when("datum.amount > 20").then(ValueRef::value("red")).otherwise(ValueRef::value("gray")),
+        );
+        b.add_mark(labels);
+        let json = b.get_json_representation();
+        assert!(json.contains("\"fill\":[{\"test\":\"datum.amount > 20\",\"value\":\"red\"},{\"value\":\"gray\"}]"));
+    }
+    #[test]
+    fn test_graticule() {
+        let mut b = BarChart::new();
+        b.add_projection(Projection::new("projection", ProjectionType::NaturalEarth1));
+        geo::add_graticule(b.extras_mut(), "projection", 15.0);
+        let json = b.get_json_representation();
+        assert!(json.contains("\"projections\":[{\"name\":\"projection\",\"type\":\"naturalEarth1\"}]"));
+        assert!(json.contains("{\"type\":\"graticule\",\"step\":[15.0,15.0]}"));
+    }
+    #[test]
+    fn test_geo_data_sets() {
+        let mut b = BarChart::new();
+        b.add_data_set(DataSet::from_url("countries", "world-110m.json", Format::topojson_feature("countries")));
+        b.add_data_set(DataSet::from_url("borders", "world-110m.json", Format::topojson_mesh("countries")));
+        b.add_data_set(DataSet::from_url("states", "states.geojson", Format::geojson()));
+        let collection = ::serde_json::from_str(
+            r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"A"},"geometry":null}]}"#,
+        ).unwrap();
+        let mut regions = DataSet::from_geojson("regions", &collection);
+        regions.extract_properties(&["name"]);
+        b.add_data_set(regions);
+        let point = ::serde_json::from_str(r#"{"type":"Point","coordinates":[8.5,47.4]}"#).unwrap();
+        b.add_data_set(DataSet::from_geojson("city", &point));
+        let json = b.get_json_representation();
+        assert!(json.contains(
+            "{\"name\":\"countries\",\"url\":\"world-110m.json\",\"format\":{\"type\":\"topojson\",\"feature\":\"countries\"}}"
+        ));
+        assert!(json.contains(
+            "{\"name\":\"borders\",\"url\":\"world-110m.json\",\"format\":{\"type\":\"topojson\",\"mesh\":\"countries\"}}"
+        ));
+        assert!(json.contains(
+            "{\"name\":\"states\",\"url\":\"states.geojson\",\"format\":{\"type\":\"json\",\"property\":\"features\"}}"
+        ));
+        assert!(json.contains(
+            "{\"name\":\"regions\",\"values\":[{\"geometry\":null,\"properties\":{\"name\":\"A\"},\"type\":\"Feature\"}],\"transform\":[{\"type\":\"formula\",\"expr\":\"datum.properties['name']\",\"as\":\"name\"}]}"
+        ));
+        assert!(json.contains("{\"name\":\"city\",\"values\":[{\"coordinates\":[8.5,47.4],\"type\":\"Point\"}]}"));
+    }
+    #[test]
+    fn test_projection_fit() {
+        let mut b = BarChart::new();
+        let mut projection = Projection::new("projection", ProjectionType::Mercator);
+        projection.fit("countries");
+        b.add_projection(projection);
+        let json = b.get_json_representation();
+        assert!(json.contains(
+            "\"projections\":[{\"name\":\"projection\",\"type\":\"mercator\",\"fit\":{\"signal\":\"data('countries')\"},\"size\":{\"signal\":\"[width, height]\"}}]"
+        ));
+    }
+    #[test]
+    fn test_tree_chart() {
+        let mut t = TreeChart::new();
+        t.add_node("root", "Root");
+        t.add_edge("root", "a");
+        t.add_edge("root", "b");
+        t.add_edge("a", "c");
+        t.add_node("c", "Leaf");
+        t.set_horizontal(true);
+        let json = t.get_json_representation();
+        assert!(json.contains("{\"id\":\"c\",\"name\":\"Leaf\",\"parent\":\"a\"}"));
+        assert!(json.contains("\"as\":[\"y\",\"x\",\"depth\",\"children\"]"));
+        assert!(json.contains("{\"type\":\"treelinks\"}"));
+        render_graph(&t, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_icicle_chart() {
+        let mut c = IcicleChart::new();
+        c.add_edge("root", "a");
+        c.add_edge("root", "b");
+        c.set_size("a", 3.0);
+        c.set_size("b", 1.0);
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"id\":\"a\",\"name\":\"a\",\"parent\":\"root\",\"size\":3.0}"));
+        assert!(json.contains("\"type\":\"partition\",\"field\":\"size\""));
+        render_graph(&c, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_circle_packing() {
+        let mut c = CirclePacking::new();
+        c.add_node("src", "src");
+        c.add_edge("src", "lib.rs");
+        c.add_edge("src", "backend");
+        c.add_edge("backend", "mod.rs");
+        c.set_size("lib.rs", 4200.0);
+        c.set_size("mod.rs", 300.0);
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"id\":\"lib.rs\",\"name\":\"lib.rs\",\"parent\":\"src\",\"size\":4200.0}"));
+        assert!(json.contains(
+            "{\"type\":\"pack\",\"field\":\"size\",\"size\":[{\"signal\":\"width\"},{\"signal\":\"height\"}],\"padding\":2.0}"
+        ));
+        assert!(json.contains("\"size\":{\"signal\":\"4 * datum.r * datum.r\"}"));
+        render_graph(&c, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_chart_spec_from_config() {
+        fs::create_dir_all("gust_build/config").unwrap();
+        fs::write(
+            "gust_build/config/spec.json",
+            r#"{"identifier": "spec_bar_chart", "theme": {"font": "Helvetica", "palette": ["red", "blue"]}}"#,
+        ).unwrap();
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 3);
+        ChartSpec::from_config("gust_build/config/spec.json").unwrap().apply(&mut b);
+        let json = b.get_json_representation();
+        assert_eq!(b.get_identifier(), "spec_bar_chart");
+        assert!(json.contains("\"range\":{\"category\":[\"red\",\"blue\"]}"));
+        b.set_config(Config::new().range_scheme("ramp", "viridis").axis(AxisConfig::new().grid("#eee")));
+        let json = b.get_json_representation();
+        assert!(json.contains("\"range\":{\"category\":[\"red\",\"blue\"],\"ramp\":{\"scheme\":\"viridis\"}}"));
+        assert!(json.contains("\"gridColor\":\"#eee\",\"labelFont\":\"Helvetica\""));
+        assert!(ChartSpec::from_config("gust_build/config/spec.ini").is_err());
+    }
+    #[test]
+    #[cfg(feature = "csv")]
+    fn test_load_csv() {
+        use super::backend::csv::{CsvError, CsvTable};
+        fs::create_dir_all("gust_build/csv").unwrap();
+        fs::write("gust_build/csv/sales.csv", "region,revenue,note\nNorth,12.5,\nSouth,7,late\n").unwrap();
+        fs::write("gust_build/csv/broken.csv", "region,revenue\nNorth,12\nSouth,n/a\n").unwrap();
+
+        let b = BarChart::from_csv("gust_build/csv/sales.csv", "region", "revenue").unwrap();
+        assert!(b.get_json_representation().contains("{\"category\":\"South\",\"amount\":7.0}"));
+
+        let table = CsvTable::from_path("gust_build/csv/sales.csv").unwrap();
+        assert!(table.is_numeric("revenue"));
+        assert!(!table.is_numeric("note"));
+        assert_eq!(
+            ::serde_json::to_string(&table.to_values()).unwrap(),
+            "[{\"note\":\"\",\"region\":\"North\",\"revenue\":12.5},{\"note\":\"late\",\"region\":\"South\",\"revenue\":7.0}]"
+        );
+
+        match BarChart::from_csv("gust_build/csv/broken.csv", "region", "revenue") {
+            Err(CsvError::Row { line, .. }) => assert_eq!(line, 3),
+            _ => panic!("expected a row error"),
+        }
+        match BarChart::from_csv("gust_build/csv/sales.csv", "region", "profit") {
+            Err(CsvError::MissingColumn(column)) => assert_eq!(column, "profit"),
+            _ => panic!("expected a missing column"),
+        }
+    }
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_time_data() {
+        use chrono::NaiveDate;
+        let day = |d| NaiveDate::from_ymd_opt(2018, 3, d).unwrap();
+        let mut c = LineChart::new();
+        c.add_time_data(day(1), 12, 0);
+        c.add_time_data(day(2).and_hms_milli_opt(6, 0, 0, 500).unwrap().and_utc(), 14, 0);
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"x\":1519862400000.0,\"y\":12.0,\"z\":0}"));
+        assert!(json.contains("\"x\":1519970400500.0"));
+        assert!(json.contains("\"name\":\"x\",\"type\":\"utc\""));
+        assert!(json.contains("\"formatType\":\"utc\""));
+
+        c.x_scale_mut().set_utc(false);
+        c.add_time_data(day(3), 9, 0);
+        assert!(c.get_json_representation().contains("\"name\":\"x\",\"type\":\"time\""));
+
+        let mut a = AreaChart::new();
+        a.add_time_data_iter(vec![(day(1), 1)]);
+        assert!(a.get_json_representation().contains("\"type\":\"utc\""));
+
+        let mut b = BarChart::new();
+        b.x_scale_mut().set_utc(true);
+        assert!(b.get_json_representation().contains("\"type\":\"band\""));
+        assert!(!b.get_json_representation().contains("\"type\":\"utc\""));
+    }
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn test_ndarray() {
+        use ndarray::{arr1, arr2};
+        let c = LineChart::from_ndarray(&arr1(&[1.0, 4.0]));
+        assert!(c.get_json_representation().contains(
+            "[{\"x\":0.0,\"y\":1.0,\"z\":0},{\"x\":1.0,\"y\":4.0,\"z\":0}]"
+        ));
+        let d = LineChart::from_ndarray2(&arr2(&[[1, 2], [3, 5]]));
+        assert!(d.get_json_representation().contains("{\"x\":1.0,\"y\":5.0,\"z\":1}"));
+        let a = AreaChart::from_ndarray(&arr1(&[2.5]).view());
+        assert!(a.get_json_representation().contains("{\"u\":0.0,\"v\":2.5}"));
+    }
+    #[test]
+    #[cfg(feature = "polars")]
+    fn test_dataframe() {
+        use polars::prelude::*;
+        let df = df!(
+            "region" => &["North", "South"],
+            "day" => &[1i64, 2],
+            "revenue" => &[Some(12.5), None]
+        ).unwrap();
+        let b = BarChart::from_dataframe(&df, "region", "revenue").unwrap();
+        let json = b.get_json_representation();
+        assert!(json.contains("[{\"category\":\"North\",\"amount\":12.5}]"));
+
+        let c = LineChart::from_dataframe(&df, "day", "revenue").unwrap();
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"x\":2.0,\"y\":null,\"z\":0}"));
+        assert!(json.contains("\"name\":\"x\",\"type\":\"linear\""));
+
+        let dates = df.column("day").unwrap().cast(&DataType::Date).unwrap();
+        let columns = vec![
+            df.column("region").unwrap().clone(),
+            dates,
+            df.column("revenue").unwrap().clone(),
+        ];
+        let df = DataFrame::new(columns).unwrap();
+        let a = AreaChart::from_dataframe(&df, "day", "revenue").unwrap();
+        let json = a.get_json_representation();
+        assert!(json.contains("{\"u\":86400000.0,\"v\":12.5}"));
+        assert!(json.contains("\"type\":\"utc\""));
+        assert!(LineChart::from_dataframe(&df, "region", "revenue").is_err());
+    }
+    #[test]
+    #[cfg(feature = "polars")]
+    fn test_dataframe_dates_and_missing_values() {
+        use polars::prelude::*;
+        let df = df!(
+            "at" => &[Some(1_000i64), Some(2_000), None, Some(4_000)],
+            "name" => &[Some("a"), None, Some("c"), Some("d")],
+            "value" => &[Some(1.5), Some(2.5), Some(3.5), None]
+        ).unwrap();
+        let datetimes = |unit: TimeUnit| {
+            let at = df.column("at").unwrap().cast(&DataType::Datetime(unit, None)).unwrap();
+            let columns = vec![at, df.column("value").unwrap().clone()];
+            DataFrame::new(columns).unwrap()
+        };
+
+        let s = ScatterPlot::from_dataframe(&datetimes(TimeUnit::Milliseconds), "at", "value").unwrap();
+        let json = s.get_json_representation();
+        assert!(json.contains("[{\"x\":1000.0,\"y\":1.5},{\"x\":2000.0,\"y\":2.5}]"));
+        assert!(json.contains("\"type\":\"utc\""));
+
+        let c = LineChart::from_dataframe(&datetimes(TimeUnit::Microseconds), "at", "value").unwrap();
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"x\":1.0,\"y\":1.5,\"z\":0}"));
+        assert!(json.contains("{\"x\":4.0,\"y\":null,\"z\":0}"));
+        assert!(!json.contains("3.5"));
+
+        let a = AreaChart::from_dataframe(&datetimes(TimeUnit::Nanoseconds), "at", "value").unwrap();
+        assert!(a.get_json_representation().contains("{\"u\":0.001,\"v\":1.5}"));
+
+        let b = BarChart::from_dataframe(&df, "name", "value").unwrap();
+        let json = b.get_json_representation();
+        assert!(json.contains("[{\"category\":\"a\",\"amount\":1.5},{\"category\":\"c\",\"amount\":3.5}]"));
+    }
+    #[test]
+    fn test_report() {
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 3);
+        let mut c = LineChart::new();
+        c.add_data(1, 2, 0);
+        let mut report = Report::new("test_report", "Tests & Results");
+        report.show_table_of_contents(true);
+        report.add_heading("Bars");
+        report.add_text("First paragraph.\n\nSecond <paragraph>.");
+        report.add_chart(&b);
+        report.add_heading("Lines");
+        report.add_chart(&c);
+        let html = report.to_html().unwrap();
+        assert!(html.contains("<li><a href=\"#section1\">Lines</a></li>"));
+        assert!(html.contains("<p>Second &lt;paragraph&gt;.</p>"));
+        assert!(html.contains("<div id=\"chart1\">"));
+        render_report(&report).unwrap();
+    }
+    #[test]
+    fn test_spec_round_trip() {
+        use super::backend::spec::Spec;
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 3);
+        b.set_data_url("data/sales.json", Format::json());
+        let mut spec = Spec::from_json_str(&b.get_json_representation()).unwrap();
+        assert_eq!(spec, Spec::from_chart(&b));
+        assert_eq!(spec.data_names(), vec!["table"]);
+        assert!(spec.values("table").is_none());
+
+        let mut m = ::serde_json::Map::new();
+        m.insert(String::from("category"), "B".into());
+        m.insert(String::from("amount"), 5.into());
+        assert!(spec.set_values("table", vec![m.into()]));
+        assert!(!spec.set_values("missing", vec![]));
+        spec.values_mut("table").unwrap().clear();
+        spec.set("width", 600.into());
+        assert_eq!(spec.get("width"), Some(&600.into()));
+
+        let json = spec.to_json_string();
+        assert!(json.contains("\"data\":[{\"name\":\"table\",\"values\":[]}]"));
+        assert_eq!(Spec::from_json_str(&json).unwrap(), spec);
+    }
+    #[test]
+    fn test_spec_graphable() {
+        use super::backend::spec::Spec;
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 3);
+        let mut spec = Spec::from_json_str(&b.get_json_representation()).unwrap();
+        spec.set_identifier("test_spec_graphable");
+        spec.set_dimensions((200, 400));
+        spec.set_color("teal");
+        spec.set_title("Revenue");
+        spec.add_transform(Transform::filter("datum.y > 0"));
+        assert_eq!(spec.get_dimensions(), (200, 400));
+
+        let json = spec.to_json_string();
+        assert!(json.contains("\"config\":{\"mark\":{\"color\":\"teal\"}}"));
+        assert!(json.contains("\"height\":200"));
+        assert!(json.contains("\"title\":{\"text\":\"Revenue\"}"));
+        assert!(json.contains("\"transform\":[{\"expr\":\"datum.y > 0\",\"type\":\"filter\"}]"));
+        assert_eq!(Spec::from_json_str(&json).unwrap(), spec);
+        render_graph(&spec, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_json_output() {
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 3);
+        let json = b.to_json_string().unwrap();
+        assert_eq!(json, b.get_json_representation());
+
+        let mut written = vec![];
+        b.to_writer(&mut written).unwrap();
+        assert_eq!(String::from_utf8(written).unwrap(), json);
+
+        b.save_json("gust_build/saved/bar.json").unwrap();
+        assert_eq!(fs::read_to_string("gust_build/saved/bar.json").unwrap(), json);
+    }
+    #[test]
+    #[cfg(feature = "export")]
+    fn test_export() {
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 3);
+        // vega-cli may not be installed, in which case the error says where to get it
+        match b.save_svg("gust_build/export/bar.svg") {
+            Ok(()) => assert!(fs::read_to_string("gust_build/export/bar.svg").unwrap().contains("<svg")),
+            Err(e) => assert!(e.to_string().contains("vega-cli")),
+        }
+    }
+    #[test]
+    fn test_preview_server() {
+        use super::frontend::serve::Server;
+        use std::io::{Read, Write};
+        use std::net::TcpStream;
+        let get = |url: &str, path: &str| {
+            let mut stream = TcpStream::connect(&url[7..url.len() - 1]).unwrap();
+            write!(stream, "GET {} HTTP/1.1\r\n\r\n", path).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let server = Server::start("127.0.0.1:0").unwrap();
+        let mut b = BarChart::new();
+        b.set_description("Preview");
+        server.refresh(&b).unwrap();
+        b.add_data(String::from("A"), 3);
+        server.refresh(&b).unwrap();
+        let charts = get(&server.url(), "/charts.json");
+        assert!(charts.starts_with("HTTP/1.1 200 OK"));
+        assert_eq!(charts.matches("\"description\":\"Preview\"").count(), 1);
+        assert!(charts.contains("\"category\":\"A\""));
+        assert!(get(&server.url(), "/").contains("new EventSource('/events')"));
+        assert!(get(&server.url(), "/missing").starts_with("HTTP/1.1 404"));
+    }
+    #[test]
+    fn test_save_html() {
+        let mut b = BarChart::new();
+        b.set_description("Sales <2018>");
+        b.add_data(String::from("</script>"), 3);
+        b.save_html("gust_build/standalone/sales.html").unwrap();
+        let html = fs::read_to_string("gust_build/standalone/sales.html").unwrap();
+        assert!(html.contains("<title>Sales &lt;2018&gt;</title>"));
+        assert!(html.contains("vega-embed@6"));
+        assert!(html.contains("\"category\":\"<\\/script>\""));
+    }
+    #[test]
+    fn test_preview_path() {
+        use super::frontend::render::preview_path;
+        let mut b = BarChart::new();
+        b.set_identifier("preview");
+        let path = preview_path(&b);
+        assert!(path.starts_with(::std::env::temp_dir()));
+        assert!(path.ends_with("gust/preview.html"));
+    }
+    #[test]
+    fn test_dashboard() {
+        use super::frontend::dashboard::Dashboard;
+        let mut b = BarChart::new();
+        b.set_description("Bars & more");
+        let c = LineChart::new();
+        let mut dashboard = Dashboard::new("test_dashboard", "Dashboard");
+        dashboard.set_columns(3);
+        dashboard.add_chart(&b);
+        dashboard.add_chart_spanning(&c, 2, 1);
+        dashboard.add_chart_spanning(&b, 5, 2);
+        let html = dashboard.to_html().unwrap();
+        assert!(html.contains("grid-template-columns: repeat(3, auto)"));
+        assert!(html.contains("style=\"grid-column: span 2; grid-row: span 1;\"><div id=\"chart1\">"));
+        assert!(html.contains("style=\"grid-column: span 3; grid-row: span 2;\"><div id=\"chart2\">"));
+        assert!(html.contains("<figcaption>Bars &amp; more</figcaption>"));
+        render_dashboard(&dashboard).unwrap();
+    }
+    #[test]
+    fn test_sidecar_data_files() {
+        let mut b = BarChart::new();
+        b.add_data(String::from("A, B"), 3);
+        b.set_identifier("sidecar_bar_chart");
+        render_graph_with_data(&b, FileType::JSON, DataFormat::CSV).unwrap();
+        let spec = fs::read_to_string("gust_build/json/sidecar_bar_chart.json").unwrap();
+        assert!(spec.contains("\"url\":\"data/sidecar_bar_chart_table.csv\""));
+        assert!(!spec.contains("\"values\""));
+        let csv = fs::read_to_string("gust_build/json/data/sidecar_bar_chart_table.csv").unwrap();
+        assert_eq!(csv, "amount,category\n3.0,\"A, B\"\n");
+    }
+    #[test]
+    fn test_golden_spec() {
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 3);
+        let path = "gust_build/golden/bar_chart.json";
+        let _ = fs::remove_file(path);
+        testing::assert_golden(&b, path);
+        assert!(testing::matches_golden(&b, path).unwrap());
+        b.set_metadata("built", "2018-06-01");
+        assert!(testing::matches_golden(&b, path).unwrap());
+        assert!(b.get_json_representation().contains("\"usermeta\":{\"built\":\"2018-06-01\"}"));
+        b.add_data(String::from("B"), 4);
+        assert!(!testing::matches_golden(&b, path).unwrap());
+        assert_eq!(testing::normalize("{\"b\":1,\"a\":{\"d\":2,\"c\":3}}").unwrap(),
+                   "{\n  \"a\": {\n    \"c\": 3,\n    \"d\": 2\n  },\n  \"b\": 1\n}");
+    }
+    #[test]
+    fn test_scatter_plot() {
+        let mut s = ScatterPlot::new();
+        s.add_data(1.5, 2.0);
+        s.add_sized_data(2.5, 1.0, 8.0);
+        s.add_trendline(Method::Linear, false);
+        s.set_identifier("test_scatter_plot");
+        let json = s.get_json_representation();
+        assert!(json.contains("\"values\":[{\"x\":1.5,\"y\":2.0},{\"size\":8.0,\"x\":2.5,\"y\":1.0}]"));
+        assert!(json.contains("\"range\":[16.0,400.0]"));
+        assert!(json.contains("{\"test\":\"isValid(datum.size)\",\"field\":\"size\",\"scale\":\"size\"}"));
+        render_graph(&s, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_legends() {
+        use super::backend::elements::legend::{Legend, LegendKind};
+        use super::backend::elements::mark::SymbolShape;
+        let mut s = ScatterPlot::new();
+        s.add_bubble(2.5, 1.0, 8.0, "Europe");
+        assert!(s.add_legend_for(LegendKind::Size, "Population"));
+        assert!(!s.add_legend_for(LegendKind::Shape, "Continent"));
+        s.add_legend(Legend::of(LegendKind::Color).symbol_type(SymbolShape::Square).symbol_size(50.0));
+        let json = s.get_json_representation();
+        assert!(json.contains("\"legends\":[{\"fill\":\"color\"},{\"size\":\"size\",\"title\":\"Population\"},{\"fill\":\"color\",\"symbolType\":\"square\",\"symbolSize\":50.0}]"));
+    }
+    #[test]
+    fn test_right_to_left_legends() {
+        use super::backend::elements::general::Orientation;
+        use super::backend::elements::legend::{Legend, LegendKind};
+        let mut s = ScatterPlot::new();
+        s.add_bubble(2.5, 1.0, 8.0, "Europe");
+        s.add_legend(Legend::of(LegendKind::Size).orient(Orientation::Bottom));
+        s.set_right_to_left(true);
+        let json = s.get_json_representation();
+        assert!(json.contains("\"legends\":[{\"fill\":\"color\",\"orient\":\"left\"},{\"size\":\"size\",\"orient\":\"bottom\"}]"));
+    }
+    #[test]
+    fn test_bubble_chart() {
+        let mut s = ScatterPlot::new();
+        s.add_data(1.5, 2.0);
+        s.add_bubble(2.5, 1.0, 8.0, "Europe");
+        s.set_identifier("test_bubble_chart");
+        let json = s.get_json_representation();
+        assert!(json.contains("{\"group\":\"Europe\",\"size\":8.0,\"x\":2.5,\"y\":1.0}"));
+        assert!(json.contains("{\"test\":\"isValid(datum.group)\",\"field\":\"group\",\"scale\":\"color\"}"));
+        assert!(json.contains("\"legends\":[{\"fill\":\"color\"}]"));
+        render_graph(&s, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_bound_signals() {
+        use super::backend::elements::signal::{Binding, Signal};
+        let mut s = ScatterPlot::new();
+        s.add_signal(Signal::slider("bandwidth", 0.0..1.0, 0.1).label("Bandwidth"));
+        s.add_signal(Signal::radio("method", &["linear", "quad"]));
+        s.add_signal(Signal::checkbox("show_points", true));
+        s.add_signal(Signal::new("size", 40).bind(Binding::select(&[20, 40, 80])));
+        let json = s.get_json_representation();
+        assert!(json.contains("{\"name\":\"bandwidth\",\"value\":0.0,\"bind\":{\"input\":\"range\",\"min\":0.0,\"max\":1.0,\"step\":0.1,\"name\":\"Bandwidth\"}}"));
+        assert!(json.contains("{\"name\":\"method\",\"value\":\"linear\",\"bind\":{\"input\":\"radio\",\"options\":[\"linear\",\"quad\"]}}"));
+        assert!(json.contains("{\"name\":\"show_points\",\"value\":true,\"bind\":{\"input\":\"checkbox\"}}"));
+        assert!(json.contains("{\"name\":\"size\",\"value\":40,\"bind\":{\"input\":\"select\",\"options\":[20,40,80]}}"));
+    }
+    #[test]
+    fn test_interpolation() {
+        let mut a = AreaChart::new();
+        a.set_interpolation(Interpolation::StepAfter);
+        let json = a.get_json_representation();
+        assert!(json.contains("{\"name\":\"interpolate\",\"value\":\"step-after\",\"bind\":{\"input\":\"select\",\"options\":[\"basis\",\"cardinal\",\"catmull-rom\",\"linear\",\"monotone\",\"natural\",\"step\",\"step-after\",\"step-before\"]}}"));
+
+        let mut s = StackedAreaChart::new();
+        s.set_interpolation(Interpolation::Step);
+        assert!(s.get_json_representation().contains("\"interpolate\":{\"value\":\"step\"}"));
+    }
+    #[test]
+    fn test_floating_point_data() {
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 2.75);
+        b.add_data(String::from("B"), 3u8);
+        assert!(b.get_json_representation().contains("\"values\":[{\"category\":\"A\",\"amount\":2.75},{\"category\":\"B\",\"amount\":3.0}]"));
+
+        let mut a = AreaChart::new();
+        a.add_data(0.5, 1.25);
+        assert!(a.get_json_representation().contains("{\"u\":0.5,\"v\":1.25}"));
+
+        let mut c = LineChart::new();
+        c.add_point(1.5, 10i64);
+        assert!(c.get_json_representation().contains("{\"x\":1.5,\"y\":10.0,\"z\":0}"));
+    }
+    #[test]
+    fn test_add_data_iter() {
+        let mut b = BarChart::new();
+        b.add_data_iter(vec![(String::from("A"), 1), (String::from("B"), 2)]);
+        assert!(b.get_json_representation().contains("\"values\":[{\"category\":\"A\",\"amount\":1.0},{\"category\":\"B\",\"amount\":2.0}]"));
+
+        let mut c = LineChart::new();
+        c.add_data_iter((0..3).map(|i| (i, i * i, 0)));
+        assert!(c.get_json_representation().contains("{\"x\":2.0,\"y\":4.0,\"z\":0}"));
+
+        let mut g = GroupedBarChart::new();
+        g.add_data_iter(vec![("2017", "North", 12.0), ("2017", "South", 9.5)]);
+        assert!(g.get_json_representation().contains("{\"category\":\"2017\",\"series\":\"South\",\"value\":9.5}"));
+    }
+    #[test]
+    fn test_data_url() {
+        use serde_json::Value;
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 3);
+        b.set_data_url("https://example.com/sales.json", Format::json());
+        let spec: Value = ::serde_json::from_str(&b.get_json_representation()).unwrap();
+        let expected: Value = ::serde_json::from_str(
+            r#"[{"name":"table","url":"https://example.com/sales.json","format":{"type":"json"}}]"#,
+        ).unwrap();
+        assert_eq!(spec["data"], expected);
+
+        let mut c = LineChart::new();
+        c.set_data_url("data/temperatures.csv", Format::csv());
+        let spec: Value = ::serde_json::from_str(&c.get_json_representation()).unwrap();
+        let expected: Value = ::serde_json::from_str(
+            r#"{"name":"table","url":"data/temperatures.csv","format":{"type":"csv","parse":"auto"}}"#,
+        ).unwrap();
+        assert_eq!(spec["data"][0], expected);
+    }
+    #[test]
+    fn test_set_records() {
+        #[derive(Serialize)]
+        struct Sale {
+            region: &'static str,
+            revenue: f64,
+        }
+        let sales = [
+            Sale { region: "North", revenue: 12.5 },
+            Sale { region: "South", revenue: 7.0 },
+        ];
+        let mut b = BarChart::new();
+        b.add_data(String::from("ignored"), 1);
+        b.set_records(&sales, &[("category", "region"), ("amount", "revenue")]).unwrap();
+        let json = b.get_json_representation();
+        assert!(json.contains("\"data\":[{\"name\":\"table\",\"transform\":[{\"as\":\"category\",\"expr\":\"datum[\\\"region\\\"]\",\"type\":\"formula\"},{\"as\":\"amount\",\"expr\":\"datum[\\\"revenue\\\"]\",\"type\":\"formula\"}],\"values\":[{\"region\":\"North\",\"revenue\":12.5},{\"region\":\"South\",\"revenue\":7.0}]}]"));
+    }
+    #[test]
+    fn test_gaps() {
+        let mut c = LineChart::new();
+        c.add_point(1, 2);
+        c.add_gap(2, 0);
+        c.add_point(3, 4);
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"x\":2.0,\"y\":null,\"z\":0}"));
+        assert!(json.contains("\"defined\":{\"signal\":\"isValid(datum.y)\"}"));
+
+        let mut a = AreaChart::new();
+        a.add_gap(5);
+        let json = a.get_json_representation();
+        assert!(json.contains("{\"u\":5.0,\"v\":null}"));
+        assert!(json.contains("\"defined\":{\"signal\":\"isValid(datum.v)\"}"));
+    }
+    #[test]
+    fn test_transform_pipeline() {
+        use super::backend::elements::transform::{Aggregate, AggregateOp, Bin, Order};
+        let mut b = BarChart::new();
+        b.add_transform(Transform::filter("datum.amount > 0"));
+        b.add_transform(Transform::Bin(Bin::new("amount", 0.0, 100.0).maxbins(10)));
+        b.add_transform(Transform::Aggregate(
+            Aggregate::new().groupby("bin0").op(AggregateOp::Count, None, "count"),
+        ));
+        b.add_transform(Transform::collect("bin0", Order::Ascending));
+        let json = b.get_json_representation();
+        assert!(json.contains("{\"type\":\"bin\",\"field\":\"amount\",\"extent\":[0.0,100.0],\"maxbins\":10}"));
+        assert!(json.contains(
+            "{\"type\":\"aggregate\",\"groupby\":[\"bin0\"],\"ops\":[\"count\"],\"fields\":[null],\"as\":[\"count\"]}"
+        ));
+        assert!(json.contains("{\"type\":\"collect\",\"sort\":{\"field\":[\"bin0\"],\"order\":[\"ascending\"]}}"));
+    }
+    #[test]
+    fn test_pivot_transform() {
+        use super::backend::elements::transform::AggregateOp;
+        let mut b = BarChart::new();
+        b.add_transform(Transform::pivot("category", "amount", &["region"]));
+        b.add_transform(Transform::Pivot {
+            field: String::from("region"),
+            value: String::from("amount"),
+            groupby: vec![],
+            op: Some(AggregateOp::Max),
+        });
+        let json = b.get_json_representation();
+        assert!(json.contains(
+            "{\"type\":\"pivot\",\"field\":\"category\",\"value\":\"amount\",\"groupby\":[\"region\"]}"
+        ));
+        assert!(json.contains("{\"type\":\"pivot\",\"field\":\"region\",\"value\":\"amount\",\"op\":\"max\"}"));
+    }
+    #[test]
+    fn test_window_transform() {
+        use super::backend::elements::transform::{Order, Window, WindowOp};
+        let mut c = LineChart::new();
+        c.add_transform(Transform::Window(
+            Window::new()
+                .sort("x", Order::Ascending)
+                .groupby("z")
+                .op(WindowOp::RowNumber, None, "rank")
+                .op(WindowOp::Sum, Some("y"), "total")
+                .frame(None, Some(0)),
+        ));
+        let json = c.get_json_representation();
+        assert!(json.contains(
+            "{\"type\":\"window\",\"sort\":{\"field\":[\"x\"],\"order\":[\"ascending\"]},\"groupby\":[\"z\"],"
+        ));
+        assert!(json.contains(
+            "\"ops\":[\"row_number\",\"sum\"],\"fields\":[null,\"y\"],\"as\":[\"rank\",\"total\"],\"frame\":[null,0]}"
+        ));
+    }
+    #[test]
+    fn test_loess() {
+        let mut c = LineChart::new();
+        for i in 0..20 {
+            c.add_data(i, i * i, 0);
+        }
+        c.add_loess(0.5);
+        let json = c.get_json_representation();
+        assert!(json.contains(
+            "{\"name\":\"loess0\",\"source\":\"table\",\"transform\":[{\"type\":\"loess\",\"x\":\"x\",\"y\":\"y\",\"groupby\":[\"z\"],\"bandwidth\":0.5}]}"
+        ));
+        assert!(json.contains("\"facet\":{\"name\":\"loess0_series\",\"data\":\"loess0\""));
+        assert!(json.contains("{\"name\":\"x\",\"type\":\"linear\""));
+
+        let mut b = BarChart::new();
+        b.add_transform(Transform::loess("category", "amount", 0.3));
+        let json = b.get_json_representation();
+        assert!(json.contains("{\"type\":\"loess\",\"x\":\"category\",\"y\":\"amount\",\"bandwidth\":0.3}"));
+    }
+    #[test]
+    fn test_fold_transform() {
+        let mut b = BarChart::new();
+        b.add_transform(Transform::fold(&["q1", "q2", "q3"]));
+        let json = b.get_json_representation();
+        assert!(json.contains("{\"type\":\"fold\",\"fields\":[\"q1\",\"q2\",\"q3\"]}"));
+    }
+    #[test]
+    fn test_kde_transform() {
+        use super::backend::elements::transform::Kde;
+        let mut c = LineChart::new();
+        c.add_transform(Transform::Kde(Kde::new("y").groupby("z").bandwidth(0.5).extent(0.0, 100.0)));
+        c.add_transform(Transform::Kde(Kde::new("x").steps(50).cumulative(true)));
+        let json = c.get_json_representation();
+        assert!(json.contains(
+            "{\"type\":\"kde\",\"field\":\"y\",\"groupby\":[\"z\"],\"bandwidth\":0.5,\"extent\":[0.0,100.0]}"
+        ));
+        assert!(json.contains("{\"type\":\"kde\",\"field\":\"x\",\"steps\":50,\"cumulative\":true}"));
+    }
+    #[test]
+    fn test_client_side_aggregation() {
+        use super::backend::data::{count_by, mean_by, sum_by};
+        let sales = vec![("North", 12.5), ("South", 7.0), ("North", 3.5)];
+        let key = |s: &&(&str, f64)| s.0.to_string();
+        assert_eq!(
+            sum_by(&sales, key, |s| s.1),
+            vec![(String::from("North"), 16.0), (String::from("South"), 7.0)]
+        );
+        assert_eq!(mean_by(&sales, key, |s| s.1)[0], (String::from("North"), 8.0));
+        assert_eq!(count_by(&sales, |s| s.0), vec![("North", 2), ("South", 1)]);
+
+        let mut b = BarChart::new();
+        b.add_data_iter(count_by(&sales, key));
+        assert!(b.get_json_representation().contains("{\"category\":\"North\",\"amount\":2.0}"));
+    }
+    #[test]
+    fn test_downsampling() {
+        use super::backend::data::{downsample, lttb};
+        let points = [(0.0, 0.0), (1.0, 0.0), (2.0, 5.0), (3.0, 0.0), (4.0, 0.0)];
+        assert_eq!(lttb(&points, 3), vec![0, 2, 4]);
+        assert_eq!(lttb(&points, 10), vec![0, 1, 2, 3, 4]);
+        let keep = downsample((0..10).map(|i| (i % 2, i as f64, Some((i / 2) as f64))), 3);
+        assert_eq!(keep.iter().filter(|&&k| k).count(), 6);
+        assert!(keep[0] && keep[1] && keep[8] && keep[9]);
+
+        let mut c = LineChart::new();
+        c.add_data_iter((0..1000).map(|i| (i, i % 7, 0)));
+        c.add_data_iter((0..10).map(|i| (i, i, 1)));
+        c.add_gap(1000, 0);
+        c.downsample_to(50);
+        let json = c.get_json_representation();
+        assert_eq!(json.matches("\"z\":0").count(), 51);
+        assert_eq!(json.matches("\"z\":1").count(), 10);
+        assert!(json.contains("{\"x\":999.0,\"y\":5.0,\"z\":0}"));
+
+        c.add_data(::std::f64::NAN, 1, 0);
+        c.downsample_to(20);
+        assert!(c.get_json_representation().contains("{\"x\":null,\"y\":1.0,\"z\":0}"));
+        let mut a = AreaChart::new();
+        a.add_data_iter((0..100).map(|i| (i, i % 3)));
+        a.add_data(::std::f64::NAN, 1);
+        a.downsample_to(10);
+        assert!(a.get_json_representation().contains("{\"u\":null,\"v\":1.0}"));
+    }
+    #[test]
+    fn test_error_bars() {
+        let mut b = BarChart::new();
+        b.add_data(String::from("A"), 10);
+        b.add_data_with_error(String::from("B"), 20, ErrorBar::Symmetric(2.5));
+        b.set_identifier("test_error_bars");
+        let json = b.get_json_representation();
+        assert!(json.contains("{\"category\":\"B\",\"amount\":20.0,\"lo\":17.5,\"hi\":22.5}"));
+        assert!(json.contains("{\"name\":\"error_bars0\",\"source\":\"table\",\"transform\":[{\"type\":\"filter\",\"expr\":\"isValid(datum.lo) && isValid(datum.hi)\"}]}"));
+        assert!(json.contains("\"x\":{\"field\":\"category\",\"scale\":\"xscale\",\"band\":0.5,\"offset\":-4.0}"));
+        render_graph(&b, FileType::HTML).unwrap();
+
+        let mut c = LineChart::new();
+        c.add_data_with_error(1, 5, 0, ErrorBar::Range(4.0, 7.0));
+        assert!(c.get_json_representation().contains("{\"x\":1.0,\"y\":5.0,\"z\":0,\"lo\":4.0,\"hi\":7.0}"));
+    }
+    #[test]
+    fn test_violin_plot() {
+        let mut v = ViolinPlot::new();
+        v.add_samples("control", &[4.1, 5.0, 5.2, 5.9]);
+        v.add_sample("treated", 6.2);
+        v.set_bandwidth(0.5);
+        v.set_identifier("test_violin_plot");
+        let json = v.get_json_representation();
+        assert!(json.contains("{\"name\":\"density\",\"source\":\"table\",\"transform\":[{\"type\":\"kde\",\"field\":\"value\",\"groupby\":[\"group\"],\"bandwidth\":0.5}]}"));
+        assert!(json.contains("\"facet\":{\"name\":\"violin\",\"data\":\"density\",\"groupby\":\"group\"}"));
+        render_graph(&v, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_ridgeline_plot() {
+        let mut r = RidgelinePlot::new();
+        r.add_samples("January", &[-2.0, 0.5, 1.0, 3.5]);
+        r.add_samples("July", &[18.0, 21.5, 22.0, 24.5]);
+        r.set_overlap(1.5);
+        r.set_identifier("test_ridgeline_plot");
+        let json = r.get_json_representation();
+        assert!(json.contains("\"range\":{\"signal\":\"[bandwidth('y'), bandwidth('y') * (1 - 1.5)]\"}"));
+        assert!(json.contains("\"facet\":{\"name\":\"ridge\",\"data\":\"density\",\"groupby\":\"group\"}"));
+        render_graph(&r, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_gauge() {
+        let mut g = Gauge::new();
+        g.set_range(0.0, 120.0);
+        g.set_value(87.0);
+        g.add_threshold(100.0, "red");
+        g.add_threshold(80.0, "orange");
+        g.add_band(100.0, 120.0, "red");
+        g.set_identifier("test_gauge");
+        let json = g.get_json_representation();
+        assert!(json.contains("{\"name\":\"table\",\"values\":[{\"value\":87.0}]}"));
+        assert!(json.contains("\"domain\":[0.0,120.0],\"range\":{\"signal\":\"[-PI / 2, PI / 2]\"}"));
+        assert!(json.contains("\"domain\":[80.0,100.0],\"range\":[\"steelblue\",\"orange\",\"red\"]"));
+        g.add_threshold(::std::f64::NAN, "black");
+        assert_eq!(g.get_json_representation(), json);
+        render_graph(&g, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_lollipop_chart() {
+        let mut l = LollipopChart::new();
+        l.add_data(String::from("A"), 28);
+        l.add_data(String::from("B"), 55);
+        l.set_identifier("test_lollipop_chart");
+        let json = l.get_json_representation();
+        assert!(json.contains("{\"amount\":28.0,\"category\":\"A\"}"));
+        assert!(json.contains("\"type\":\"rule\""));
+        assert!(json.contains("\"type\":\"symbol\""));
+        render_graph(&l, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_network_graph() {
+        let mut n = NetworkGraph::new();
+        n.add_node("Alice");
+        n.add_edge("Alice", "Bob");
+        n.add_edge("Bob", "Alice");
+        n.set_link_distance(60.0);
+        n.set_identifier("test_network_graph");
+        let json = n.get_json_representation();
+        assert!(json.contains("{\"name\":\"nodes\",\"values\":[{\"id\":\"Alice\"},{\"id\":\"Bob\"}]}"));
+        assert!(json.contains("{\"force\":\"link\",\"links\":\"links\",\"id\":\"datum.id\",\"distance\":60.0}"));
+        assert!(json.contains("\"require\":{\"signal\":\"force\"},\"sourceX\":\"datum.source.x\""));
+        render_graph(&n, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_funnel_chart() {
+        let mut f = FunnelChart::new();
+        f.add_stage("Visits", 5000.0);
+        f.add_stage("Purchase", 380.0);
+        f.set_identifier("test_funnel_chart");
+        let json = f.get_json_representation();
+        assert!(json.contains("\"xc\":{\"signal\":\"width / 2\"},\"width\":{\"field\":\"value\",\"scale\":\"x\"}"));
+        render_graph(&f, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_word_cloud() {
+        let mut w = WordCloud::new();
+        w.add_word("rust", 40.0);
+        w.add_word("vega", 25.0);
+        w.set_rotations(&[0.0, 90.0]);
+        w.set_identifier("test_word_cloud");
+        let json = w.get_json_representation();
+        assert!(json.contains("{\"type\":\"formula\",\"expr\":\"[0, 90][floor(random() * 2)]\",\"as\":\"angle\"}"));
+        assert!(json.contains("\"type\":\"wordcloud\""));
+        assert!(json.contains("\"fontSizeRange\":[12.0,56.0]"));
+        render_graph(&w, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_sankey_chart() {
+        let mut s = SankeyChart::new();
+        s.add_flow("Solar", "Grid", 30.0);
+        s.add_flow("Wind", "Grid", 50.0);
+        s.add_flow("Grid", "Homes", 80.0);
+        s.set_node_padding(0.0);
+        s.set_identifier("test_sankey_chart");
+        let json = s.get_json_representation();
+        assert!(json.contains("{\"column\":0.5,\"name\":\"Grid\",\"value\":80.0,\"y0\":0.0,\"y1\":1.0}"));
+        assert!(json.contains("{\"column\":1.0,\"name\":\"Homes\",\"value\":80.0,\"y0\":0.0,\"y1\":1.0}"));
+        assert!(json.contains("{\"column0\":0.0,\"column1\":0.5,\"size\":0.625,\"source\":\"Wind\",\"target\":\"Grid\",\"value\":50.0,\"y0\":0.6875,\"y1\":0.6875}"));
+        render_graph(&s, FileType::HTML).unwrap();
+
+        // a flow whose weight isn't a number doesn't break the layout
+        let mut s = SankeyChart::new();
+        s.add_flow("Solar", "Grid", ::std::f64::NAN);
+        s.add_flow("Solar", "Homes", 10.0);
+        s.add_flow("Wind", "Grid", 5.0);
+        assert!(s.get_json_representation().contains("\"name\":\"Homes\""));
+    }
+    #[test]
+    fn test_radar_chart() {
+        let mut r = RadarChart::new(&["Speed", "Power", "Range"]);
+        r.add_series("Model A", &[7.0, 5.0, 9.0]);
+        r.set_identifier("test_radar_chart");
+        let json = r.get_json_representation();
+        assert!(json.contains("\"values\":[{\"key\":\"Speed\"},{\"key\":\"Power\"},{\"key\":\"Range\"}]"));
+        assert!(json.contains("{\"key\":\"Range\",\"series\":\"Model A\",\"value\":9.0}"));
+        assert!(json.contains("\"interpolate\":{\"value\":\"linear-closed\"}"));
+        render_graph(&r, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_gantt_chart() {
+        let mut g = GanttChart::new();
+        g.add_task("Design", 1514764800000, 1515369600000);
+        g.set_identifier("test_gantt_chart");
+        let json = g.get_json_representation();
+        assert!(json.contains("{\"end\":1515369600000,\"start\":1514764800000,\"task\":\"Design\"}"));
+        assert!(json.contains("\"x2\":{\"field\":\"end\",\"scale\":\"x\"}"));
+        render_graph(&g, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_candlestick_chart() {
+        let mut c = CandlestickChart::new();
+        c.add_candle(1514764800000, 24.3, 25.9, 23.8, 25.1);
+        c.add_candle(1514851200000, 25.1, 25.4, 22.9, 23.2);
+        c.set_identifier("test_candlestick_chart");
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"close\":25.1,\"high\":25.9,\"low\":23.8,\"open\":24.3,\"time\":1514764800000}"));
+        assert!(json.contains("\"fill\":[{\"test\":\"datum.close >= datum.open\",\"value\":\"#06982d\"},{\"value\":\"#ae1325\"}]"));
+        render_graph(&c, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_stream_graph() {
+        let mut s = StackedAreaChart::new();
+        for i in 0..5 {
+            s.add_data(i as f64, "rock", (i * 2) as f64);
+            s.add_data(i as f64, "jazz", (5 - i) as f64);
+        }
+        s.set_stack_offset(StackOffset::Wiggle);
+        s.set_identifier("test_stream_graph");
+        let json = s.get_json_representation();
+        assert!(json.contains("\"type\":\"stack\",\"groupby\":[\"x\"],\"sort\":{\"field\":\"series\"},\"field\":\"value\",\"offset\":\"wiggle\""));
+        assert!(json.contains("\"domain\":{\"data\":\"table\",\"fields\":[\"y0\",\"y1\"]}"));
+        render_graph(&s, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_multi_line_chart() {
+        let mut c = MultiLineChart::new();
+        c.add_series("Berlin", &[(1.0, 0.5), (2.0, 2.1)]);
+        c.add_series("Lisbon", &[(1.0, 11.2)]);
+        c.set_identifier("test_multi_line_chart");
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"series\":\"Lisbon\",\"x\":1.0,\"y\":11.2}"));
+        assert!(json.contains("\"legends\":[{\"stroke\":\"color\"}]"));
+        render_graph(&c, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_grouped_bar_chart() {
+        let mut g = GroupedBarChart::new();
+        g.add_data("2017", "North", 12.0);
+        g.add_data("2017", "South", 9.5);
+        g.set_identifier("test_grouped_bar_chart");
+        let json = g.get_json_representation();
+        assert!(json.contains("\"from\":{\"facet\":{\"name\":\"category\",\"data\":\"table\",\"groupby\":\"category\"}}"));
+        assert!(json.contains("\"scales\":[{\"name\":\"series\",\"type\":\"band\",\"domain\":{\"data\":\"category\",\"field\":\"series\"},\"range\":{\"signal\":\"[0, bandwidth('x')]\"}}]"));
+        render_graph(&g, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_pie_chart() {
+        let mut p = PieChart::new();
+        p.add_slice("Rent", 1200.0);
+        p.add_slice("Food", 400.0);
+        p.set_inner_radius(0.5);
+        p.show_labels(true);
+        p.set_identifier("test_pie_chart");
+        let json = p.get_json_representation();
+        assert!(json.contains("\"transform\":[{\"type\":\"pie\",\"field\":\"value\"}]"));
+        assert!(json.contains("\"innerRadius\":{\"signal\":\"min(width, height) / 2 * 0.5\"}"));
+        assert!(json.contains("\"type\":\"arc\""));
+        render_graph(&p, FileType::HTML).unwrap();
+    }
+    #[test]
+    fn test_right_to_left() {
+        let mut c = LineChart::new();
+        c.add_data(1, 2, 0);
+        let ltr = c.get_json_representation();
+        c.set_right_to_left(true);
+        let json = c.get_json_representation();
+        assert!(json.contains("\"reverse\":true"));
+        assert!(json.contains("{\"orient\":\"right\",\"scale\":\"y\""));
+        assert!(json.contains("\"labelAlign\":\"left\""));
+        c.set_right_to_left(false);
+        assert_eq!(c.get_json_representation(), ltr);
+
+        c.set_right_to_left(true);
+        c.add_series_secondary("rainfall", vec![(1, 120)]);
+        let json = c.get_json_representation();
+        assert!(json.contains("{\"orient\":\"left\",\"scale\":\"y2\",\"labelAlign\":\"right\"}"));
+        c.set_right_to_left(false);
+        assert!(c.get_json_representation().contains("{\"orient\":\"right\",\"scale\":\"y2\"}"));
+
+        let mut p = PieChart::new();
+        p.show_labels(true);
+        p.add_slice("Rent", 1200.0);
+        p.set_right_to_left(true);
+        assert!(p.get_json_representation().contains("\"dir\":{\"value\":\"rtl\"}"));
+        p.set_right_to_left(false);
+        assert!(p.get_json_representation().contains("\"dir\":{\"value\":\"ltr\"}"));
+    }
+    #[test]
+    fn test_qq_plot() {
+        let mut q = QQPlot::new();
+        for i in 0..50 {
+            q.add_data((i * i) as f64);
+        }
+        assert!(q.get_json_representation().contains("quantileNormal(datum.prob, 0, 1)"));
+        for i in 0..50 {
+            q.add_reference_data(i as f64);
+        }
+        let json = q.get_json_representation();
+        assert!(json.contains("\"from\":\"reference_quantiles\""));
+        assert!(!json.contains("quantileNormal"));
+        render_graph(&q, FileType::HTML).unwrap();
+    }
 
 }
@@ -0,0 +1,110 @@
+/*!
+Helpers for snapshot testing the charts a crate generates.
+
+A golden file holds the normalized specification of a chart as it is expected to look. Tests
+compare freshly generated charts against it, so any change to the output shows up as a failing
+test. Setting the environment variable `GUST_UPDATE_GOLDEN` rewrites the golden files instead,
+after a change to the output was intended.
+
+```rust,no_run
+use gust::backend::bar_chart::BarChart;
+use gust::testing::assert_golden;
+
+let mut b = BarChart::new();
+b.add_data(String::from("A"), 3);
+assert_golden(&b, "tests/golden/bar_chart.json");
+```
+ */
+
+use backend::traits::Graphable;
+use serde_json;
+use serde_json::{Map, Value};
+use std;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Fields that change between runs without the chart changing, like build information kept in
+/// the usermeta block, and are therefore left out of normalized specifications
+pub const VOLATILE_FIELDS: [&'static str; 1] = ["usermeta"];
+
+/// Sorts the keys of all objects in `value`, and removes the top level fields in `strip`
+fn sorted(value: Value, strip: &[&str]) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(String, Value)> = object
+                .into_iter()
+                .filter(|&(ref k, _)| !strip.contains(&k.as_str()))
+                .collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            let mut normalized = Map::new();
+            for (k, v) in entries {
+                normalized.insert(k, sorted(v, &[]));
+            }
+            Value::Object(normalized)
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(|v| sorted(v, &[])).collect()),
+        value => value,
+    }
+}
+
+/// Normalizes a specification for comparison: keys are sorted, the fields in
+/// `VOLATILE_FIELDS` are removed, and the result is pretty printed so differences are easy to
+/// spot line by line.
+pub fn normalize(spec: &str) -> Result<String, serde_json::Error> {
+    normalize_without(spec, &VOLATILE_FIELDS)
+}
+
+/// Normalizes a specification like `normalize`, but removes the given top level fields instead
+/// of the default volatile ones
+pub fn normalize_without(spec: &str, strip: &[&str]) -> Result<String, serde_json::Error> {
+    let value: Value = serde_json::from_str(spec)?;
+    serde_json::to_string_pretty(&sorted(value, strip))
+}
+
+/// Whether the normalized specification of `graph` matches the golden file at `path`
+pub fn matches_golden<T: Graphable, P: AsRef<Path>>(
+    graph: &T,
+    path: P,
+) -> Result<bool, std::io::Error> {
+    let expected = normalize(&fs::read_to_string(path)?)?;
+    Ok(normalize(&graph.get_json_representation())? == expected)
+}
+
+/// Panics if the normalized specification of `graph` differs from the golden file at `path`,
+/// pointing at the first line that differs. The golden file is written instead if it doesn't
+/// exist yet, or if `GUST_UPDATE_GOLDEN` is set.
+pub fn assert_golden<T: Graphable, P: AsRef<Path>>(graph: &T, path: P) {
+    let path = path.as_ref();
+    let actual = normalize(&graph.get_json_representation()).unwrap();
+
+    if env::var_os("GUST_UPDATE_GOLDEN").is_some() || !path.exists() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(path, actual + "\n").unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(path).unwrap();
+    let expected = normalize(&expected)
+        .unwrap_or_else(|e| panic!("golden file {} is not valid json: {}", path.display(), e));
+    if actual == expected {
+        return;
+    }
+    let (line, (want, got)) = expected
+        .lines()
+        .chain(std::iter::repeat(""))
+        .zip(actual.lines().chain(std::iter::repeat("")))
+        .enumerate()
+        .find(|&(_, (want, got))| want != got)
+        .unwrap();
+    panic!(
+        "chart differs from golden file {} at line {}:\n  expected: {}\n  actual:   {}\n\
+         (set GUST_UPDATE_GOLDEN to update the golden file)",
+        path.display(),
+        line + 1,
+        want,
+        got
+    );
+}